@@ -2,4 +2,6 @@ pub mod rs_base_graph_native;
 pub mod rs_digraph_native;
 pub mod rs_weighted_graph_native;
 pub mod rs_weighted_digraph_native;
-pub mod rs_hypergraph_native;
\ No newline at end of file
+pub mod rs_hypergraph_native;
+pub mod rs_graph_format;
+pub mod rs_graph_algorithms;
\ No newline at end of file
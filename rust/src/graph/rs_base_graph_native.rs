@@ -4,6 +4,8 @@ use pyo3::prelude::*;
 use pyo3::types::PyList;
 use pyo3::PyObject;
 use rustc_hash::{FxHashMap, FxHashSet};
+use crate::graph::rs_graph_format::{decode_payload, encode_payload, GraphData};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -24,7 +26,7 @@ impl GraphNode {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct BaseGraph {
     nodes: FxHashMap<usize, GraphNode>,
     next_id: usize,
@@ -39,6 +41,167 @@ impl BaseGraph {
             }
         }
     }
+
+    fn sorted_neighbours(&self, id: usize) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = match self.nodes.get(&id) {
+            Some(node) => node.neighbours.iter().copied().collect(),
+            None => Vec::new(),
+        };
+
+        neighbours.sort_unstable();
+        neighbours
+    }
+
+    fn collect_component(&self, start_id: usize, visited: &mut FxHashSet<usize>) -> Vec<usize> {
+        let mut component = Vec::new();
+        let mut id_queue = VecDeque::new();
+
+        visited.insert(start_id);
+        id_queue.push_back(start_id);
+
+        while let Some(current_id) = id_queue.pop_front() {
+            component.push(current_id);
+
+            if let Some(node) = self.nodes.get(&current_id) {
+                for neigh_id in node.neighbours.iter() {
+                    if visited.insert(*neigh_id) {
+                        id_queue.push_back(*neigh_id);
+                    }
+                }
+            }
+        }
+
+        component.sort_unstable();
+        component
+    }
+
+    // Iterative Hopcroft-Tarjan low-link DFS, shared by bridges() & articulation_points().
+    fn low_link_scan(&self) -> (Vec<(usize, usize)>, Vec<usize>) {
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut timer = 0;
+        let mut discovery: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut low_link: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut bridges = Vec::new();
+        let mut cut_nodes = FxHashSet::default();
+
+        for root_id in ids {
+            if discovery.contains_key(&root_id) {
+                continue;
+            }
+
+            let mut root_children = 0;
+            let mut work: Vec<(usize, Option<usize>, Vec<usize>, usize)> = Vec::new();
+
+            discovery.insert(root_id, timer);
+            low_link.insert(root_id, timer);
+            timer += 1;
+            work.push((root_id, None, self.sorted_neighbours(root_id), 0));
+
+            while let Some((current_id, parent_id, neighbours, position)) = work.last_mut() {
+                let current_id = *current_id;
+                let parent_id = *parent_id;
+
+                if *position < neighbours.len() {
+                    let next_id = neighbours[*position];
+                    *position += 1;
+
+                    if next_id == current_id || Some(next_id) == parent_id {
+                        continue;
+                    }
+
+                    match discovery.get(&next_id) {
+                        Some(&seen_time) => {
+                            let lowest = low_link[&current_id].min(seen_time);
+                            low_link.insert(current_id, lowest);
+                        },
+                        None => {
+                            if current_id == root_id {
+                                root_children += 1;
+                            }
+                            discovery.insert(next_id, timer);
+                            low_link.insert(next_id, timer);
+                            timer += 1;
+                            work.push((next_id, Some(current_id), self.sorted_neighbours(next_id), 0));
+                        },
+                    }
+                    continue;
+                }
+
+                work.pop();
+
+                if let Some(parent_id) = parent_id {
+                    let lowest = low_link[&parent_id].min(low_link[&current_id]);
+                    low_link.insert(parent_id, lowest);
+
+                    if low_link[&current_id] > discovery[&parent_id] {
+                        bridges.push((parent_id.min(current_id), parent_id.max(current_id)));
+                    }
+
+                    if parent_id != root_id && low_link[&current_id] >= discovery[&parent_id] {
+                        cut_nodes.insert(parent_id);
+                    }
+                }
+            }
+
+            if root_children > 1 {
+                cut_nodes.insert(root_id);
+            }
+        }
+
+        bridges.sort_unstable();
+        let mut cut_nodes: Vec<usize> = cut_nodes.into_iter().collect();
+        cut_nodes.sort_unstable();
+
+        (bridges, cut_nodes)
+    }
+
+    fn insert_with_id(&mut self, id: usize, payload: PyObject) {
+        let new_node = GraphNode::new(id, payload);
+
+        self.nodes.insert(id, new_node);
+    }
+
+    fn export_data(&self, py: Python, encoder: &Option<PyObject>) -> PyResult<GraphData> {
+        let mut data = GraphData::new(false, false);
+
+        for (id, node) in self.nodes.iter() {
+            data.nodes.push((*id, Some(encode_payload(py, &node.payload, encoder)?)));
+
+            // Undirected edges are stored on both nodes, so only export the lower-to-higher copy.
+            for neigh_id in node.neighbours.iter() {
+                if id <= neigh_id {
+                    data.edges.push((*id, *neigh_id, 1.0));
+                }
+            }
+        }
+
+        data.sort();
+        Ok(data)
+    }
+
+    fn import_data(py: Python, data: GraphData, decoder: &Option<PyObject>) -> PyResult<Self> {
+        data.expect_pairwise(false)?;
+        data.check_nodes()?;
+
+        let mut graph = BaseGraph::new();
+        for (id, payload) in data.nodes.iter() {
+            graph.insert_with_id(*id, decode_payload(py, payload, decoder)?);
+            graph.next_id = graph.next_id.max(id + 1);
+        }
+        graph.count = graph.nodes.len();
+
+        for (from_id, to_id, _) in data.edges.iter() {
+            let from_node = graph.nodes.get_mut(from_id).expect("From ID node not found!");
+            from_node.neighbours.insert(*to_id);
+
+            let to_node = graph.nodes.get_mut(to_id).expect("To ID node not found!");
+            to_node.neighbours.insert(*from_id);
+        }
+
+        Ok(graph)
+    }
 }
 
 #[pymethods]
@@ -389,6 +552,96 @@ impl BaseGraph {
         Ok(false)
     }
 
+    pub fn connected_components<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut visited = FxHashSet::default();
+        let mut results = Vec::new();
+
+        for start_id in ids {
+            if !visited.contains(&start_id) {
+                let component = self.collect_component(start_id, &mut visited);
+                results.push(PyList::new(py, component).to_object(py));
+            }
+        }
+
+        Ok(PyList::new(py, results))
+    }
+
+    pub fn component_of<'py>(&self, py: Python<'py>, id: usize) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&id) {
+            return Err(PyValueError::new_err("ID value not found in Graph"));
+        }
+
+        let mut visited = FxHashSet::default();
+        let component = self.collect_component(id, &mut visited);
+        Ok(PyList::new(py, component))
+    }
+
+    pub fn bridges<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let (bridges, _) = self.low_link_scan();
+        Ok(PyList::new(py, bridges))
+    }
+
+    pub fn articulation_points<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let (_, cut_nodes) = self.low_link_scan();
+        Ok(PyList::new(py, cut_nodes))
+    }
+
+    pub fn to_edge_list(&self, py: Python) -> PyResult<String> {
+        Ok(self.export_data(py, &None)?.to_edge_list())
+    }
+
+    #[staticmethod]
+    pub fn from_edge_list(py: Python, text: &str) -> PyResult<Self> {
+        let data = GraphData::from_edge_list(text, false, false, false)?;
+        Self::import_data(py, data, &None)
+    }
+
+    pub fn to_dot(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_dot())
+    }
+
+    #[staticmethod]
+    pub fn from_dot(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_dot(text)?, &decoder)
+    }
+
+    pub fn to_graphml(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_graphml())
+    }
+
+    #[staticmethod]
+    pub fn from_graphml(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_graphml(py, text)?, &decoder)
+    }
+
+    pub fn to_json(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        self.export_data(py, &encoder)?.to_json(py)
+    }
+
+    #[staticmethod]
+    pub fn from_json(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_json(py, text)?, &decoder)
+    }
+
     pub fn is_empty(&self) -> PyResult<bool> {
         if self.count == 0 {
             Ok(true)
@@ -411,4 +664,63 @@ impl BaseGraph {
         self.count = 0;
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut nodes = Vec::with_capacity(ids.len());
+        for id in ids {
+            let node = &self.nodes[&id];
+            let mut neighbours: Vec<usize> = node.neighbours.iter().copied().collect();
+            neighbours.sort_unstable();
+            nodes.push((id, node.payload.clone_ref(py), neighbours).to_object(py));
+        }
+        Ok((STATE_VERSION, self.next_id, self.count, nodes).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "BaseGraph", 3)?;
+        let next_id: usize = state.get_item(1)?.extract()?;
+        let count: usize = state.get_item(2)?.extract()?;
+        let entries: Vec<(usize, PyObject, Vec<usize>)> = state.get_item(3)?.extract()?;
+
+        let mut nodes = FxHashMap::default();
+        for (id, payload, neighbours) in entries {
+            if id >= next_id {
+                return Err(invalid_state("BaseGraph", "node id beyond next_id"));
+            }
+            let mut node = GraphNode::new(id, payload);
+            node.neighbours = neighbours.into_iter().collect();
+            nodes.insert(id, node);
+        }
+
+        for node in nodes.values() {
+            if node.neighbours.iter().any(|other| !nodes.contains_key(other)) {
+                return Err(invalid_state("BaseGraph", "edge refers to an unknown node"));
+            }
+        }
+
+        self.nodes = nodes;
+        self.next_id = next_id;
+        self.count = count;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = BaseGraph::new();
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, BaseGraph::new())?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
@@ -1,9 +1,13 @@
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 use pyo3::{exceptions::PyValueError, types::PyTuple};
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 use pyo3::PyObject;
 use rustc_hash::{FxHashMap, FxHashSet};
+use crate::graph::rs_graph_algorithms as graph_algorithms;
+use crate::graph::rs_graph_format::{decode_payload, encode_payload, GraphData};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -24,7 +28,7 @@ impl DigraphNode {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct Digraph {
     nodes: FxHashMap<usize, DigraphNode>,
     next_id: usize,
@@ -65,6 +69,49 @@ impl Digraph {
         rec_stack.remove(&node_id);
         false
     }
+
+    fn sorted_neighbours(&self, id: usize) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = match self.nodes.get(&id) {
+            Some(node) => node.neighbours.iter().copied().collect(),
+            None => Vec::new(),
+        };
+
+        neighbours.sort_unstable();
+        neighbours
+    }
+
+    fn export_data(&self, py: Python, encoder: &Option<PyObject>) -> PyResult<GraphData> {
+        let mut data = GraphData::new(true, false);
+
+        for (id, node) in self.nodes.iter() {
+            data.nodes.push((*id, Some(encode_payload(py, &node.payload, encoder)?)));
+            for neigh_id in node.neighbours.iter() {
+                data.edges.push((*id, *neigh_id, 1.0));
+            }
+        }
+
+        data.sort();
+        Ok(data)
+    }
+
+    fn import_data(py: Python, data: GraphData, decoder: &Option<PyObject>) -> PyResult<Self> {
+        data.expect_pairwise(true)?;
+        data.check_nodes()?;
+
+        let mut graph = Digraph::new();
+        for (id, payload) in data.nodes.iter() {
+            graph.insert_with_id(*id, decode_payload(py, payload, decoder)?);
+            graph.next_id = graph.next_id.max(id + 1);
+        }
+        graph.count = graph.nodes.len();
+
+        for (from_id, to_id, _) in data.edges.iter() {
+            let from_node = graph.nodes.get_mut(from_id).expect("From ID node not found!");
+            from_node.neighbours.insert(*to_id);
+        }
+
+        Ok(graph)
+    }
 }
 
 #[pymethods]
@@ -459,6 +506,133 @@ impl Digraph {
         return Ok(false);
     }
 
+    pub fn topological_sort<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let mut in_degree: FxHashMap<usize, usize> = self.nodes.keys().map(|id| (*id, 0)).collect();
+        for (_, node) in self.nodes.iter() {
+            for to_id in node.neighbours.iter() {
+                *in_degree.get_mut(to_id).expect("Edge target not found!") += 1;
+            }
+        }
+
+        // Kahn's algorithm, always emitting the smallest ready ID for a stable ordering.
+        let mut ready: BinaryHeap<Reverse<usize>> = in_degree.iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| Reverse(*id))
+            .collect();
+        let mut results = Vec::with_capacity(self.nodes.len());
+
+        while let Some(Reverse(current_id)) = ready.pop() {
+            results.push(current_id);
+
+            for next_id in self.sorted_neighbours(current_id) {
+                let degree = in_degree.get_mut(&next_id).expect("Edge target not found!");
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(Reverse(next_id));
+                }
+            }
+        }
+
+        if results.len() < self.nodes.len() {
+            let cycle = graph_algorithms::find_cycle(self.nodes.keys().copied().collect(), |id| self.sorted_neighbours(id)).unwrap_or_default();
+            let cycle_text: Vec<String> = cycle.iter().map(|id| id.to_string()).collect();
+            return Err(PyValueError::new_err(format!("Graph contains a cycle: {}", cycle_text.join(" -> "))));
+        }
+
+        Ok(PyList::new(py, results))
+    }
+
+    pub fn strongly_connected_components<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let mut results = Vec::new();
+        for component in graph_algorithms::tarjan_components(self.nodes.keys().copied().collect(), |id| self.sorted_neighbours(id)) {
+            results.push(PyList::new(py, component).to_object(py));
+        }
+
+        Ok(PyList::new(py, results))
+    }
+
+    pub fn condensation(&self, py: Python) -> PyResult<PyObject> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let components = graph_algorithms::tarjan_components(self.nodes.keys().copied().collect(), |id| self.sorted_neighbours(id));
+        let mut component_of: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut new_digraph = Digraph::new();
+
+        // Component IDs follow topological order and each payload lists the member node IDs.
+        for (index, component) in components.iter().enumerate() {
+            let component_id = index + 1;
+            for member_id in component.iter() {
+                component_of.insert(*member_id, component_id);
+            }
+            new_digraph.insert_with_id(component_id, PyList::new(py, component).to_object(py));
+        }
+
+        new_digraph.next_id = components.len() + 1;
+        new_digraph.count = components.len();
+
+        for (from_id, node) in self.nodes.iter() {
+            let from_component = component_of[from_id];
+            for to_id in node.neighbours.iter() {
+                let to_component = component_of[to_id];
+                if from_component == to_component {
+                    continue;
+                }
+
+                let entry = new_digraph.nodes.get_mut(&from_component).expect("Component node not found!");
+                entry.neighbours.insert(to_component);
+            }
+        }
+
+        Py::new(py, new_digraph).map(|py_object| py_object.into_py(py))
+    }
+
+    pub fn to_edge_list(&self, py: Python) -> PyResult<String> {
+        Ok(self.export_data(py, &None)?.to_edge_list())
+    }
+
+    #[staticmethod]
+    pub fn from_edge_list(py: Python, text: &str) -> PyResult<Self> {
+        let data = GraphData::from_edge_list(text, true, false, false)?;
+        Self::import_data(py, data, &None)
+    }
+
+    pub fn to_dot(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_dot())
+    }
+
+    #[staticmethod]
+    pub fn from_dot(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_dot(text)?, &decoder)
+    }
+
+    pub fn to_graphml(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_graphml())
+    }
+
+    #[staticmethod]
+    pub fn from_graphml(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_graphml(py, text)?, &decoder)
+    }
+
+    pub fn to_json(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        self.export_data(py, &encoder)?.to_json(py)
+    }
+
+    #[staticmethod]
+    pub fn from_json(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_json(py, text)?, &decoder)
+    }
+
     pub fn is_empty(&self) -> PyResult<bool> {
         if self.count == 0 {
             Ok(true)
@@ -481,4 +655,63 @@ impl Digraph {
         self.count = 0;
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut nodes = Vec::with_capacity(ids.len());
+        for id in ids {
+            let node = &self.nodes[&id];
+            let mut neighbours: Vec<usize> = node.neighbours.iter().copied().collect();
+            neighbours.sort_unstable();
+            nodes.push((id, node.payload.clone_ref(py), neighbours).to_object(py));
+        }
+        Ok((STATE_VERSION, self.next_id, self.count, nodes).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "Digraph", 3)?;
+        let next_id: usize = state.get_item(1)?.extract()?;
+        let count: usize = state.get_item(2)?.extract()?;
+        let entries: Vec<(usize, PyObject, Vec<usize>)> = state.get_item(3)?.extract()?;
+
+        let mut nodes = FxHashMap::default();
+        for (id, payload, neighbours) in entries {
+            if id >= next_id {
+                return Err(invalid_state("Digraph", "node id beyond next_id"));
+            }
+            let mut node = DigraphNode::new(id, payload);
+            node.neighbours = neighbours.into_iter().collect();
+            nodes.insert(id, node);
+        }
+
+        for node in nodes.values() {
+            if node.neighbours.iter().any(|other| !nodes.contains_key(other)) {
+                return Err(invalid_state("Digraph", "edge refers to an unknown node"));
+            }
+        }
+
+        self.nodes = nodes;
+        self.next_id = next_id;
+        self.count = count;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = Digraph::new();
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, Digraph::new())?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,284 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+// ---------------------------------------------------------------------------------
+// Traversal algorithms shared by the graph classes, driven through neighbour closures
+// ---------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+struct PathState {
+    cost: f64,
+    id: usize,
+}
+
+// Reversed ordering turns the std BinaryHeap into a min-heap on cost.
+impl Ord for PathState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl PartialOrd for PathState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PathState {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PathState {}
+
+pub fn check_non_negative<W>(weights: W) -> PyResult<()>
+where
+    W: IntoIterator<Item = f64>,
+{
+    for weight in weights {
+        // NaN fails every comparison, so it has to be rejected explicitly alongside negative weights.
+        if weight.is_nan() || weight < 0.0 {
+            return Err(PyValueError::new_err("Dijkstra requires non-negative, non-NaN edge weights"));
+        }
+    }
+    Ok(())
+}
+
+// Returns (distances, previous) for every node settled before `end_id`, or for all reachable nodes.
+pub fn dijkstra<F, I>(start_id: usize, end_id: Option<usize>, neighbours: F) -> (FxHashMap<usize, f64>, FxHashMap<usize, usize>)
+where
+    F: Fn(usize) -> I,
+    I: IntoIterator<Item = (usize, f64)>,
+{
+    let mut distances: FxHashMap<usize, f64> = FxHashMap::default();
+    let mut previous: FxHashMap<usize, usize> = FxHashMap::default();
+    let mut settled = FxHashSet::default();
+    let mut heap = BinaryHeap::new();
+
+    distances.insert(start_id, 0.0);
+    heap.push(PathState { cost: 0.0, id: start_id });
+
+    while let Some(PathState { cost, id }) = heap.pop() {
+        if !settled.insert(id) {
+            continue;
+        }
+
+        if Some(id) == end_id {
+            break;
+        }
+
+        for (neigh_id, weight) in neighbours(id) {
+            let next_cost = cost + weight;
+            let improved = match distances.get(&neigh_id) {
+                Some(&current) => next_cost < current,
+                None => true,
+            };
+
+            if improved {
+                distances.insert(neigh_id, next_cost);
+                previous.insert(neigh_id, id);
+                heap.push(PathState { cost: next_cost, id: neigh_id });
+            }
+        }
+    }
+
+    (distances, previous)
+}
+
+// Closed nodes are never reopened, so `estimate` has to be consistent for the path to be optimal.
+pub fn astar<F, I, H>(start_id: usize, end_id: usize, neighbours: F, mut estimate: H) -> PyResult<Option<(Vec<usize>, f64)>>
+where
+    F: Fn(usize) -> I,
+    I: IntoIterator<Item = (usize, f64)>,
+    H: FnMut(usize) -> PyResult<f64>,
+{
+    let mut g_score: FxHashMap<usize, f64> = FxHashMap::default();
+    let mut previous: FxHashMap<usize, usize> = FxHashMap::default();
+    let mut closed = FxHashSet::default();
+    let mut heap = BinaryHeap::new();
+
+    g_score.insert(start_id, 0.0);
+    heap.push(PathState { cost: estimate(start_id)?, id: start_id });
+
+    while let Some(PathState { id, .. }) = heap.pop() {
+        if id == end_id {
+            let path = rebuild_path(&previous, start_id, end_id);
+            return Ok(Some((path, g_score[&id])));
+        }
+
+        if !closed.insert(id) {
+            continue;
+        }
+
+        let current_cost = g_score[&id];
+
+        for (neigh_id, weight) in neighbours(id) {
+            if closed.contains(&neigh_id) {
+                continue;
+            }
+
+            let tentative = current_cost + weight;
+            let improved = match g_score.get(&neigh_id) {
+                Some(&known) => tentative < known,
+                None => true,
+            };
+
+            if improved {
+                g_score.insert(neigh_id, tentative);
+                previous.insert(neigh_id, id);
+                heap.push(PathState { cost: tentative + estimate(neigh_id)?, id: neigh_id });
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn rebuild_path(previous: &FxHashMap<usize, usize>, start_id: usize, end_id: usize) -> Vec<usize> {
+    let mut path = vec![end_id];
+    let mut current = end_id;
+
+    while current != start_id {
+        current = previous[&current];
+        path.push(current);
+    }
+
+    path.reverse();
+    path
+}
+
+// `successors` must return neighbours in ascending order so the reported cycle is deterministic.
+pub fn find_cycle<F>(mut ids: Vec<usize>, successors: F) -> Option<Vec<usize>>
+where
+    F: Fn(usize) -> Vec<usize>,
+{
+    ids.sort_unstable();
+
+    let mut visited = FxHashSet::default();
+
+    for start_id in ids {
+        if visited.contains(&start_id) {
+            continue;
+        }
+
+        // Iterative DFS where `path` mirrors the recursion stack of a recursive search.
+        let mut path: Vec<usize> = Vec::new();
+        let mut on_path = FxHashSet::default();
+        let mut work: Vec<(usize, Vec<usize>, usize)> = Vec::new();
+
+        visited.insert(start_id);
+        on_path.insert(start_id);
+        path.push(start_id);
+        work.push((start_id, successors(start_id), 0));
+
+        while let Some((_, neighbours, position)) = work.last_mut() {
+            if *position < neighbours.len() {
+                let next_id = neighbours[*position];
+                *position += 1;
+
+                if on_path.contains(&next_id) {
+                    let start = path.iter().position(|id| *id == next_id).expect("Cycle node not on path!");
+                    let mut cycle = path[start..].to_vec();
+                    cycle.push(next_id);
+                    return Some(cycle);
+                }
+
+                if visited.insert(next_id) {
+                    on_path.insert(next_id);
+                    path.push(next_id);
+                    work.push((next_id, successors(next_id), 0));
+                }
+            } else {
+                let (finished_id, _, _) = work.pop().expect("Work stack unexpectedly empty!");
+                on_path.remove(&finished_id);
+                path.pop();
+            }
+        }
+    }
+
+    None
+}
+
+// Tarjan's algorithm, returning components in topological order of the condensation.
+pub fn tarjan_components<F>(mut ids: Vec<usize>, successors: F) -> Vec<Vec<usize>>
+where
+    F: Fn(usize) -> Vec<usize>,
+{
+    ids.sort_unstable();
+
+    let mut next_index = 0;
+    let mut index_of: FxHashMap<usize, usize> = FxHashMap::default();
+    let mut low_link: FxHashMap<usize, usize> = FxHashMap::default();
+    let mut on_stack = FxHashSet::default();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut components: Vec<Vec<usize>> = Vec::new();
+
+    for start_id in ids {
+        if index_of.contains_key(&start_id) {
+            continue;
+        }
+
+        let mut work: Vec<(usize, Vec<usize>, usize)> = Vec::new();
+
+        index_of.insert(start_id, next_index);
+        low_link.insert(start_id, next_index);
+        next_index += 1;
+        stack.push(start_id);
+        on_stack.insert(start_id);
+        work.push((start_id, successors(start_id), 0));
+
+        while let Some((current_id, neighbours, position)) = work.last_mut() {
+            let current_id = *current_id;
+
+            if *position < neighbours.len() {
+                let next_id = neighbours[*position];
+                *position += 1;
+
+                match index_of.get(&next_id) {
+                    Some(&seen_index) => {
+                        if on_stack.contains(&next_id) {
+                            let lowest = low_link[&current_id].min(seen_index);
+                            low_link.insert(current_id, lowest);
+                        }
+                    },
+                    None => {
+                        index_of.insert(next_id, next_index);
+                        low_link.insert(next_id, next_index);
+                        next_index += 1;
+                        stack.push(next_id);
+                        on_stack.insert(next_id);
+                        work.push((next_id, successors(next_id), 0));
+                    },
+                }
+                continue;
+            }
+
+            work.pop();
+
+            if let Some((parent_id, _, _)) = work.last() {
+                let lowest = low_link[parent_id].min(low_link[&current_id]);
+                low_link.insert(*parent_id, lowest);
+            }
+
+            if low_link[&current_id] == index_of[&current_id] {
+                let mut component = Vec::new();
+                while let Some(member_id) = stack.pop() {
+                    on_stack.remove(&member_id);
+                    component.push(member_id);
+                    if member_id == current_id {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    components.reverse();
+    components
+}
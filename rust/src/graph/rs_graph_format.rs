@@ -0,0 +1,750 @@
+use std::fmt::Write;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::PyObject;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+// ---------------------------------------------------------------------------------
+// Shared intermediate representation used by every graph class for import/export
+// ---------------------------------------------------------------------------------
+
+const GRAPHML_NAMESPACE: &str = "http://graphml.graphdrawing.org/xmlns";
+const HYPEREDGE_PREFIX: &str = "edge:";
+
+pub struct GraphData {
+    pub directed: bool,
+    pub weighted: bool,
+    pub hyper: bool,
+    pub nodes: Vec<(usize, Option<String>)>,
+    pub edges: Vec<(usize, usize, f64)>,
+    pub hyperedges: Vec<(String, Vec<usize>)>,
+}
+
+pub fn encode_payload(py: Python, payload: &PyObject, encoder: &Option<PyObject>) -> PyResult<String> {
+    match encoder {
+        Some(function) => function.call1(py, (payload.clone_ref(py),))?.extract(py),
+        None => payload.as_ref(py).str()?.extract(),
+    }
+}
+
+pub fn decode_payload(py: Python, text: &Option<String>, decoder: &Option<PyObject>) -> PyResult<PyObject> {
+    match (text, decoder) {
+        (Some(value), Some(function)) => function.call1(py, (value.as_str(),)),
+        (Some(value), None) => Ok(value.to_object(py)),
+        (None, _) => Ok(py.None()),
+    }
+}
+
+fn escape_quoted(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn parse_node_id(text: &str) -> PyResult<usize> {
+    text.parse::<usize>().map_err(|_| PyValueError::new_err(format!("Invalid node ID '{}' in serialized graph", text)))
+}
+
+fn parse_weight(text: &str) -> PyResult<f64> {
+    text.parse::<f64>().map_err(|_| PyValueError::new_err(format!("Invalid edge weight '{}' in serialized graph", text)))
+}
+
+impl GraphData {
+    pub fn new(directed: bool, weighted: bool) -> Self {
+        Self {
+            directed,
+            weighted,
+            hyper: false,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            hyperedges: Vec::new(),
+        }
+    }
+
+    pub fn expect_pairwise(&self, directed: bool) -> PyResult<()> {
+        if self.hyper {
+            return Err(PyValueError::new_err("Serialized graph holds hyperedges and can only be loaded as a HyperGraph"));
+        }
+
+        if self.directed != directed {
+            let found = if self.directed { "directed" } else { "undirected" };
+            return Err(PyValueError::new_err(format!("Serialized graph is {}, which does not match the target Graph", found)));
+        }
+        Ok(())
+    }
+
+    pub fn expect_hyper(&self) -> PyResult<()> {
+        if !self.edges.is_empty() {
+            return Err(PyValueError::new_err("Serialized graph holds pairwise edges and cannot be loaded as a HyperGraph"));
+        }
+        Ok(())
+    }
+
+    pub fn check_nodes(&self) -> PyResult<()> {
+        let known: FxHashSet<usize> = self.nodes.iter().map(|(id, _)| *id).collect();
+        let edge_ids = self.edges.iter().flat_map(|(from_id, to_id, _)| [*from_id, *to_id]);
+        let member_ids = self.hyperedges.iter().flat_map(|(_, members)| members.iter().copied());
+
+        for id in edge_ids.chain(member_ids) {
+            if !known.contains(&id) {
+                return Err(PyValueError::new_err(format!("Serialized edge references unknown node ID {}", id)));
+            }
+        }
+        Ok(())
+    }
+
+    // Sorting keeps exports stable across runs, regardless of hash map iteration order.
+    pub fn sort(&mut self) {
+        self.nodes.sort_by_key(|(id, _)| *id);
+        self.edges.sort_by_key(|(from_id, to_id, _)| (*from_id, *to_id));
+        self.hyperedges.sort_by(|a, b| a.0.cmp(&b.0));
+        for (_, members) in self.hyperedges.iter_mut() {
+            members.sort_unstable();
+        }
+    }
+
+    // ---------------------------------------------------------------------------------
+    // Edge list: "from to [weight]" per edge, lone IDs for isolated nodes, no payloads
+    // ---------------------------------------------------------------------------------
+
+    pub fn to_edge_list(&self) -> String {
+        let mut output = String::new();
+
+        let mut linked = vec![false; self.nodes.len()];
+        let positions: FxHashMap<usize, usize> = self.nodes.iter().enumerate().map(|(index, (id, _))| (*id, index)).collect();
+
+        // Hyperedge lines always carry a TAB, so lone node IDs stay distinguishable from empty hyperedges.
+        for (edge_id, members) in self.hyperedges.iter() {
+            for id in members.iter() {
+                if let Some(index) = positions.get(id) {
+                    linked[*index] = true;
+                }
+            }
+            let member_text: Vec<String> = members.iter().map(|id| id.to_string()).collect();
+            let _ = writeln!(output, "{}\t{}", edge_id, member_text.join(" "));
+        }
+
+        for (from_id, to_id, weight) in self.edges.iter() {
+            for id in [from_id, to_id] {
+                if let Some(index) = positions.get(id) {
+                    linked[*index] = true;
+                }
+            }
+
+            if self.weighted {
+                let _ = writeln!(output, "{} {} {:?}", from_id, to_id, weight);
+            } else {
+                let _ = writeln!(output, "{} {}", from_id, to_id);
+            }
+        }
+
+        for ((id, _), has_edge) in self.nodes.iter().zip(linked) {
+            if !has_edge {
+                let _ = writeln!(output, "{}", id);
+            }
+        }
+
+        output
+    }
+
+    pub fn from_edge_list(text: &str, directed: bool, weighted: bool, hyper: bool) -> PyResult<Self> {
+        let mut data = GraphData::new(directed, weighted);
+        data.hyper = hyper;
+        let mut seen: FxHashSet<usize> = FxHashSet::default();
+        let mut remember = |id: usize, data: &mut GraphData| {
+            if seen.insert(id) {
+                data.nodes.push((id, None));
+            }
+        };
+
+        for raw_line in text.lines() {
+            // Only the line ending is stripped from the raw line -> the TAB of an empty hyperedge survives.
+            let raw_line = raw_line.trim_end_matches(['\r', '\n']);
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // Hyperedge lines are "edge_id<TAB>members", so edge IDs may contain spaces.
+            // Lines without a TAB are either a lone node ID or a hand-written "edge_id members" line.
+            if hyper {
+                let (edge_id, member_text) = match raw_line.split_once('\t') {
+                    Some(parts) => parts,
+                    None => match line.split_once(char::is_whitespace) {
+                        Some(parts) => parts,
+                        None => {
+                            remember(parse_node_id(line)?, &mut data);
+                            continue;
+                        }
+                    },
+                };
+
+                let mut members = Vec::new();
+                for token in member_text.split_whitespace() {
+                    let id = parse_node_id(token)?;
+                    remember(id, &mut data);
+                    members.push(id);
+                }
+                data.hyperedges.push((edge_id.trim().to_string(), members));
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            match tokens.len() {
+                1 => remember(parse_node_id(tokens[0])?, &mut data),
+                2 | 3 => {
+                    let from_id = parse_node_id(tokens[0])?;
+                    let to_id = parse_node_id(tokens[1])?;
+                    let weight = match tokens.get(2) {
+                        Some(value) => parse_weight(value)?,
+                        None => 1.0,
+                    };
+                    remember(from_id, &mut data);
+                    remember(to_id, &mut data);
+                    data.edges.push((from_id, to_id, weight));
+                },
+                _ => return Err(PyValueError::new_err(format!("Invalid edge list line: '{}'", line))),
+            }
+        }
+
+        Ok(data)
+    }
+
+    // ---------------------------------------------------------------------------------
+    // DOT (Graphviz): payloads become labels, hyperedges become "edge:<id>" box nodes
+    // ---------------------------------------------------------------------------------
+
+    pub fn to_dot(&self) -> String {
+        let mut output = String::new();
+        let (keyword, connector) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+
+        let _ = writeln!(output, "{} {{", keyword);
+
+        for (id, payload) in self.nodes.iter() {
+            match payload {
+                Some(label) => { let _ = writeln!(output, "    {} [label=\"{}\"];", id, escape_quoted(label)); },
+                None => { let _ = writeln!(output, "    {};", id); },
+            }
+        }
+
+        for (from_id, to_id, weight) in self.edges.iter() {
+            if self.weighted {
+                let _ = writeln!(output, "    {} {} {} [weight={:?}];", from_id, connector, to_id, weight);
+            } else {
+                let _ = writeln!(output, "    {} {} {};", from_id, connector, to_id);
+            }
+        }
+
+        for (edge_id, members) in self.hyperedges.iter() {
+            let dot_id = escape_quoted(&format!("{}{}", HYPEREDGE_PREFIX, edge_id));
+            let _ = writeln!(output, "    \"{}\" [shape=box, label=\"{}\"];", dot_id, escape_quoted(edge_id));
+            for member_id in members.iter() {
+                let _ = writeln!(output, "    \"{}\" {} {};", dot_id, connector, member_id);
+            }
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    pub fn from_dot(text: &str) -> PyResult<Self> {
+        let tokens = DotToken::tokenize(text)?;
+        let mut cursor = 0;
+
+        let next = |cursor: &mut usize| -> Option<DotToken> {
+            let token = tokens.get(*cursor).cloned();
+            *cursor += 1;
+            token
+        };
+
+        let mut header = next(&mut cursor);
+        if matches!(&header, Some(DotToken::Word(word)) if word.eq_ignore_ascii_case("strict")) {
+            header = next(&mut cursor);
+        }
+
+        let directed = match header {
+            Some(DotToken::Word(word)) if word.eq_ignore_ascii_case("digraph") => true,
+            Some(DotToken::Word(word)) if word.eq_ignore_ascii_case("graph") => false,
+            _ => return Err(PyValueError::new_err("DOT input must start with 'graph' or 'digraph'")),
+        };
+
+        match tokens.get(cursor) {
+            Some(DotToken::Symbol('{')) => cursor += 1,
+            Some(DotToken::Word(_)) if tokens.get(cursor + 1) == Some(&DotToken::Symbol('{')) => cursor += 2,
+            _ => return Err(PyValueError::new_err("DOT input is missing the opening '{'")),
+        }
+
+        let mut dot_nodes: Vec<(String, Option<String>)> = Vec::new();
+        let mut dot_edges: Vec<(String, String, Option<String>)> = Vec::new();
+        let mut closed = false;
+
+        while cursor < tokens.len() {
+            let token = tokens[cursor].clone();
+            cursor += 1;
+
+            let first = match token {
+                DotToken::Symbol('}') => {
+                    closed = true;
+                    break;
+                },
+                DotToken::Symbol(';') | DotToken::Symbol(',') => continue,
+                DotToken::Word(word) => word,
+                DotToken::Quoted(word) => word,
+                _ => return Err(PyValueError::new_err("Unexpected symbol in DOT statement")),
+            };
+
+            if first == "subgraph" {
+                return Err(PyValueError::new_err("DOT subgraphs are not supported"));
+            }
+
+            // Default attribute statements (graph/node/edge [..]) carry nothing we store.
+            if matches!(first.as_str(), "graph" | "node" | "edge") && tokens.get(cursor) == Some(&DotToken::Symbol('[')) {
+                DotToken::read_attributes(&tokens, &mut cursor)?;
+                continue;
+            }
+
+            if tokens.get(cursor) == Some(&DotToken::Symbol('=')) {
+                cursor += 2;
+                continue;
+            }
+
+            let mut chain = vec![first];
+            while let Some(DotToken::Connector(_)) = tokens.get(cursor) {
+                match tokens.get(cursor + 1) {
+                    Some(DotToken::Word(word)) | Some(DotToken::Quoted(word)) => chain.push(word.clone()),
+                    _ => return Err(PyValueError::new_err("DOT edge is missing its target node")),
+                }
+                cursor += 2;
+            }
+
+            let attributes = if tokens.get(cursor) == Some(&DotToken::Symbol('[')) {
+                DotToken::read_attributes(&tokens, &mut cursor)?
+            } else {
+                FxHashMap::default()
+            };
+
+            if chain.len() == 1 {
+                let label = attributes.get("label").cloned();
+                match dot_nodes.iter_mut().find(|(id, _)| *id == chain[0]) {
+                    Some(existing) => existing.1 = label.or(existing.1.take()),
+                    None => dot_nodes.push((chain[0].clone(), label)),
+                }
+                continue;
+            }
+
+            for pair in chain.windows(2) {
+                for id in pair.iter() {
+                    if !dot_nodes.iter().any(|(known, _)| known == id) {
+                        dot_nodes.push((id.clone(), None));
+                    }
+                }
+                dot_edges.push((pair[0].clone(), pair[1].clone(), attributes.get("weight").cloned()));
+            }
+        }
+
+        if !closed {
+            return Err(PyValueError::new_err("DOT input is missing the closing '}'"));
+        }
+
+        let mut data = GraphData::new(directed, false);
+        let mut hyper_members: FxHashMap<String, Vec<usize>> = FxHashMap::default();
+
+        for (id, label) in dot_nodes.into_iter() {
+            match id.strip_prefix(HYPEREDGE_PREFIX) {
+                Some(edge_id) => { hyper_members.entry(edge_id.to_string()).or_default(); },
+                None => data.nodes.push((parse_node_id(&id)?, label)),
+            }
+        }
+
+        for (from_id, to_id, weight) in dot_edges.into_iter() {
+            if let Some(edge_id) = from_id.strip_prefix(HYPEREDGE_PREFIX) {
+                hyper_members.entry(edge_id.to_string()).or_default().push(parse_node_id(&to_id)?);
+                continue;
+            }
+
+            let weight = match weight {
+                Some(value) => {
+                    data.weighted = true;
+                    parse_weight(&value)?
+                },
+                None => 1.0,
+            };
+            data.edges.push((parse_node_id(&from_id)?, parse_node_id(&to_id)?, weight));
+        }
+
+        data.hyper = !hyper_members.is_empty();
+        data.hyperedges = hyper_members.into_iter().collect();
+        Ok(data)
+    }
+
+    // ---------------------------------------------------------------------------------
+    // GraphML: written directly, parsed through Python's xml.etree.ElementTree
+    // ---------------------------------------------------------------------------------
+
+    pub fn to_graphml(&self) -> String {
+        let mut output = String::new();
+        let direction = if self.directed { "directed" } else { "undirected" };
+
+        output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(output, "<graphml xmlns=\"{}\">", GRAPHML_NAMESPACE);
+        output.push_str("  <key id=\"payload\" for=\"node\" attr.name=\"payload\" attr.type=\"string\"/>\n");
+        if self.weighted {
+            output.push_str("  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n");
+        }
+        let _ = writeln!(output, "  <graph id=\"G\" edgedefault=\"{}\">", direction);
+
+        for (id, payload) in self.nodes.iter() {
+            match payload {
+                Some(value) => { let _ = writeln!(output, "    <node id=\"{}\"><data key=\"payload\">{}</data></node>", id, escape_xml(value)); },
+                None => { let _ = writeln!(output, "    <node id=\"{}\"/>", id); },
+            }
+        }
+
+        for (from_id, to_id, weight) in self.edges.iter() {
+            if self.weighted {
+                let _ = writeln!(output, "    <edge source=\"{}\" target=\"{}\"><data key=\"weight\">{:?}</data></edge>", from_id, to_id, weight);
+            } else {
+                let _ = writeln!(output, "    <edge source=\"{}\" target=\"{}\"/>", from_id, to_id);
+            }
+        }
+
+        for (edge_id, members) in self.hyperedges.iter() {
+            let _ = writeln!(output, "    <hyperedge id=\"{}\">", escape_xml(edge_id));
+            for member_id in members.iter() {
+                let _ = writeln!(output, "      <endpoint node=\"{}\"/>", member_id);
+            }
+            output.push_str("    </hyperedge>\n");
+        }
+
+        output.push_str("  </graph>\n</graphml>\n");
+        output
+    }
+
+    pub fn from_graphml(py: Python, text: &str) -> PyResult<Self> {
+        let element_tree = py.import("xml.etree.ElementTree")?;
+        let root = element_tree.call_method1("fromstring", (text,))?;
+
+        let local_name = |element: &PyAny| -> PyResult<String> {
+            let tag: String = element.getattr("tag")?.extract()?;
+            Ok(tag.rsplit('}').next().unwrap_or_default().to_string())
+        };
+        let attribute = |element: &PyAny, name: &str| -> PyResult<Option<String>> {
+            element.call_method1("get", (name,))?.extract()
+        };
+
+        // Map GraphML key IDs (e.g. "d0") onto the attribute names they describe.
+        let mut key_names: FxHashMap<String, String> = FxHashMap::default();
+        let mut data = GraphData::new(false, false);
+
+        for element in root.call_method0("iter")?.iter()? {
+            let element = element?;
+            match local_name(element)?.as_str() {
+                "key" => {
+                    if let (Some(id), Some(name)) = (attribute(element, "id")?, attribute(element, "attr.name")?) {
+                        key_names.insert(id, name);
+                    }
+                },
+                "graph" => {
+                    data.directed = attribute(element, "edgedefault")?.as_deref() == Some("directed");
+                },
+                _ => {},
+            }
+        }
+
+        let read_data = |element: &PyAny, wanted: &[&str]| -> PyResult<Option<String>> {
+            for child in element.iter()? {
+                let child = child?;
+                if local_name(child)? != "data" {
+                    continue;
+                }
+                let key = attribute(child, "key")?.unwrap_or_default();
+                let name = key_names.get(&key).cloned().unwrap_or(key);
+                if wanted.contains(&name.as_str()) {
+                    let value: Option<String> = child.getattr("text")?.extract()?;
+                    return Ok(Some(value.unwrap_or_default()));
+                }
+            }
+            Ok(None)
+        };
+
+        for element in root.call_method0("iter")?.iter()? {
+            let element = element?;
+            match local_name(element)?.as_str() {
+                "node" => {
+                    let id = attribute(element, "id")?.ok_or_else(|| PyValueError::new_err("GraphML node is missing its ID"))?;
+                    data.nodes.push((parse_node_id(&id)?, read_data(element, &["payload", "label"])?));
+                },
+                "edge" => {
+                    let source = attribute(element, "source")?.ok_or_else(|| PyValueError::new_err("GraphML edge is missing its source"))?;
+                    let target = attribute(element, "target")?.ok_or_else(|| PyValueError::new_err("GraphML edge is missing its target"))?;
+                    let weight = match read_data(element, &["weight"])? {
+                        Some(value) => {
+                            data.weighted = true;
+                            parse_weight(value.trim())?
+                        },
+                        None => 1.0,
+                    };
+                    data.edges.push((parse_node_id(&source)?, parse_node_id(&target)?, weight));
+                },
+                "hyperedge" => {
+                    let edge_id = attribute(element, "id")?.ok_or_else(|| PyValueError::new_err("GraphML hyperedge is missing its ID"))?;
+                    let mut members = Vec::new();
+                    for child in element.iter()? {
+                        let child = child?;
+                        if local_name(child)? == "endpoint" {
+                            let node_id = attribute(child, "node")?.ok_or_else(|| PyValueError::new_err("GraphML endpoint is missing its node"))?;
+                            members.push(parse_node_id(&node_id)?);
+                        }
+                    }
+                    data.hyper = true;
+                    data.hyperedges.push((edge_id, members));
+                },
+                _ => {},
+            }
+        }
+
+        Ok(data)
+    }
+
+    // ---------------------------------------------------------------------------------
+    // JSON adjacency: built as Python dicts & (de)serialized through Python's json module
+    // ---------------------------------------------------------------------------------
+
+    pub fn to_json(&self, py: Python) -> PyResult<String> {
+        let mut adjacency: FxHashMap<usize, Vec<(usize, f64)>> = FxHashMap::default();
+        for (from_id, to_id, weight) in self.edges.iter() {
+            adjacency.entry(*from_id).or_default().push((*to_id, *weight));
+            if !self.directed {
+                adjacency.entry(*to_id).or_default().push((*from_id, *weight));
+            }
+        }
+
+        let root = PyDict::new(py);
+        root.set_item("directed", self.directed)?;
+        root.set_item("weighted", self.weighted)?;
+
+        let node_list = PyList::empty(py);
+        for (id, payload) in self.nodes.iter() {
+            let entry = PyDict::new(py);
+            entry.set_item("id", id)?;
+            entry.set_item("payload", payload)?;
+
+            let neighbour_list = PyList::empty(py);
+            let mut neighbours = adjacency.remove(id).unwrap_or_default();
+            neighbours.sort_by_key(|(neigh_id, _)| *neigh_id);
+            for (neigh_id, weight) in neighbours {
+                let link = PyDict::new(py);
+                link.set_item("id", neigh_id)?;
+                if self.weighted {
+                    link.set_item("weight", weight)?;
+                }
+                neighbour_list.append(link)?;
+            }
+            entry.set_item("adjacency", neighbour_list)?;
+            node_list.append(entry)?;
+        }
+        root.set_item("nodes", node_list)?;
+
+        if self.hyper {
+            let edge_list = PyList::empty(py);
+            for (edge_id, members) in self.hyperedges.iter() {
+                let entry = PyDict::new(py);
+                entry.set_item("id", edge_id)?;
+                entry.set_item("nodes", members)?;
+                edge_list.append(entry)?;
+            }
+            root.set_item("hyperedges", edge_list)?;
+        }
+
+        py.import("json")?.call_method1("dumps", (root,))?.extract()
+    }
+
+    pub fn from_json(py: Python, text: &str) -> PyResult<Self> {
+        let parsed = py.import("json")?.call_method1("loads", (text,))?;
+        let root: &PyDict = parsed.downcast()?;
+
+        let flag = |name: &str| -> PyResult<bool> {
+            match root.get_item(name) {
+                Some(value) => value.extract(),
+                None => Ok(false),
+            }
+        };
+
+        let mut data = GraphData::new(flag("directed")?, flag("weighted")?);
+
+        if let Some(node_list) = root.get_item("nodes") {
+            for entry in node_list.iter()? {
+                let entry: &PyDict = entry?.downcast()?;
+                let id: usize = entry.get_item("id").ok_or_else(|| PyValueError::new_err("JSON node is missing its ID"))?.extract()?;
+                let payload: Option<String> = match entry.get_item("payload") {
+                    Some(value) => value.extract()?,
+                    None => None,
+                };
+                data.nodes.push((id, payload));
+
+                if let Some(neighbour_list) = entry.get_item("adjacency") {
+                    for link in neighbour_list.iter()? {
+                        let link: &PyDict = link?.downcast()?;
+                        let neigh_id: usize = link.get_item("id").ok_or_else(|| PyValueError::new_err("JSON adjacency entry is missing its ID"))?.extract()?;
+                        let weight: f64 = match link.get_item("weight") {
+                            Some(value) => value.extract()?,
+                            None => 1.0,
+                        };
+
+                        // Undirected adjacency lists every edge from both ends, so keep one copy.
+                        if data.directed || id <= neigh_id {
+                            data.edges.push((id, neigh_id, weight));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(edge_list) = root.get_item("hyperedges") {
+            data.hyper = true;
+            for entry in edge_list.iter()? {
+                let entry: &PyDict = entry?.downcast()?;
+                let edge_id: String = entry.get_item("id").ok_or_else(|| PyValueError::new_err("JSON hyperedge is missing its ID"))?.extract()?;
+                let members: Vec<usize> = match entry.get_item("nodes") {
+                    Some(value) => value.extract()?,
+                    None => Vec::new(),
+                };
+                data.hyperedges.push((edge_id, members));
+            }
+        }
+
+        Ok(data)
+    }
+}
+
+// ---------------------------------------------------------------------------------
+// Minimal DOT tokenizer covering the subset written by GraphData::to_dot
+// ---------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum DotToken {
+    Word(String),
+    Quoted(String),
+    Connector(String),
+    Symbol(char),
+}
+
+impl DotToken {
+    fn tokenize(text: &str) -> PyResult<Vec<DotToken>> {
+        let characters: Vec<char> = text.chars().collect();
+        let mut tokens = Vec::new();
+        let mut index = 0;
+
+        while index < characters.len() {
+            let current = characters[index];
+
+            if current.is_whitespace() {
+                index += 1;
+            } else if current == '/' && characters.get(index + 1) == Some(&'/') || current == '#' {
+                while index < characters.len() && characters[index] != '\n' {
+                    index += 1;
+                }
+            } else if current == '/' && characters.get(index + 1) == Some(&'*') {
+                index += 2;
+                while index + 1 < characters.len() && !(characters[index] == '*' && characters[index + 1] == '/') {
+                    index += 1;
+                }
+                index += 2;
+            } else if current == '-' && matches!(characters.get(index + 1), Some('-') | Some('>')) {
+                tokens.push(DotToken::Connector(characters[index..index + 2].iter().collect()));
+                index += 2;
+            } else if "{}[]=;,".contains(current) {
+                tokens.push(DotToken::Symbol(current));
+                index += 1;
+            } else if current == '"' {
+                let mut value = String::new();
+                index += 1;
+                loop {
+                    match characters.get(index) {
+                        None => return Err(PyValueError::new_err("Unterminated quoted string in DOT input")),
+                        Some('"') => break,
+                        Some('\\') => {
+                            match characters.get(index + 1) {
+                                Some('n') => value.push('\n'),
+                                Some(escaped) => value.push(*escaped),
+                                None => return Err(PyValueError::new_err("Unterminated quoted string in DOT input")),
+                            }
+                            index += 1;
+                        },
+                        Some(other) => value.push(*other),
+                    }
+                    index += 1;
+                }
+                tokens.push(DotToken::Quoted(value));
+                index += 1;
+            } else {
+                let start = index;
+                while index < characters.len() && (characters[index].is_alphanumeric() || "_.-:".contains(characters[index])) {
+                    if characters[index] == '-' && matches!(characters.get(index + 1), Some('-') | Some('>')) {
+                        break;
+                    }
+                    index += 1;
+                }
+                if start == index {
+                    return Err(PyValueError::new_err(format!("Unexpected character '{}' in DOT input", current)));
+                }
+                tokens.push(DotToken::Word(characters[start..index].iter().collect()));
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    fn read_attributes(tokens: &[DotToken], cursor: &mut usize) -> PyResult<FxHashMap<String, String>> {
+        let mut attributes = FxHashMap::default();
+        *cursor += 1;
+
+        loop {
+            match tokens.get(*cursor) {
+                Some(DotToken::Symbol(']')) => {
+                    *cursor += 1;
+                    return Ok(attributes);
+                },
+                Some(DotToken::Symbol(',')) | Some(DotToken::Symbol(';')) => *cursor += 1,
+                Some(DotToken::Word(name)) | Some(DotToken::Quoted(name)) => {
+                    if tokens.get(*cursor + 1) != Some(&DotToken::Symbol('=')) {
+                        return Err(PyValueError::new_err(format!("DOT attribute '{}' is missing a value", name)));
+                    }
+                    match tokens.get(*cursor + 2) {
+                        Some(DotToken::Word(value)) | Some(DotToken::Quoted(value)) => {
+                            attributes.insert(name.clone(), value.clone());
+                        },
+                        _ => return Err(PyValueError::new_err(format!("DOT attribute '{}' is missing a value", name))),
+                    }
+                    *cursor += 3;
+                },
+                _ => return Err(PyValueError::new_err("Unterminated attribute list in DOT input")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GraphData;
+
+    #[test]
+    fn hyper_edge_list_round_trips_empty_edges_with_spaces() {
+        let mut data = GraphData::new(false, false);
+        data.hyper = true;
+        data.nodes = vec![(1, None), (2, None), (3, None)];
+        data.hyperedges = vec![("full edge".to_string(), vec![1, 2]), ("empty edge".to_string(), Vec::new())];
+
+        let text = data.to_edge_list();
+        let restored = GraphData::from_edge_list(&text, false, false, true).unwrap();
+
+        let node_ids: Vec<usize> = restored.nodes.iter().map(|(id, _)| *id).collect();
+        assert_eq!(node_ids, vec![1, 2, 3]);
+        assert_eq!(restored.hyperedges, data.hyperedges);
+        assert_eq!(restored.to_edge_list(), text);
+    }
+}
@@ -1,8 +1,10 @@
 use pyo3::{exceptions::PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyList};
+use pyo3::types::{PyList, PyTuple};
 use pyo3::PyObject;
 use rustc_hash::{FxHashMap, FxHashSet};
+use crate::graph::rs_graph_format::{decode_payload, encode_payload, GraphData};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -47,9 +49,47 @@ impl HyperGraph {
             }
         }
     }
+
+    fn export_data(&self, py: Python, encoder: &Option<PyObject>) -> PyResult<GraphData> {
+        let mut data = GraphData::new(false, false);
+        data.hyper = true;
+
+        for (id, node) in self.nodes.iter() {
+            data.nodes.push((*id, Some(encode_payload(py, &node.payload, encoder)?)));
+        }
+
+        for (edge_id, edge) in self.hyperedges.iter() {
+            data.hyperedges.push((edge_id.clone(), edge.vertices.iter().copied().collect()));
+        }
+
+        data.sort();
+        Ok(data)
+    }
+
+    fn import_data(py: Python, data: GraphData, decoder: &Option<PyObject>) -> PyResult<Self> {
+        data.expect_hyper()?;
+        data.check_nodes()?;
+
+        let mut graph = HyperGraph::new();
+        for (id, payload) in data.nodes.iter() {
+            graph.nodes.insert(*id, HyperNode::new(*id, decode_payload(py, payload, decoder)?));
+            graph.next_id = graph.next_id.max(id + 1);
+        }
+
+        for (edge_id, members) in data.hyperedges.into_iter() {
+            let mut new_edge = HyperEdge::new(edge_id.clone());
+            for node_id in members {
+                new_edge.vertices.insert(node_id);
+                graph.node_to_edge.entry(node_id).or_default().insert(edge_id.clone());
+            }
+            graph.hyperedges.insert(edge_id, new_edge);
+        }
+
+        Ok(graph)
+    }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct HyperGraph {
     nodes: FxHashMap<usize, HyperNode>,
     hyperedges: FxHashMap<String, HyperEdge>,
@@ -339,6 +379,43 @@ impl HyperGraph {
         }
     }
 
+    pub fn to_edge_list(&self, py: Python) -> PyResult<String> {
+        Ok(self.export_data(py, &None)?.to_edge_list())
+    }
+
+    #[staticmethod]
+    pub fn from_edge_list(py: Python, text: &str) -> PyResult<Self> {
+        let data = GraphData::from_edge_list(text, false, false, true)?;
+        Self::import_data(py, data, &None)
+    }
+
+    pub fn to_dot(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_dot())
+    }
+
+    #[staticmethod]
+    pub fn from_dot(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_dot(text)?, &decoder)
+    }
+
+    pub fn to_graphml(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_graphml())
+    }
+
+    #[staticmethod]
+    pub fn from_graphml(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_graphml(py, text)?, &decoder)
+    }
+
+    pub fn to_json(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        self.export_data(py, &encoder)?.to_json(py)
+    }
+
+    #[staticmethod]
+    pub fn from_json(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_json(py, text)?, &decoder)
+    }
+
     pub fn is_empty(&self) -> PyResult<bool> {
         if self.nodes.is_empty() {
             Ok(true)
@@ -372,4 +449,72 @@ impl HyperGraph {
         self.next_id = 1;
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let nodes: Vec<PyObject> = ids
+            .into_iter()
+            .map(|id| (id, self.nodes[&id].payload.clone_ref(py)).to_object(py))
+            .collect();
+
+        let mut edge_ids: Vec<&String> = self.hyperedges.keys().collect();
+        edge_ids.sort_unstable();
+        let mut hyperedges = Vec::with_capacity(edge_ids.len());
+        for edge_id in edge_ids {
+            let mut members: Vec<usize> = self.hyperedges[edge_id].vertices.iter().copied().collect();
+            members.sort_unstable();
+            hyperedges.push((edge_id.as_str(), members).to_object(py));
+        }
+
+        Ok((STATE_VERSION, self.next_id, nodes, hyperedges).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "HyperGraph", 3)?;
+        let next_id: usize = state.get_item(1)?.extract()?;
+        let entries: Vec<(usize, PyObject)> = state.get_item(2)?.extract()?;
+        let edges: Vec<(String, Vec<usize>)> = state.get_item(3)?.extract()?;
+
+        let mut graph = HyperGraph::new();
+        for (id, payload) in entries {
+            if id >= next_id {
+                return Err(invalid_state("HyperGraph", "node id beyond next_id"));
+            }
+            graph.nodes.insert(id, HyperNode::new(id, payload));
+        }
+
+        for (edge_id, members) in edges {
+            let mut new_edge = HyperEdge::new(edge_id.clone());
+            for node_id in members {
+                if !graph.nodes.contains_key(&node_id) {
+                    return Err(invalid_state("HyperGraph", "hyperedge refers to an unknown node"));
+                }
+                new_edge.vertices.insert(node_id);
+                graph.node_to_edge.entry(node_id).or_default().insert(edge_id.clone());
+            }
+            graph.hyperedges.insert(edge_id, new_edge);
+        }
+
+        graph.next_id = next_id;
+        *self = graph;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = HyperGraph::new();
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, HyperGraph::new())?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
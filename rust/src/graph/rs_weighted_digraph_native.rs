@@ -1,9 +1,16 @@
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 use pyo3::{exceptions::PyValueError, types::PyTuple};
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyDict, PyList};
 use pyo3::PyObject;
 use rustc_hash::{FxHashMap, FxHashSet};
+use crate::graph::rs_graph_algorithms as graph_algorithms;
+use crate::graph::rs_graph_format::{decode_payload, encode_payload, GraphData};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+
+// Pickled node layout -> (id, payload, [(neighbour id, weight), ...]).
+type NodeState = (usize, PyObject, Vec<(usize, f64)>);
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -24,7 +31,7 @@ impl WeightedNode {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct WeightedDigraph {
     nodes: FxHashMap<usize, WeightedNode>,
     next_id: usize,
@@ -65,6 +72,152 @@ impl WeightedDigraph {
         rec_stack.remove(&node_id);
         false
     }
+
+    fn sorted_neighbours(&self, id: usize) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = match self.nodes.get(&id) {
+            Some(node) => node.neighbours.keys().copied().collect(),
+            None => Vec::new(),
+        };
+
+        neighbours.sort_unstable();
+        neighbours
+    }
+
+    fn weighted_neighbours(&self, id: usize) -> Vec<(usize, f64)> {
+        match self.nodes.get(&id) {
+            Some(node) => node.neighbours.iter().map(|(neigh_id, weight)| (*neigh_id, *weight)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn edge_weights(&self) -> impl Iterator<Item = f64> + '_ {
+        self.nodes.values().flat_map(|node| node.neighbours.values().copied())
+    }
+
+    // Edmonds-Karp over a residual map keyed by (from_id, to_id); returns flow value & final residuals.
+    fn edmonds_karp(&self, source_id: usize, sink_id: usize) -> (f64, FxHashMap<(usize, usize), f64>) {
+        let mut residual: FxHashMap<(usize, usize), f64> = FxHashMap::default();
+        let mut adjacency: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+
+        for (from_id, node) in self.nodes.iter() {
+            for (to_id, capacity) in node.neighbours.iter() {
+                *residual.entry((*from_id, *to_id)).or_insert(0.0) += *capacity;
+                residual.entry((*to_id, *from_id)).or_insert(0.0);
+                adjacency.entry(*from_id).or_default().push(*to_id);
+                adjacency.entry(*to_id).or_default().push(*from_id);
+            }
+        }
+
+        for neighbours in adjacency.values_mut() {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        }
+
+        let mut total_flow = 0.0;
+
+        loop {
+            let mut previous: FxHashMap<usize, usize> = FxHashMap::default();
+            let mut id_queue = VecDeque::new();
+            id_queue.push_back(source_id);
+
+            while let Some(current_id) = id_queue.pop_front() {
+                if current_id == sink_id {
+                    break;
+                }
+
+                if let Some(neighbours) = adjacency.get(&current_id) {
+                    for next_id in neighbours.iter() {
+                        if *next_id == source_id || previous.contains_key(next_id) {
+                            continue;
+                        }
+
+                        if residual[&(current_id, *next_id)] > 0.0 {
+                            previous.insert(*next_id, current_id);
+                            id_queue.push_back(*next_id);
+                        }
+                    }
+                }
+            }
+
+            if !previous.contains_key(&sink_id) {
+                break;
+            }
+
+            let path = graph_algorithms::rebuild_path(&previous, source_id, sink_id);
+            let bottleneck = path.windows(2)
+                .map(|pair| residual[&(pair[0], pair[1])])
+                .fold(f64::INFINITY, f64::min);
+
+            for pair in path.windows(2) {
+                *residual.get_mut(&(pair[0], pair[1])).expect("Residual edge not found!") -= bottleneck;
+                *residual.get_mut(&(pair[1], pair[0])).expect("Residual edge not found!") += bottleneck;
+            }
+
+            total_flow += bottleneck;
+        }
+
+        (total_flow, residual)
+    }
+
+    fn check_flow_nodes(&self, source_id: usize, sink_id: usize) -> PyResult<()> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&source_id) {
+            return Err(PyValueError::new_err("Source ID node not found in current Graph"));
+        }
+
+        if !self.nodes.contains_key(&sink_id) {
+            return Err(PyValueError::new_err("Sink ID node not found in current Graph"));
+        }
+
+        if source_id == sink_id {
+            return Err(PyValueError::new_err("Source and sink must be different nodes"));
+        }
+
+        for (_, node) in self.nodes.iter() {
+            for (_, capacity) in node.neighbours.iter() {
+                if *capacity < 0.0 {
+                    return Err(PyValueError::new_err("Edge capacities must be non-negative"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn export_data(&self, py: Python, encoder: &Option<PyObject>) -> PyResult<GraphData> {
+        let mut data = GraphData::new(true, true);
+
+        for (id, node) in self.nodes.iter() {
+            data.nodes.push((*id, Some(encode_payload(py, &node.payload, encoder)?)));
+            for (neigh_id, weight) in node.neighbours.iter() {
+                data.edges.push((*id, *neigh_id, *weight));
+            }
+        }
+
+        data.sort();
+        Ok(data)
+    }
+
+    fn import_data(py: Python, data: GraphData, decoder: &Option<PyObject>) -> PyResult<Self> {
+        data.expect_pairwise(true)?;
+        data.check_nodes()?;
+
+        let mut graph = WeightedDigraph::new();
+        for (id, payload) in data.nodes.iter() {
+            graph.insert_with_id(*id, decode_payload(py, payload, decoder)?);
+            graph.next_id = graph.next_id.max(id + 1);
+        }
+        graph.count = graph.nodes.len();
+
+        for (from_id, to_id, weight) in data.edges.iter() {
+            let from_node = graph.nodes.get_mut(from_id).expect("From ID node not found!");
+            from_node.neighbours.insert(*to_id, *weight);
+        }
+
+        Ok(graph)
+    }
 }
 
 #[pymethods]
@@ -494,6 +647,342 @@ impl WeightedDigraph {
         return Ok(false);
     }
 
+    pub fn topological_sort<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let mut in_degree: FxHashMap<usize, usize> = self.nodes.keys().map(|id| (*id, 0)).collect();
+        for (_, node) in self.nodes.iter() {
+            for to_id in node.neighbours.keys() {
+                *in_degree.get_mut(to_id).expect("Edge target not found!") += 1;
+            }
+        }
+
+        // Kahn's algorithm, always emitting the smallest ready ID for a stable ordering.
+        let mut ready: BinaryHeap<Reverse<usize>> = in_degree.iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| Reverse(*id))
+            .collect();
+        let mut results = Vec::with_capacity(self.nodes.len());
+
+        while let Some(Reverse(current_id)) = ready.pop() {
+            results.push(current_id);
+
+            for next_id in self.sorted_neighbours(current_id) {
+                let degree = in_degree.get_mut(&next_id).expect("Edge target not found!");
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(Reverse(next_id));
+                }
+            }
+        }
+
+        if results.len() < self.nodes.len() {
+            let cycle = graph_algorithms::find_cycle(self.nodes.keys().copied().collect(), |id| self.sorted_neighbours(id)).unwrap_or_default();
+            let cycle_text: Vec<String> = cycle.iter().map(|id| id.to_string()).collect();
+            return Err(PyValueError::new_err(format!("Graph contains a cycle: {}", cycle_text.join(" -> "))));
+        }
+
+        Ok(PyList::new(py, results))
+    }
+
+    pub fn strongly_connected_components<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let mut results = Vec::new();
+        for component in graph_algorithms::tarjan_components(self.nodes.keys().copied().collect(), |id| self.sorted_neighbours(id)) {
+            results.push(PyList::new(py, component).to_object(py));
+        }
+
+        Ok(PyList::new(py, results))
+    }
+
+    pub fn condensation(&self, py: Python) -> PyResult<PyObject> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let components = graph_algorithms::tarjan_components(self.nodes.keys().copied().collect(), |id| self.sorted_neighbours(id));
+        let mut component_of: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut new_digraph = WeightedDigraph::new();
+
+        // Component IDs follow topological order and each payload lists the member node IDs.
+        for (index, component) in components.iter().enumerate() {
+            let component_id = index + 1;
+            for member_id in component.iter() {
+                component_of.insert(*member_id, component_id);
+            }
+            new_digraph.insert_with_id(component_id, PyList::new(py, component).to_object(py));
+        }
+
+        new_digraph.next_id = components.len() + 1;
+        new_digraph.count = components.len();
+
+        for (from_id, node) in self.nodes.iter() {
+            let from_component = component_of[from_id];
+            for (to_id, weight) in node.neighbours.iter() {
+                let to_component = component_of[to_id];
+                if from_component == to_component {
+                    continue;
+                }
+
+                let entry = new_digraph.nodes.get_mut(&from_component).expect("Component node not found!");
+                let lightest = match entry.neighbours.get(&to_component) {
+                    Some(current) => current.min(*weight),
+                    None => *weight,
+                };
+                entry.neighbours.insert(to_component, lightest);
+            }
+        }
+
+        Py::new(py, new_digraph).map(|py_object| py_object.into_py(py))
+    }
+
+    pub fn shortest_path(&self, py: Python, start_id: usize, end_id: usize) -> PyResult<PyObject> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&start_id) {
+            return Err(PyValueError::new_err("Start ID node not found in current Graph"));
+        }
+
+        if !self.nodes.contains_key(&end_id) {
+            return Err(PyValueError::new_err("End ID node not found in current Graph"));
+        }
+
+        graph_algorithms::check_non_negative(self.edge_weights())?;
+        let (distances, previous) = graph_algorithms::dijkstra(start_id, Some(end_id), |id| self.weighted_neighbours(id));
+
+        let total_cost = match distances.get(&end_id) {
+            Some(value) => *value,
+            None => return Err(PyValueError::new_err("No path found between specified nodes")),
+        };
+
+        let path = graph_algorithms::rebuild_path(&previous, start_id, end_id);
+        Ok((PyList::new(py, path), total_cost).to_object(py))
+    }
+
+    pub fn shortest_distances<'py>(&self, py: Python<'py>, start_id: usize) -> PyResult<&'py PyDict> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&start_id) {
+            return Err(PyValueError::new_err("Start ID node not found in current Graph"));
+        }
+
+        graph_algorithms::check_non_negative(self.edge_weights())?;
+        let (distances, _) = graph_algorithms::dijkstra(start_id, None, |id| self.weighted_neighbours(id));
+
+        let py_dict = PyDict::new(py);
+        for (id, cost) in distances.iter() {
+            py_dict.set_item(id, cost)?;
+        }
+
+        Ok(py_dict)
+    }
+
+    pub fn astar_path(&self, py: Python, start_id: usize, end_id: usize, heuristic: PyObject) -> PyResult<PyObject> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&start_id) {
+            return Err(PyValueError::new_err("Start ID node not found in current Graph"));
+        }
+
+        if !self.nodes.contains_key(&end_id) {
+            return Err(PyValueError::new_err("End ID node not found in current Graph"));
+        }
+
+        graph_algorithms::check_non_negative(self.edge_weights())?;
+
+        // Heuristic is called once per node as heuristic(node_id, end_id) and cached.
+        let mut estimates: FxHashMap<usize, f64> = FxHashMap::default();
+        let estimate = |id: usize| -> PyResult<f64> {
+            if let Some(value) = estimates.get(&id) {
+                return Ok(*value);
+            }
+            let value: f64 = heuristic.call1(py, (id, end_id))?.extract(py)?;
+            estimates.insert(id, value);
+            Ok(value)
+        };
+
+        match graph_algorithms::astar(start_id, end_id, |id| self.weighted_neighbours(id), estimate)? {
+            Some((path, total_cost)) => Ok((PyList::new(py, path), total_cost).to_object(py)),
+            None => Err(PyValueError::new_err("No path found between specified nodes")),
+        }
+    }
+
+    pub fn bellman_ford<'py>(&self, py: Python<'py>, start_id: usize) -> PyResult<&'py PyDict> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&start_id) {
+            return Err(PyValueError::new_err("Start ID node not found in current Graph"));
+        }
+
+        let mut distances: FxHashMap<usize, f64> = FxHashMap::default();
+        distances.insert(start_id, 0.0);
+
+        for _ in 1..self.nodes.len() {
+            let mut changed = false;
+
+            for (from_id, node) in self.nodes.iter() {
+                let from_cost = match distances.get(from_id) {
+                    Some(value) => *value,
+                    None => continue,
+                };
+
+                for (to_id, weight) in node.neighbours.iter() {
+                    let next_cost = from_cost + weight;
+                    let improved = match distances.get(to_id) {
+                        Some(&current) => next_cost < current,
+                        None => true,
+                    };
+
+                    if improved {
+                        distances.insert(*to_id, next_cost);
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        // Any edge that still relaxes after |V| - 1 rounds lies on or behind a negative cycle.
+        for (from_id, node) in self.nodes.iter() {
+            if let Some(&from_cost) = distances.get(from_id) {
+                for (to_id, weight) in node.neighbours.iter() {
+                    let relaxes = match distances.get(to_id) {
+                        Some(&current) => from_cost + weight < current,
+                        None => true,
+                    };
+
+                    if relaxes {
+                        return Err(PyValueError::new_err("Negative weight cycle reachable from start node"));
+                    }
+                }
+            }
+        }
+
+        let py_dict = PyDict::new(py);
+        for (id, cost) in distances.iter() {
+            py_dict.set_item(id, cost)?;
+        }
+
+        Ok(py_dict)
+    }
+
+    pub fn max_flow(&self, py: Python, source_id: usize, sink_id: usize) -> PyResult<PyObject> {
+        self.check_flow_nodes(source_id, sink_id)?;
+
+        let (total_flow, residual) = self.edmonds_karp(source_id, sink_id);
+
+        // Flow along an original edge is whatever capacity the augmenting paths used up.
+        let edge_flows = PyDict::new(py);
+        for (from_id, node) in self.nodes.iter() {
+            for (to_id, capacity) in node.neighbours.iter() {
+                let used = (capacity - residual[&(*from_id, *to_id)]).max(0.0);
+                edge_flows.set_item((*from_id, *to_id), used.min(*capacity))?;
+            }
+        }
+
+        Ok((total_flow, edge_flows).to_object(py))
+    }
+
+    pub fn min_cut(&self, py: Python, source_id: usize, sink_id: usize) -> PyResult<PyObject> {
+        self.check_flow_nodes(source_id, sink_id)?;
+
+        let (_, residual) = self.edmonds_karp(source_id, sink_id);
+
+        // Nodes still reachable through positive residual capacity form the source side.
+        let mut reachable = FxHashSet::default();
+        let mut id_queue = VecDeque::new();
+        reachable.insert(source_id);
+        id_queue.push_back(source_id);
+
+        let mut adjacency: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+        for ((from_id, to_id), capacity) in residual.iter() {
+            if *capacity > 0.0 {
+                adjacency.entry(*from_id).or_default().push(*to_id);
+            }
+        }
+
+        while let Some(current_id) = id_queue.pop_front() {
+            if let Some(neighbours) = adjacency.get(&current_id) {
+                for next_id in neighbours.iter() {
+                    if reachable.insert(*next_id) {
+                        id_queue.push_back(*next_id);
+                    }
+                }
+            }
+        }
+
+        let mut source_side: Vec<usize> = reachable.iter().copied().collect();
+        let mut sink_side: Vec<usize> = self.nodes.keys().filter(|id| !reachable.contains(id)).copied().collect();
+        source_side.sort_unstable();
+        sink_side.sort_unstable();
+
+        let mut cut_edges = Vec::new();
+        for from_id in source_side.iter() {
+            let node = self.nodes.get(from_id).expect("Source side node not found!");
+            for to_id in node.neighbours.keys() {
+                if !reachable.contains(to_id) {
+                    cut_edges.push((*from_id, *to_id));
+                }
+            }
+        }
+        cut_edges.sort_unstable();
+
+        Ok((PyList::new(py, source_side), PyList::new(py, sink_side), PyList::new(py, cut_edges)).to_object(py))
+    }
+
+    pub fn to_edge_list(&self, py: Python) -> PyResult<String> {
+        Ok(self.export_data(py, &None)?.to_edge_list())
+    }
+
+    #[staticmethod]
+    pub fn from_edge_list(py: Python, text: &str) -> PyResult<Self> {
+        let data = GraphData::from_edge_list(text, true, true, false)?;
+        Self::import_data(py, data, &None)
+    }
+
+    pub fn to_dot(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_dot())
+    }
+
+    #[staticmethod]
+    pub fn from_dot(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_dot(text)?, &decoder)
+    }
+
+    pub fn to_graphml(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_graphml())
+    }
+
+    #[staticmethod]
+    pub fn from_graphml(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_graphml(py, text)?, &decoder)
+    }
+
+    pub fn to_json(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        self.export_data(py, &encoder)?.to_json(py)
+    }
+
+    #[staticmethod]
+    pub fn from_json(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_json(py, text)?, &decoder)
+    }
+
     pub fn is_empty(&self) -> PyResult<bool> {
         if self.count == 0 {
             Ok(true)
@@ -526,4 +1015,63 @@ impl WeightedDigraph {
         self.count = 0;
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut nodes = Vec::with_capacity(ids.len());
+        for id in ids {
+            let node = &self.nodes[&id];
+            let mut neighbours: Vec<(usize, f64)> = node.neighbours.iter().map(|(other, weight)| (*other, *weight)).collect();
+            neighbours.sort_unstable_by_key(|(other, _)| *other);
+            nodes.push((id, node.payload.clone_ref(py), neighbours).to_object(py));
+        }
+        Ok((STATE_VERSION, self.next_id, self.count, nodes).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "WeightedDigraph", 3)?;
+        let next_id: usize = state.get_item(1)?.extract()?;
+        let count: usize = state.get_item(2)?.extract()?;
+        let entries: Vec<NodeState> = state.get_item(3)?.extract()?;
+
+        let mut nodes = FxHashMap::default();
+        for (id, payload, neighbours) in entries {
+            if id >= next_id {
+                return Err(invalid_state("WeightedDigraph", "node id beyond next_id"));
+            }
+            let mut node = WeightedNode::new(id, payload);
+            node.neighbours = neighbours.into_iter().collect();
+            nodes.insert(id, node);
+        }
+
+        for node in nodes.values() {
+            if node.neighbours.keys().any(|other| !nodes.contains_key(other)) {
+                return Err(invalid_state("WeightedDigraph", "edge refers to an unknown node"));
+            }
+        }
+
+        self.nodes = nodes;
+        self.next_id = next_id;
+        self.count = count;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = WeightedDigraph::new();
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, WeightedDigraph::new())?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
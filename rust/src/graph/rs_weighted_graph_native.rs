@@ -1,9 +1,15 @@
 use std::collections::VecDeque;
 use pyo3::{exceptions::PyValueError, types::PyTuple};
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyDict, PyList};
 use pyo3::PyObject;
 use rustc_hash::{FxHashMap, FxHashSet};
+use crate::graph::rs_graph_algorithms as graph_algorithms;
+use crate::graph::rs_graph_format::{decode_payload, encode_payload, GraphData};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+
+// Pickled node layout -> (id, payload, [(neighbour id, weight), ...]).
+type NodeState = (usize, PyObject, Vec<(usize, f64)>);
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -24,7 +30,7 @@ impl WeightedNode {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct WeightedGraph {
     nodes: FxHashMap<usize, WeightedNode>,
     next_id: usize,
@@ -39,6 +45,80 @@ impl WeightedGraph {
             }
         }
     }
+
+    fn insert_with_id(&mut self, id: usize, payload: PyObject) {
+        let new_node = WeightedNode::new(id, payload);
+
+        self.nodes.insert(id, new_node);
+    }
+
+    fn find_root(parents: &mut FxHashMap<usize, usize>, id: usize) -> usize {
+        let mut root = id;
+        while parents[&root] != root {
+            root = parents[&root];
+        }
+
+        // Path compression keeps later lookups close to constant time.
+        let mut current = id;
+        while current != root {
+            let next = parents[&current];
+            parents.insert(current, root);
+            current = next;
+        }
+
+        root
+    }
+
+    fn weighted_neighbours(&self, id: usize) -> Vec<(usize, f64)> {
+        match self.nodes.get(&id) {
+            Some(node) => node.neighbours.iter().map(|(neigh_id, weight)| (*neigh_id, *weight)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn edge_weights(&self) -> impl Iterator<Item = f64> + '_ {
+        self.nodes.values().flat_map(|node| node.neighbours.values().copied())
+    }
+
+    fn export_data(&self, py: Python, encoder: &Option<PyObject>) -> PyResult<GraphData> {
+        let mut data = GraphData::new(false, true);
+
+        for (id, node) in self.nodes.iter() {
+            data.nodes.push((*id, Some(encode_payload(py, &node.payload, encoder)?)));
+
+            // Undirected edges are stored on both nodes, so only export the lower-to-higher copy.
+            for (neigh_id, weight) in node.neighbours.iter() {
+                if id <= neigh_id {
+                    data.edges.push((*id, *neigh_id, *weight));
+                }
+            }
+        }
+
+        data.sort();
+        Ok(data)
+    }
+
+    fn import_data(py: Python, data: GraphData, decoder: &Option<PyObject>) -> PyResult<Self> {
+        data.expect_pairwise(false)?;
+        data.check_nodes()?;
+
+        let mut graph = WeightedGraph::new();
+        for (id, payload) in data.nodes.iter() {
+            graph.insert_with_id(*id, decode_payload(py, payload, decoder)?);
+            graph.next_id = graph.next_id.max(id + 1);
+        }
+        graph.count = graph.nodes.len();
+
+        for (from_id, to_id, weight) in data.edges.iter() {
+            let from_node = graph.nodes.get_mut(from_id).expect("From ID node not found!");
+            from_node.neighbours.insert(*to_id, *weight);
+
+            let to_node = graph.nodes.get_mut(to_id).expect("To ID node not found!");
+            to_node.neighbours.insert(*from_id, *weight);
+        }
+
+        Ok(graph)
+    }
 }
 
 #[pymethods]
@@ -432,6 +512,168 @@ impl WeightedGraph {
         Ok(false)
     }
 
+    pub fn shortest_path(&self, py: Python, start_id: usize, end_id: usize) -> PyResult<PyObject> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&start_id) {
+            return Err(PyValueError::new_err("Start ID node not found in current Graph"));
+        }
+
+        if !self.nodes.contains_key(&end_id) {
+            return Err(PyValueError::new_err("End ID node not found in current Graph"));
+        }
+
+        graph_algorithms::check_non_negative(self.edge_weights())?;
+        let (distances, previous) = graph_algorithms::dijkstra(start_id, Some(end_id), |id| self.weighted_neighbours(id));
+
+        let total_cost = match distances.get(&end_id) {
+            Some(value) => *value,
+            None => return Err(PyValueError::new_err("No path found between specified nodes")),
+        };
+
+        let path = graph_algorithms::rebuild_path(&previous, start_id, end_id);
+        Ok((PyList::new(py, path), total_cost).to_object(py))
+    }
+
+    pub fn shortest_distances<'py>(&self, py: Python<'py>, start_id: usize) -> PyResult<&'py PyDict> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&start_id) {
+            return Err(PyValueError::new_err("Start ID node not found in current Graph"));
+        }
+
+        graph_algorithms::check_non_negative(self.edge_weights())?;
+        let (distances, _) = graph_algorithms::dijkstra(start_id, None, |id| self.weighted_neighbours(id));
+
+        let py_dict = PyDict::new(py);
+        for (id, cost) in distances.iter() {
+            py_dict.set_item(id, cost)?;
+        }
+
+        Ok(py_dict)
+    }
+
+    pub fn astar_path(&self, py: Python, start_id: usize, end_id: usize, heuristic: PyObject) -> PyResult<PyObject> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        if !self.nodes.contains_key(&start_id) {
+            return Err(PyValueError::new_err("Start ID node not found in current Graph"));
+        }
+
+        if !self.nodes.contains_key(&end_id) {
+            return Err(PyValueError::new_err("End ID node not found in current Graph"));
+        }
+
+        graph_algorithms::check_non_negative(self.edge_weights())?;
+
+        // Heuristic is called once per node as heuristic(node_id, end_id) and cached.
+        let mut estimates: FxHashMap<usize, f64> = FxHashMap::default();
+        let estimate = |id: usize| -> PyResult<f64> {
+            if let Some(value) = estimates.get(&id) {
+                return Ok(*value);
+            }
+            let value: f64 = heuristic.call1(py, (id, end_id))?.extract(py)?;
+            estimates.insert(id, value);
+            Ok(value)
+        };
+
+        match graph_algorithms::astar(start_id, end_id, |id| self.weighted_neighbours(id), estimate)? {
+            Some((path, total_cost)) => Ok((PyList::new(py, path), total_cost).to_object(py)),
+            None => Err(PyValueError::new_err("No path found between specified nodes")),
+        }
+    }
+
+    pub fn minimum_spanning_tree(&self, py: Python) -> PyResult<PyObject> {
+        if self.nodes.is_empty() {
+            return Err(PyValueError::new_err("No elements currently available in Graph"));
+        }
+
+        let mut new_graph = WeightedGraph::new();
+        new_graph.next_id = self.next_id;
+        new_graph.count = self.count;
+
+        let mut parents: FxHashMap<usize, usize> = FxHashMap::default();
+        for (id, item) in self.nodes.iter() {
+            new_graph.insert_with_id(*id, item.payload.clone_ref(py));
+            parents.insert(*id, *id);
+        }
+
+        // Each undirected edge is stored on both nodes, so only keep the x < y copy.
+        let mut edge_list = Vec::new();
+        for (x, node) in self.nodes.iter() {
+            for (y, weight) in node.neighbours.iter() {
+                if x < y {
+                    edge_list.push((*weight, *x, *y));
+                }
+            }
+        }
+
+        edge_list.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| (a.1, a.2).cmp(&(b.1, b.2))));
+
+        // Kruskal: disconnected inputs naturally produce a minimum spanning forest.
+        for (weight, x, y) in edge_list {
+            let x_root = Self::find_root(&mut parents, x);
+            let y_root = Self::find_root(&mut parents, y);
+
+            if x_root == y_root {
+                continue;
+            }
+
+            parents.insert(x_root, y_root);
+
+            let x_node = new_graph.nodes.get_mut(&x).expect("X node not found!");
+            x_node.neighbours.insert(y, weight);
+
+            let y_node = new_graph.nodes.get_mut(&y).expect("Y node not found!");
+            y_node.neighbours.insert(x, weight);
+        }
+
+        Py::new(py, new_graph).map(|py_object| py_object.into_py(py))
+    }
+
+    pub fn to_edge_list(&self, py: Python) -> PyResult<String> {
+        Ok(self.export_data(py, &None)?.to_edge_list())
+    }
+
+    #[staticmethod]
+    pub fn from_edge_list(py: Python, text: &str) -> PyResult<Self> {
+        let data = GraphData::from_edge_list(text, false, true, false)?;
+        Self::import_data(py, data, &None)
+    }
+
+    pub fn to_dot(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_dot())
+    }
+
+    #[staticmethod]
+    pub fn from_dot(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_dot(text)?, &decoder)
+    }
+
+    pub fn to_graphml(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        Ok(self.export_data(py, &encoder)?.to_graphml())
+    }
+
+    #[staticmethod]
+    pub fn from_graphml(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_graphml(py, text)?, &decoder)
+    }
+
+    pub fn to_json(&self, py: Python, encoder: Option<PyObject>) -> PyResult<String> {
+        self.export_data(py, &encoder)?.to_json(py)
+    }
+
+    #[staticmethod]
+    pub fn from_json(py: Python, text: &str, decoder: Option<PyObject>) -> PyResult<Self> {
+        Self::import_data(py, GraphData::from_json(py, text)?, &decoder)
+    }
+
     pub fn is_empty(&self) -> PyResult<bool> {
         if self.count == 0 {
             Ok(true)
@@ -464,4 +706,63 @@ impl WeightedGraph {
         self.count = 0;
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut ids: Vec<usize> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut nodes = Vec::with_capacity(ids.len());
+        for id in ids {
+            let node = &self.nodes[&id];
+            let mut neighbours: Vec<(usize, f64)> = node.neighbours.iter().map(|(other, weight)| (*other, *weight)).collect();
+            neighbours.sort_unstable_by_key(|(other, _)| *other);
+            nodes.push((id, node.payload.clone_ref(py), neighbours).to_object(py));
+        }
+        Ok((STATE_VERSION, self.next_id, self.count, nodes).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "WeightedGraph", 3)?;
+        let next_id: usize = state.get_item(1)?.extract()?;
+        let count: usize = state.get_item(2)?.extract()?;
+        let entries: Vec<NodeState> = state.get_item(3)?.extract()?;
+
+        let mut nodes = FxHashMap::default();
+        for (id, payload, neighbours) in entries {
+            if id >= next_id {
+                return Err(invalid_state("WeightedGraph", "node id beyond next_id"));
+            }
+            let mut node = WeightedNode::new(id, payload);
+            node.neighbours = neighbours.into_iter().collect();
+            nodes.insert(id, node);
+        }
+
+        for node in nodes.values() {
+            if node.neighbours.keys().any(|other| !nodes.contains_key(other)) {
+                return Err(invalid_state("WeightedGraph", "edge refers to an unknown node"));
+            }
+        }
+
+        self.nodes = nodes;
+        self.next_id = next_id;
+        self.count = count;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = WeightedGraph::new();
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, WeightedGraph::new())?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
@@ -1,3 +1,7 @@
+pub mod rs_map_key;
+pub mod rs_map_protocol;
+pub mod rs_map_views;
+pub mod rs_map_sizing;
 pub mod rs_snapmap_native;
 pub mod rs_rhoodmap_native;
 pub mod rs_quadmap_native;
\ No newline at end of file
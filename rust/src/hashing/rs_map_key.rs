@@ -0,0 +1,132 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyLong, PyString, PyTuple};
+use rustc_hash::FxHasher;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use xxhash_rust::xxh3::Xxh3;
+
+// ---------------------------------------------------------------------------------
+// Key hashing & equality shared by SnapMap, RhoodMap and QuadMap
+// ---------------------------------------------------------------------------------
+//
+// Any hashable Python object can be used as a key. None, str, bytes and tuples of
+// those are hashed natively without calling back into Python. Numbers and every
+// other object feed their Python hash() into the same domain, so keys that compare
+// equal across types (1 == 1.0 == True, Fraction(1, 2) == 0.5) land on the same
+// hash - exactly like they do in a dict.
+
+// Modulus of CPython's numeric hash (2**61 - 1 on 64-bit builds).
+const NUMERIC_MODULUS: i128 = (1 << 61) - 1;
+
+// Type tags keep the native encodings of different key kinds apart.
+const TAG_NONE: u8 = 0;
+const TAG_STR: u8 = 1;
+const TAG_BYTES: u8 = 2;
+const TAG_TUPLE: u8 = 3;
+const TAG_OBJECT: u8 = 4;
+
+fn numeric_hash(value: i64) -> isize {
+    // Mirrors CPython's hash(int) -> |value| mod (2**61 - 1), signed, with -1 reserved for errors.
+    let reduced = (value as i128).abs() % NUMERIC_MODULUS;
+    let hash = if value < 0 { -reduced } else { reduced } as isize;
+    if hash == -1 { -2 } else { hash }
+}
+
+pub(crate) fn hash_key<H: Hasher>(key: &PyAny, state: &mut H) -> PyResult<()> {
+    // Fast native paths -> None, str, bytes & (nested) tuples never call back into Python.
+    if key.is_none() {
+        state.write_u8(TAG_NONE);
+    } else if let Ok(text) = key.downcast::<PyString>() {
+        state.write_u8(TAG_STR);
+        text.to_string_lossy().hash(state);
+    } else if let Ok(bytes) = key.downcast::<PyBytes>() {
+        state.write_u8(TAG_BYTES);
+        bytes.as_bytes().hash(state);
+    } else if let Ok(tuple) = key.downcast::<PyTuple>() {
+        state.write_u8(TAG_TUPLE);
+        state.write_usize(tuple.len());
+        for item in tuple.iter() {
+            hash_key(item, state)?;
+        }
+    } else {
+        // Numbers & arbitrary objects share Python's hash domain so equal values of different types match.
+        state.write_u8(TAG_OBJECT);
+        // Ints (and bools, an int subclass) that fit in an i64 are reduced natively.
+        let hash = if key.is_instance_of::<PyLong>()? {
+            match key.extract::<i64>() {
+                Ok(value) => numeric_hash(value),
+                Err(_) => key.hash()?,
+            }
+        } else {
+            key.hash()?
+        };
+        state.write_isize(hash);
+    }
+    Ok(())
+}
+
+fn finish_with<H: Hasher>(key: &PyAny, mut hasher: H) -> PyResult<u64> {
+    // Feed the key through an already constructed hasher & return the finished digest.
+    hash_key(key, &mut hasher)?;
+    Ok(hasher.finish())
+}
+
+pub(crate) fn keys_equal(stored: &PyAny, key: &PyAny) -> PyResult<bool> {
+    // Identity first, then __eq__ -> the same rule dict uses (so a NaN key still finds itself).
+    Ok(stored.is(key) || stored.eq(key)?)
+}
+
+// ---------------------------------------------------------------------------------
+// Hash family selected per map
+// ---------------------------------------------------------------------------------
+//
+// 'fx' is the fastest, but crafted keys can easily force collisions. 'siphash'
+// draws a random key for every map, so untrusted keys can't be aimed at one probe
+// chain (HashDoS). 'seeded' runs xxh3 under a caller-chosen seed, giving the same
+// layout in every process - for None, str, bytes, numbers & tuples of those, not
+// for objects whose hash() depends on their identity.
+
+#[derive(Debug, Clone)]
+pub(crate) enum MapHasher {
+    Fx,
+    SipHash(RandomState),
+    Seeded(u64),
+}
+
+impl MapHasher {
+    pub(crate) fn new(hasher: Option<&str>, seed: Option<u64>) -> PyResult<Self> {
+        // A bare seed implies the seeded family, otherwise SipHash with a random key is the default.
+        let scheme = hasher.unwrap_or(if seed.is_some() { "seeded" } else { "siphash" });
+        match (scheme, seed) {
+            ("fx", None) => Ok(MapHasher::Fx),
+            ("siphash", None) => Ok(MapHasher::SipHash(RandomState::new())),
+            ("seeded", seed) => Ok(MapHasher::Seeded(seed.unwrap_or(0))),
+            ("fx" | "siphash", Some(_)) => Err(PyValueError::new_err(format!("A seed can only be used with the 'seeded' hasher, not '{}'", scheme))),
+            _ => Err(PyValueError::new_err(format!("Unknown hasher '{}' -> expected 'fx', 'siphash' or 'seeded'", scheme))),
+        }
+    }
+
+    pub(crate) fn scheme(&self) -> &'static str {
+        match self {
+            MapHasher::Fx => "fx",
+            MapHasher::SipHash(_) => "siphash",
+            MapHasher::Seeded(_) => "seeded",
+        }
+    }
+
+    pub(crate) fn seed(&self) -> Option<u64> {
+        match self {
+            MapHasher::Seeded(seed) => Some(*seed),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn hash(&self, key: &PyAny) -> PyResult<u64> {
+        match self {
+            MapHasher::Fx => finish_with(key, FxHasher::default()),
+            MapHasher::SipHash(state) => finish_with(key, state.build_hasher()),
+            MapHasher::Seeded(seed) => finish_with(key, Xxh3::with_seed(*seed)),
+        }
+    }
+}
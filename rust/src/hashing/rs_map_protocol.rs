@@ -0,0 +1,99 @@
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyModule, PyTuple};
+use crate::hashing::rs_map_key::keys_equal;
+use crate::hashing::rs_map_views::EntryWalk;
+
+// ---------------------------------------------------------------------------------
+// Mapping protocol helpers shared by SnapMap, RhoodMap and QuadMap
+// ---------------------------------------------------------------------------------
+//
+// The three maps keep their own storage, but argument handling for update(),
+// comparisons against other mappings & the collections.abc registration behave
+// identically, so they live here.
+
+pub(crate) fn register_mappings(py: Python, module: &PyModule, names: &[&str]) -> PyResult<()> {
+    // Let the hash maps pass isinstance(map, MutableMapping) checks, exactly like a dict.
+    let mutable_mapping = py.import("collections.abc")?.getattr("MutableMapping")?;
+    for name in names {
+        mutable_mapping.call_method1("register", (module.getattr(*name)?,))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn legacy_update(args: &PyTuple, kwargs: Option<&PyDict>) -> PyResult<Option<(PyObject, PyObject)>> {
+    // The original update(key, new_value) call form is still honoured when passed positionally.
+    // Keywords always mean dict.update(**kwargs), so update(key=.., new_value=..) inserts both names.
+    match (args.len(), kwargs) {
+        (2, None) => Ok(Some((args.get_item(0)?.into(), args.get_item(1)?.into()))),
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn update_pairs(args: &PyTuple, kwargs: Option<&PyDict>) -> PyResult<Vec<(PyObject, PyObject)>> {
+    // Collect pairs the way dict.update() does -> a mapping (anything with .keys()), else an iterable of pairs, then keywords.
+    if args.len() > 1 {
+        return Err(PyTypeError::new_err(format!("update expected at most 1 argument, got {}", args.len())));
+    }
+
+    let mut pairs = Vec::new();
+    if let Ok(other) = args.get_item(0) {
+        if other.hasattr("keys")? {
+            for key in other.call_method0("keys")?.iter()? {
+                let key = key?;
+                pairs.push((key.into(), other.get_item(key)?.into()));
+            }
+        } else {
+            for (position, element) in other.iter()?.enumerate() {
+                let pair: Vec<&PyAny> = element?.iter()?.collect::<PyResult<_>>()?;
+                if pair.len() != 2 {
+                    return Err(PyValueError::new_err(format!("dictionary update sequence element #{} has length {}; 2 is required", position, pair.len())));
+                }
+                pairs.push((pair[0].into(), pair[1].into()));
+            }
+        }
+    }
+    if let Some(kwargs) = kwargs {
+        for (key, value) in kwargs.iter() {
+            pairs.push((key.into(), value.into()));
+        }
+    }
+    Ok(pairs)
+}
+
+pub(crate) fn pop_default(default: &PyTuple) -> PyResult<Option<PyObject>> {
+    // pop(key[, default]) -> An explicit 'None' default must still count as a default.
+    match default.len() {
+        0 => Ok(None),
+        1 => Ok(Some(default.get_item(0)?.into())),
+        n => Err(PyTypeError::new_err(format!("pop expected at most 2 arguments, got {}", n + 1))),
+    }
+}
+
+pub(crate) fn mapping_compare(py: Python, map: &dyn EntryWalk, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+    // Only == and != are defined, and only against other mappings (dicts, or another map).
+    let equal = match op {
+        CompareOp::Eq => true,
+        CompareOp::Ne => false,
+        _ => return Ok(py.NotImplemented()),
+    };
+    let mapping = py.import("collections.abc")?.getattr("Mapping")?;
+    if !other.is_instance(mapping)? {
+        return Ok(py.NotImplemented());
+    }
+
+    // Same length & every stored key maps to an equal value on the other side.
+    let mut same = other.len()? == map.entry_count();
+    let mut cursor = 0;
+    while same {
+        let Some((position, key, value)) = map.entry_from(py, cursor) else { break };
+        same = match other.get_item(key) {
+            Ok(other_value) => keys_equal(value.as_ref(py), other_value)?,
+            Err(err) if err.is_instance_of::<PyKeyError>(py) => false,
+            Err(err) => return Err(err),
+        };
+        cursor = position + 1;
+    }
+    Ok((same == equal).into_py(py))
+}
@@ -0,0 +1,60 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+// ---------------------------------------------------------------------------------
+// Load-factor policy shared by the open-addressing maps (RhoodMap & QuadMap).
+//
+// An insert that would push the occupied share of the slots above max_load
+// grows the map first; a delete that leaves the live entries below min_load
+// halves it again, never dropping under the capacity the map was created with.
+// A min_load of 0.0 turns automatic shrinking off.
+// ---------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LoadPolicy {
+    pub(crate) max_load: f64,
+    pub(crate) min_load: f64,
+}
+
+impl LoadPolicy {
+    pub(crate) fn new(max_load: Option<f64>, min_load: Option<f64>, default_max: f64, default_min: f64) -> PyResult<Self> {
+        let max_load = max_load.unwrap_or(default_max);
+        let min_load = min_load.unwrap_or(default_min);
+        if max_load.is_nan() || max_load <= 0.0 || max_load > 1.0 {
+            return Err(PyValueError::new_err("Max load must be a number within (0.0, 1.0]"));
+        }
+        // Keeping min_load under half of max_load stops a grow from immediately triggering a shrink (and back).
+        if min_load.is_nan() || min_load < 0.0 || min_load * 2.0 >= max_load {
+            return Err(PyValueError::new_err("Min load must be at least 0.0 and below half of max load"));
+        }
+        Ok(Self { max_load, min_load })
+    }
+
+    pub(crate) fn fits(&self, occupied: usize, capacity: usize) -> bool {
+        // Whether 'occupied' slots stay within the max load of 'capacity' slots.
+        occupied as f64 <= self.max_load * capacity as f64
+    }
+
+    pub(crate) fn capacity_for(&self, entries: usize) -> usize {
+        // Smallest capacity holding 'entries' within the max load (an empty map still keeps one slot).
+        let mut capacity = ((entries as f64 / self.max_load).ceil() as usize).max(1);
+        while !self.fits(entries, capacity) {
+            capacity += 1;
+        }
+        capacity
+    }
+
+    pub(crate) fn grown(&self, capacity: usize, entries: usize) -> usize {
+        // Double the slot count, or more if that still can't hold 'entries'.
+        (capacity * 2).max(self.capacity_for(entries))
+    }
+
+    pub(crate) fn shrunk(&self, capacity: usize, entries: usize, floor: usize) -> Option<usize> {
+        // Halve the slot count while the live entries sit below the min load & the result stays above 'floor'.
+        let mut target = capacity;
+        while (entries as f64) < self.min_load * target as f64 && target / 2 >= floor.max(1) && self.fits(entries, target / 2) {
+            target /= 2;
+        }
+        (target < capacity).then_some(target)
+    }
+}
@@ -0,0 +1,373 @@
+// pyo3 0.18 expands the set operator and __richcmp__ slots of the view #[pymethods] blocks into nested impls
+// that newer compilers flag. The generated wrappers sit outside the impl blocks, so the allow covers this module.
+#![allow(non_local_definitions)]
+
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyModule, PySet, PyTuple};
+use crate::hashing::rs_map_key::keys_equal;
+use crate::hashing::rs_quadmap_native::QuadMap;
+use crate::hashing::rs_rhoodmap_native::RhoodMap;
+use crate::hashing::rs_snapmap_native::SnapMap;
+
+// ---------------------------------------------------------------------------------
+// Lazy views & iterators shared by SnapMap, RhoodMap and QuadMap
+// ---------------------------------------------------------------------------------
+//
+// keys(), values() and items() hand out light view objects instead of copying the
+// map into a list. Views & iterators only hold a reference to their map plus a
+// cursor into its storage, walking buckets and slots directly on every step.
+// Each map bumps a version counter whenever a key is added or removed; an
+// iterator that sees a different version raises RuntimeError, like dict does.
+
+pub(crate) trait EntryWalk {
+    // Name used in error messages & reprs.
+    fn name(&self) -> &'static str;
+
+    // Structural mutation counter -> changes whenever a key is added or removed.
+    fn version(&self) -> u64;
+
+    // Number of key-value pairs currently stored.
+    fn entry_count(&self) -> usize;
+
+    // First occupied entry at or after 'cursor', together with its own cursor position.
+    fn entry_from(&self, py: Python, cursor: usize) -> Option<(usize, PyObject, PyObject)>;
+
+    // Value stored under 'key', if any.
+    fn value_of(&self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>>;
+}
+
+#[derive(Clone)]
+pub(crate) enum MapHandle {
+    Snap(Py<SnapMap>),
+    Rhood(Py<RhoodMap>),
+    Quad(Py<QuadMap>),
+}
+
+impl MapHandle {
+    fn with<R>(&self, py: Python, walk: impl FnOnce(&dyn EntryWalk) -> PyResult<R>) -> PyResult<R> {
+        // Borrow the underlying map for the duration of a single step.
+        match self {
+            MapHandle::Snap(map) => walk(&*map.try_borrow(py)?),
+            MapHandle::Rhood(map) => walk(&*map.try_borrow(py)?),
+            MapHandle::Quad(map) => walk(&*map.try_borrow(py)?),
+        }
+    }
+
+    fn clone_ref(&self, py: Python) -> Self {
+        match self {
+            MapHandle::Snap(map) => MapHandle::Snap(map.clone_ref(py)),
+            MapHandle::Rhood(map) => MapHandle::Rhood(map.clone_ref(py)),
+            MapHandle::Quad(map) => MapHandle::Quad(map.clone_ref(py)),
+        }
+    }
+
+    fn entries(&self, py: Python) -> PyResult<Vec<(PyObject, PyObject)>> {
+        // Snapshot every entry -> Only used where a full pass is unavoidable (set operations, reprs).
+        self.with(py, |map| {
+            let mut entries = Vec::with_capacity(map.entry_count());
+            let mut cursor = 0;
+            while let Some((position, key, value)) = map.entry_from(py, cursor) {
+                entries.push((key, value));
+                cursor = position + 1;
+            }
+            Ok(entries)
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ViewKind {
+    Keys,
+    Values,
+    Items,
+}
+
+impl ViewKind {
+    fn label(&self) -> &'static str {
+        match self {
+            ViewKind::Keys => "keys",
+            ViewKind::Values => "values",
+            ViewKind::Items => "items",
+        }
+    }
+
+    fn project(&self, py: Python, key: PyObject, value: PyObject) -> PyObject {
+        match self {
+            ViewKind::Keys => key,
+            ViewKind::Values => value,
+            ViewKind::Items => (key, value).to_object(py),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------------
+// Implementation of MapIterator-class & related operations
+// ---------------------------------------------------------------------------------
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct MapIterator {
+    map: MapHandle,
+    kind: ViewKind,
+    version: u64,
+    cursor: usize,
+}
+
+impl MapIterator {
+    fn start(py: Python, map: MapHandle, kind: ViewKind) -> PyResult<Self> {
+        // Remember the map's version at creation -> Any later structural change invalidates the iterator.
+        let version = map.with(py, |map| Ok(map.version()))?;
+        Ok(Self { map, kind, version, cursor: 0 })
+    }
+
+    pub(crate) fn keys(py: Python, map: MapHandle) -> PyResult<Self> {
+        // Plain 'iter(map)' -> Walks the stored keys.
+        Self::start(py, map, ViewKind::Keys)
+    }
+}
+
+#[pymethods]
+impl MapIterator {
+    pub fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    pub fn __next__(&mut self, py: Python) -> PyResult<Option<PyObject>> {
+        let (version, cursor) = (self.version, self.cursor);
+        let step = self.map.with(py, |map| {
+            // Raise RuntimeError if a key was added or removed since the iterator was created.
+            if map.version() != version {
+                return Err(PyRuntimeError::new_err(format!("{} changed size during iteration", map.name())));
+            }
+            Ok(map.entry_from(py, cursor))
+        })?;
+
+        match step {
+            Some((position, key, value)) => {
+                self.cursor = position + 1;
+                Ok(Some(self.kind.project(py, key, value)))
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------------
+// Shared view behaviour -> len(), iter(), 'in', repr() & set operations
+// ---------------------------------------------------------------------------------
+
+fn view_len(py: Python, map: &MapHandle) -> PyResult<usize> {
+    map.with(py, |map| Ok(map.entry_count()))
+}
+
+fn view_iter(py: Python, map: &MapHandle, kind: ViewKind) -> PyResult<MapIterator> {
+    MapIterator::start(py, map.clone_ref(py), kind)
+}
+
+fn view_repr(py: Python, map: &MapHandle, kind: ViewKind) -> PyResult<String> {
+    // e.g. SnapMap_keys([1, 2, 3]) -> Mirrors dict_keys([1, 2, 3]).
+    let name = map.with(py, |map| Ok(map.name()))?;
+    let mut parts = Vec::new();
+    for (key, value) in map.entries(py)? {
+        parts.push(kind.project(py, key, value).as_ref(py).repr()?.to_string());
+    }
+    Ok(format!("{}_{}([{}])", name, kind.label(), parts.join(", ")))
+}
+
+fn view_set<'py>(py: Python<'py>, map: &MapHandle, kind: ViewKind) -> PyResult<&'py PySet> {
+    // Materialise the view as a set -> Keys (and hashable items) behave like collections.abc.Set.
+    let elements: Vec<PyObject> = map.entries(py)?.into_iter().map(|(key, value)| kind.project(py, key, value)).collect();
+    PySet::new(py, &elements)
+}
+
+fn set_operation(py: Python, map: &MapHandle, kind: ViewKind, other: &PyAny, method: &str, reflected: bool) -> PyResult<PyObject> {
+    // Set operations accept any iterable on the other side & always return a plain set, like dict views.
+    let other = match other.iter() {
+        Ok(other) => other,
+        Err(_) => return Ok(py.NotImplemented()),
+    };
+    let mine = view_set(py, map, kind)?;
+    let result = if reflected {
+        py.get_type::<PySet>().call1((other,))?.call_method1(method, (mine,))?
+    } else {
+        mine.call_method1(method, (other,))?
+    };
+    Ok(result.into())
+}
+
+fn set_compare(py: Python, map: &MapHandle, kind: ViewKind, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+    // Compare against other sets (set, frozenset, key views, ...) with the usual subset / superset semantics.
+    let abc_set = py.import("collections.abc")?.getattr("Set")?;
+    if !other.is_instance(abc_set)? {
+        return Ok(py.NotImplemented());
+    }
+    let other = py.get_type::<PySet>().call1((other,))?;
+    Ok(view_set(py, map, kind)?.rich_compare(other, op)?.into())
+}
+
+macro_rules! set_like_view {
+    ($view:ident, $kind:expr) => {
+        #[pymethods]
+        impl $view {
+            pub fn __len__(&self, py: Python) -> PyResult<usize> {
+                view_len(py, &self.map)
+            }
+
+            pub fn __iter__(&self, py: Python) -> PyResult<MapIterator> {
+                view_iter(py, &self.map, $kind)
+            }
+
+            pub fn __contains__(&self, py: Python, element: &PyAny) -> PyResult<bool> {
+                self.holds(py, element)
+            }
+
+            pub fn __repr__(&self, py: Python) -> PyResult<String> {
+                view_repr(py, &self.map, $kind)
+            }
+
+            pub fn isdisjoint(&self, py: Python, other: &PyAny) -> PyResult<bool> {
+                // 'True' if no element of the other iterable is found in this view.
+                for element in other.iter()? {
+                    if self.holds(py, element?)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+
+            pub fn __and__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "intersection", false)
+            }
+
+            pub fn __rand__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "intersection", true)
+            }
+
+            pub fn __or__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "union", false)
+            }
+
+            pub fn __ror__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "union", true)
+            }
+
+            pub fn __sub__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "difference", false)
+            }
+
+            pub fn __rsub__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "difference", true)
+            }
+
+            pub fn __xor__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "symmetric_difference", false)
+            }
+
+            pub fn __rxor__(&self, py: Python, other: &PyAny) -> PyResult<PyObject> {
+                set_operation(py, &self.map, $kind, other, "symmetric_difference", true)
+            }
+
+            pub fn __richcmp__(&self, py: Python, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+                set_compare(py, &self.map, $kind, other, op)
+            }
+        }
+    };
+}
+
+// ---------------------------------------------------------------------------------
+// Implementation of MapKeys, MapValues & MapItems view-classes
+// ---------------------------------------------------------------------------------
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct MapKeys {
+    map: MapHandle,
+}
+
+impl MapKeys {
+    pub(crate) fn new(map: MapHandle) -> Self {
+        Self { map }
+    }
+
+    fn holds(&self, py: Python, key: &PyAny) -> PyResult<bool> {
+        // Key lookups go straight through the map's hashing -> O(1) like dict_keys.
+        self.map.with(py, |map| Ok(map.value_of(py, key)?.is_some()))
+    }
+}
+
+set_like_view!(MapKeys, ViewKind::Keys);
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct MapItems {
+    map: MapHandle,
+}
+
+impl MapItems {
+    pub(crate) fn new(map: MapHandle) -> Self {
+        Self { map }
+    }
+
+    fn holds(&self, py: Python, item: &PyAny) -> PyResult<bool> {
+        // Only (key, value) pairs can be members -> Look the key up, then compare the stored value.
+        let pair = match item.downcast::<PyTuple>() {
+            Ok(pair) if pair.len() == 2 => pair,
+            _ => return Ok(false),
+        };
+        let stored = self.map.with(py, |map| map.value_of(py, pair.get_item(0)?))?;
+        match stored {
+            Some(value) => keys_equal(value.as_ref(py), pair.get_item(1)?),
+            None => Ok(false),
+        }
+    }
+}
+
+set_like_view!(MapItems, ViewKind::Items);
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct MapValues {
+    map: MapHandle,
+}
+
+impl MapValues {
+    pub(crate) fn new(map: MapHandle) -> Self {
+        Self { map }
+    }
+}
+
+#[pymethods]
+impl MapValues {
+    pub fn __len__(&self, py: Python) -> PyResult<usize> {
+        view_len(py, &self.map)
+    }
+
+    pub fn __iter__(&self, py: Python) -> PyResult<MapIterator> {
+        view_iter(py, &self.map, ViewKind::Values)
+    }
+
+    pub fn __contains__(&self, py: Python, element: &PyAny) -> PyResult<bool> {
+        // Values aren't indexed -> Walk every entry, comparing by identity first like dict_values.
+        self.map.with(py, |map| {
+            let mut cursor = 0;
+            while let Some((position, _, value)) = map.entry_from(py, cursor) {
+                if keys_equal(value.as_ref(py), element)? {
+                    return Ok(true);
+                }
+                cursor = position + 1;
+            }
+            Ok(false)
+        })
+    }
+
+    pub fn __repr__(&self, py: Python) -> PyResult<String> {
+        view_repr(py, &self.map, ViewKind::Values)
+    }
+}
+
+pub(crate) fn register_views(py: Python, module: &PyModule) -> PyResult<()> {
+    // Let the views pass isinstance(view, KeysView / ValuesView / ItemsView) checks, like dict views.
+    let abc = py.import("collections.abc")?;
+    for (view, name) in [("MapKeys", "KeysView"), ("MapValues", "ValuesView"), ("MapItems", "ItemsView")] {
+        abc.getattr(name)?.call_method1("register", (module.getattr(view)?,))?;
+    }
+    Ok(())
+}
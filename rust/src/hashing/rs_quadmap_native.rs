@@ -1,298 +1,202 @@
-use pyo3::exceptions::PyValueError;
+// pyo3 0.18 expands the __richcmp__ slot of the #[pymethods] block below into nested impls that newer
+// compilers flag. The generated wrappers sit outside the impl block, so the allow has to cover this module.
+#![allow(non_local_definitions)]
+
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyKeyError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{IntoPyDict, PyDict, PyList, PyTuple};
 use pyo3::PyObject;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::hashing::rs_map_key::{keys_equal, MapHasher};
+use crate::hashing::rs_map_views::{EntryWalk, MapHandle, MapItems, MapIterator, MapKeys, MapValues};
+use crate::hashing::rs_map_protocol::{legacy_update, mapping_compare, pop_default, update_pairs};
+use crate::hashing::rs_map_sizing::LoadPolicy;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+
+// Open addressing degrades quickly past 3/4 full -> Grow at 75% (Tombstones included) & shrink below 10% by default.
+const DEFAULT_MAX_LOAD: f64 = 0.75;
+const DEFAULT_MIN_LOAD: f64 = 0.1;
 
 /// ---------------------------------------------------------------------------------
-/// Implementation Hashable Enum & Conversion of Python objects -> Rust data types
+/// Implementation of Slot Enum type
 /// ---------------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
 enum Slot {
     Empty,
     Tombstone,
-    Occupied((PyObject, PyObject)),
-}
-
-enum Hashable {
-    Int(i64),
-    Float(u64),
-    Str(String),
-    Bool(bool),
-}
-
-impl Hash for Hashable {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        match self {
-            Hashable::Int(i) => i.hash(state),
-            Hashable::Float(f) => f.hash(state),
-            Hashable::Str(s) => s.hash(state),
-            Hashable::Bool(b) => b.hash(state),
-        }
-    }
+    Occupied((PyObject, PyObject, usize)),
 }
 
 /// ---------------------------------------------------------------------------------
 /// Implementation of QuadMap-class & related operations
 /// ---------------------------------------------------------------------------------
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct QuadMap {
     capacity: usize,
+    min_capacity: usize,
     map_size: usize,
+    tombstones: usize,
+    version: u64,
+    policy: LoadPolicy,
+    hasher: MapHasher,
     series: Vec<Slot>
 }
 
 impl QuadMap {
-    fn python_to_rust(py: Python, item: &PyObject) -> PyResult<Hashable> {
-        // Converts Python native data types -> Rust native data types
-        if let Ok(i) = item.extract::<i64>(py) {
-            return Ok(Hashable::Int(i));
-        } else if let Ok(f) = item.extract::<f64>(py) {
-            return Ok(Hashable::Float(f.to_bits()));
-        } else if let Ok(s) = item.extract::<String>(py)  {
-            return Ok(Hashable::Str(s));
-        } else if let Ok(b) = item.extract::<bool>(py) {
-            return Ok(Hashable::Bool(b));
-        } else {
-            return Err(PyValueError::new_err("Unsupported data type for Rust conversion"));
-        }
+    fn generate_hash(&self, key: &PyAny) -> PyResult<usize> {
+        // Generates the full Hash value -> Entries keep it, so resizing never has to re-hash a key.
+        let hash_value = self.hasher.hash(key)?;
+        Ok(hash_value as usize)
     }
 
-    fn generate_hash<T: Hash>(&self, key: &T) -> usize {
-        // Generates the intial Hash index for Vector insertion
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        let hash_value = hasher.finish();
-        let map_capacity = self.capacity;
-        return (hash_value as usize) % map_capacity;
-    }
-}
-
-#[pymethods]
-impl QuadMap {
-    #[new]
-    pub fn new(capacity: Option<usize>) -> Self {
-        let qm_cap = capacity.unwrap_or(1024);
-        Self {
-            capacity: qm_cap,
-            map_size: 0,
-            series: vec![Slot::Empty; qm_cap],
-        }
+    fn probe(&self, hash: usize, quad_idx: usize) -> usize {
+        // Quadratic probing over triangular numbers -> Visits every Slot when the capacity is a power of two.
+        (hash % self.capacity + quad_idx * (quad_idx + 1) / 2) % self.capacity
     }
 
-    pub fn insert(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<bool> {
-        // Check if the current map size is above the internal capacity values -> Map if full!
+    fn find(&self, py: Python, key: &PyAny) -> PyResult<Option<usize>> {
+        // An empty map has no Slots to probe -> Value can't be found.
         let cap = self.capacity;
-        if self.map_size >= cap {
-            return Err(PyValueError::new_err(format!("Maximum capacity {} reached! Unable to insert key-value", cap)));
+        if cap == 0 {
+            return Ok(None);
         }
+        let hash = self.generate_hash(key)?;
 
-        // Convert key-value Rust data-type & produce hash-value for indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let hash = Self::generate_hash(&self, &rust_hash); 
-
-        // Iterate over the internal Series array -> Probe Chaining
+        // Follow the quadratic probe chain -> Tombstones are skipped, an Empty slot ends the chain.
         for quad_idx in 0..cap {
-
-            // Calculate the index for loop. 
-            let index = (hash + quad_idx * quad_idx) % cap;
-            // Match the Slot at specified index in internal Series array.
-            match &mut self.series[index] {
-                // If Slot::Empty -> Insert key-value tuple & increment map_size.
-                Slot::Empty => {
-                    self.series[index] = Slot::Occupied((key.clone_ref(py), value.clone_ref(py)));
-                    self.map_size+= 1;
-                    return Ok(true)
-                },
-                // If Slot::Tombstone -> Insert key-value tuple & increment map_size.
-                Slot::Tombstone => {
-                    self.series[index] = Slot::Occupied((key.clone_ref(py), value.clone_ref(py)));
-                    self.map_size += 1;
-                    return Ok(true)
-                },
-                // If Slot::Occupied -> Continue to next loop iteration (Begin Probe Chain).
+            let index = self.probe(hash, quad_idx);
+            match &self.series[index] {
                 Slot::Occupied(tuple) => {
-                    if tuple.0.as_ref(py).eq(key.as_ref(py))? {
-                        tuple.1 = value;
-                        return Ok(false)
+                    if tuple.2 == hash && keys_equal(tuple.0.as_ref(py), key)? {
+                        return Ok(Some(index));
                     }
                 },
+                Slot::Tombstone => continue,
+                Slot::Empty => return Ok(None),
             }
         }
-        // DEFAULT = Return a PyValueEror if the value was nuable to be inserted.
-        return Err(PyValueError::new_err(format!("Could not insert key {} into QuadMap", key)));
+        Ok(None)
     }
 
-    pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<PyObject> {
-        // Get maximum capacity number to utilise. 
-        let cap = self.capacity;
-
-        // Convert key Rust data-type & produce hash-value for indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let hash = Self::generate_hash(&self, &rust_hash);
-
-        // Iterate over the internal Series array -> Probe Chaining
-        for quad_idx in 0..cap {
-            // Calculate the index for loop. 
-            let index = (hash + quad_idx * quad_idx) % cap;
-            // Match the Slot at specified index in internal Series array.
-            match &mut self.series[index] {
-                // If Slot::Tombstone -> Continue to next loop iteration.
-                Slot::Tombstone => {
-                    continue;
-                },
-                // If Slot::Empty -> Raise ValueError (No entry with key-value found!).
-                Slot::Empty => {
-                    return Err(PyValueError::new_err(format!("Could not locate key {} in QuadMap", key)));
-                },
-                // If Slot::Occupied -> Remove & return value.
-                // Set the Slot at the removed value index to Slot::Tombstone. 
-                Slot::Occupied(tuple) => {
-                    if tuple.0.as_ref(py).eq(key.as_ref(py))? {
-                        let removed_value = tuple.1.clone_ref(py);
-                        self.series[index] = Slot::Tombstone;
-                        self.map_size -= 1;
-                        return Ok(removed_value);
-                    }
-                }
-            }
+    fn take_at(&mut self, index: usize) -> PyResult<Option<(PyObject, PyObject)>> {
+        // Replace the Slot with a Tombstone so later probe chains stay intact.
+        match std::mem::replace(&mut self.series[index], Slot::Tombstone) {
+            Slot::Occupied((key, value, _)) => {
+                self.map_size -= 1;
+                self.tombstones += 1;
+                self.version += 1;
+                self.shrink_if_sparse();
+                Ok(Some((key, value)))
+            },
+            other => {
+                self.series[index] = other;
+                Ok(None)
+            },
         }
-        // Default = Raise ValueError (No entry with key-value found!)
-        return Err(PyValueError::new_err(format!("Could not locate key {} in QuadMap", key)));
     }
 
-    pub fn update(&mut self, py: Python, key: PyObject, new_value: PyObject) -> PyResult<bool> {
-        // Get maximum capacity number to utilise. 
-        let cap = self.capacity;
-
-        // Convert key Rust data-type & produce hash-value for indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let hash = Self::generate_hash(&self, &rust_hash);
-
-        // Iterate over entire Series-array starting from 'Index'.
-        for quad_idx in 0..cap {
-
-            // Calculate the index for loop. 
-            let index = (hash + quad_idx * quad_idx) % cap;
-            // Match internal Slots.
-            match &mut self.series[index] {
-                // If Slot::Occupied -> Check to see if the correct key is present.
-                // If correct -> Update the value variable & return 'True'.
-                Slot::Occupied(tuple) => {
-                    if tuple.0.as_ref(py).eq(key.as_ref(py))? {
-                        tuple.1 = new_value;
-                        return Ok(true)
-                    }
+    fn place(&mut self, entry: (PyObject, PyObject, usize)) -> Option<(PyObject, PyObject, usize)> {
+        // Store the entry in the first Empty / Tombstone Slot of its probe chain -> Hand it back if the chain has none.
+        for quad_idx in 0..self.capacity {
+            let index = self.probe(entry.2, quad_idx);
+            match &self.series[index] {
+                Slot::Empty => {
+                    self.series[index] = Slot::Occupied(entry);
+                    return None;
                 },
-                // If Slot::Tombstone -> Continue Quadratic Probing.
                 Slot::Tombstone => {
-                    continue;
+                    self.series[index] = Slot::Occupied(entry);
+                    self.tombstones -= 1;
+                    return None;
                 },
-                // If Slot::Empty -> Value is not found. Return 'False'.
-                Slot::Empty => {
-                    return Ok(false);
-                }
+                Slot::Occupied(_) => continue,
             }
         }
-        // DEFAULT = Iterated over entire Series-array & key-value is not found.
-        Ok(false)
+        Some(entry)
     }
 
-    pub fn get(&self, py: Python, key: PyObject) -> PyResult<PyObject> {
-        // Get maximum capacity number to utilise.
-        let cap = self.capacity;
-
-        // Convert key Rust data-type & produce hash-value for indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let hash = Self::generate_hash(&self, &rust_hash);
-
-        // Iterate through intern Series array.
-        for quad_idx in 0..cap {
+    fn drain_entries(&mut self) -> Vec<(PyObject, PyObject, usize)> {
+        // Empty the Series-array & collect every stored entry.
+        std::mem::take(&mut self.series).into_iter().filter_map(|slot| match slot {
+            Slot::Occupied(entry) => Some(entry),
+            _ => None,
+        }).collect()
+    }
 
-            // Calculate the index for loop. 
-            let index = (hash + quad_idx * quad_idx) % cap;
-            // Match internal Slots.
-            match &self.series[index] {
-                // If Slot::Occupied -> Check to see if the correct key is present.
-                Slot::Occupied(tuple) => {
-                    if tuple.0.as_ref(py).eq(key.as_ref(py))? {
-                        let result = tuple.1.clone_ref(py);
-                        return Ok(result)
-                    }
-                },
-                // If Slot::Tombstone -> Continnue Quadratic Probe Chain.
-                Slot::Tombstone => {
-                    continue;
-                },
-                // If Slot::Empty -> Value is not found. Return 'None'.
-                Slot::Empty => {
-                    return Ok(py.None());
-                }
+    fn resize(&mut self, capacity: usize) {
+        // Move every entry into a fresh Series-array of 'capacity' Slots -> All Tombstones are dropped on the way.
+        let mut entries = self.drain_entries();
+        let mut capacity = capacity;
+        self.version += 1;
+        loop {
+            self.capacity = capacity;
+            self.tombstones = 0;
+            self.series = vec![Slot::Empty; capacity];
+            let stranded: Vec<_> = entries.into_iter().filter_map(|entry| self.place(entry)).collect();
+            if stranded.is_empty() {
+                return;
             }
+            // Some probe chain never reached a free Slot (only possible for non power-of-two capacities) -> Grow & retry.
+            entries = self.drain_entries();
+            entries.extend(stranded);
+            capacity = self.policy.grown(capacity, entries.len());
         }
-        // DEFAULT = Return None if the value was not found (Iterated over entire Map).
-        return Ok(py.None())
     }
 
-    pub fn contains(&self, py: Python, key: PyObject) -> PyResult<bool> {
-        // Get maximum capacity number to utilise.
-        let cap = self.capacity;
+    fn make_room(&mut self) {
+        // Tombstones lengthen probe chains just like entries, so both count towards the max load.
+        if self.policy.fits(self.map_size + self.tombstones + 1, self.capacity) {
+            return;
+        }
+        // Mostly Tombstones -> Purging them at the same capacity is enough, otherwise grow the Series-array.
+        if self.policy.fits(2 * (self.map_size + 1), self.capacity) {
+            self.resize(self.capacity);
+        } else {
+            self.resize(self.policy.grown(self.capacity, self.map_size + 1));
+        }
+    }
 
-        // Convert key Rust data-type & produce hash-value for indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let hash = Self::generate_hash(&self, &rust_hash);
+    fn shrink_if_sparse(&mut self) {
+        // Halve the Series-array once the entries drop below the min load (never under the initial capacity).
+        if let Some(capacity) = self.policy.shrunk(self.capacity, self.map_size, self.min_capacity) {
+            self.resize(capacity);
+        }
+    }
 
-        // Utilise loop structure to check internal Series-array for specified key-value.
-        for quad_idx in 0..cap {
+    fn probe_lengths(&self) -> (f64, usize) {
+        // Average & longest number of Slots a lookup of a stored key has to inspect.
+        let lengths: Vec<usize> = self.series.iter().enumerate().filter_map(|(index, slot)| match slot {
+            Slot::Occupied(tuple) => (0..self.capacity).position(|quad_idx| self.probe(tuple.2, quad_idx) == index).map(|steps| steps + 1),
+            _ => None,
+        }).collect();
+        let average = if lengths.is_empty() { 0.0 } else { lengths.iter().sum::<usize>() as f64 / lengths.len() as f64 };
+        (average, lengths.into_iter().max().unwrap_or(0))
+    }
 
-            // Calculate the index for loop. 
-            let index = (hash + quad_idx * quad_idx) % cap;
-            // Match internal Slots.
-            match &self.series[index] {
-                // If Slot::Occupied -> Check to see if the correct key is present.
-                Slot::Occupied(tuple) => {
-                    if tuple.0.as_ref(py).eq(key.as_ref(py))? {
-                        return Ok(true);
-                    }
-                },
-                // If Slot::Tombstone -> Continue Quadratic Probe Chain.
-                Slot::Tombstone => {
-                    continue;
-                },
-                // If Slot::Empty -> Value is not found. Return 'False'.
-                Slot::Empty => {
-                    return Ok(false);
-                }
+    fn lookup(&self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>> {
+        // Locate the key along its probe chain -> Return a reference to the stored value.
+        if let Some(index) = self.find(py, key)? {
+            if let Slot::Occupied(tuple) = &self.series[index] {
+                return Ok(Some(tuple.1.clone_ref(py)));
             }
         }
-        // DEFAULT = If iterated over the entire Series-array return 'False'.
-        Ok(false)
+        Ok(None)
     }
 
-    pub fn from_keys<'py>(&self, py: Python<'py>, iterable: &PyAny) -> PyResult<&'py PyList> {
-        // Initiate new Rust Vectors to store value.
-        let mut elements = Vec::new();
-
-        // Iterate over all keys elements in 'iterable' parameter.
-        for key_object in iterable.iter()? {
-            // Extract the key-value from behind Result-type.
-            let key = key_object?;
-            // Utilise internal .get() method to retrieve values.
-            let value = self.get(py, key.to_object(py))?;
-
-            // Check if the retrieved value is 'None' before adding to elements list.
-            if !value.is_none(py) {
-                elements.push(value);
+    fn replace(&mut self, py: Python, key: &PyAny, new_value: PyObject) -> PyResult<bool> {
+        // Locate the key along its probe chain -> Update the value if the key is present.
+        if let Some(index) = self.find(py, key)? {
+            if let Slot::Occupied(tuple) = &mut self.series[index] {
+                tuple.1 = new_value;
+                return Ok(true);
             }
         }
-        // Create a final PyList-object and return to user.
-        Ok(PyList::new(py, &elements))
+        Ok(false)
     }
 
-    pub fn keys<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+    fn key_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
         // Initialize a temporery vector array to store key-values in.
         let mut elements = Vec::new();
 
@@ -318,7 +222,7 @@ impl QuadMap {
         Ok(PyList::new(py, elements))
     }
 
-    pub fn values<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+    fn value_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
         // Initialize a temporery vector array to store values in.
         let mut elements = Vec::new();
 
@@ -344,7 +248,7 @@ impl QuadMap {
         Ok(PyList::new(py, elements))
     }
 
-    pub fn items<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+    fn item_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
         // Initialize a temporery vector array to store key-value pairs in.
         let mut elements = Vec::new();
 
@@ -369,13 +273,159 @@ impl QuadMap {
         // Convert the 'Elements' Vector to a Python native list structure.
         Ok(PyList::new(py, elements))
     }
+}
+
+impl EntryWalk for QuadMap {
+    fn name(&self) -> &'static str {
+        "QuadMap"
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn entry_count(&self) -> usize {
+        self.map_size
+    }
+
+    fn entry_from(&self, py: Python, cursor: usize) -> Option<(usize, PyObject, PyObject)> {
+        // Cursor = index into the Series-array -> Skip ahead to the next occupied Slot.
+        self.series.iter().enumerate().skip(cursor).find_map(|(index, slot)| match slot {
+            Slot::Occupied((key, value, _)) => Some((index, key.clone_ref(py), value.clone_ref(py))),
+            _ => None,
+        })
+    }
+
+    fn value_of(&self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>> {
+        self.lookup(py, key)
+    }
+}
+
+#[pymethods]
+impl QuadMap {
+    #[new]
+    pub fn new(capacity: Option<usize>, max_load: Option<f64>, min_load: Option<f64>, hasher: Option<&str>, seed: Option<u64>) -> PyResult<Self> {
+        let qm_cap = capacity.unwrap_or(1024);
+        let policy = LoadPolicy::new(max_load, min_load, DEFAULT_MAX_LOAD, DEFAULT_MIN_LOAD)?;
+        Ok(Self {
+            capacity: qm_cap,
+            min_capacity: qm_cap,
+            map_size: 0,
+            tombstones: 0,
+            version: 0,
+            policy,
+            hasher: MapHasher::new(hasher, seed)?,
+            series: vec![Slot::Empty; qm_cap],
+        })
+    }
+
+    pub fn insert(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<bool> {
+        // If the key is already stored -> Replace its value in place (dict semantics, no duplicate entries).
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if let Slot::Occupied(tuple) = &mut self.series[index] {
+                tuple.1 = value;
+            }
+            return Ok(false)
+        }
+
+        // Hash the key & produce hash-value for indexing (before touching the layout).
+        let hash = self.generate_hash(key.as_ref(py))?;
+
+        // Purge Tombstones or grow the Series-array if the new entry would push it above the max load.
+        self.make_room();
+
+        // Insert into the first free Slot of the probe chain -> Grow & retry if the chain has none.
+        let mut entry = (key, value, hash);
+        while let Some(stranded) = self.place(entry) {
+            entry = stranded;
+            self.resize(self.policy.grown(self.capacity, self.map_size + 1));
+        }
+        self.map_size += 1;
+        self.version += 1;
+        Ok(true)
+    }
+
+    pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<PyObject> {
+        // Locate the key along its probe chain -> Extract and return the stored value.
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if let Some((_, value)) = self.take_at(index)? {
+                return Ok(value);
+            }
+        }
+        // If not found -> Value could not be located!
+        Err(PyValueError::new_err(format!("Could not locate key {} in QuadMap", key)))
+    }
+
+    pub fn get(&self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        // Return the stored value, or 'default' (or 'None') if the key is not found.
+        match self.lookup(py, key.as_ref(py))? {
+            Some(value) => Ok(value),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    pub fn update(&mut self, py: Python, args: &PyTuple, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        // update(key, new_value) -> Replace a single value, returning 'True' if the key was found.
+        if let Some((key, new_value)) = legacy_update(args, kwargs)? {
+            return Ok(self.replace(py, key.as_ref(py), new_value)?.into_py(py));
+        }
+
+        // update(mapping_or_iterable, **kwargs) -> Insert or replace every pair, exactly like dict.update().
+        for (key, value) in update_pairs(args, kwargs)? {
+            self.insert(py, key, value)?;
+        }
+        Ok(py.None())
+    }
+
+    pub fn contains(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        // Locate the key along its probe chain.
+        Ok(self.find(py, key.as_ref(py))?.is_some())
+    }
+
+    pub fn from_keys<'py>(&self, py: Python<'py>, iterable: &PyAny) -> PyResult<&'py PyList> {
+        // Initiate new Rust Vectors to store value.
+        let mut elements = Vec::new();
+
+        // Iterate over all keys elements in 'iterable' parameter.
+        for key_object in iterable.iter()? {
+            // Extract the key-value from behind Result-type.
+            let key = key_object?;
+            // Utilise internal .get() method to retrieve values.
+            let value = self.get(py, key.to_object(py), None)?;
+
+            // Check if the retrieved value is 'None' before adding to elements list.
+            if !value.is_none(py) {
+                elements.push(value);
+            }
+        }
+        // Create a final PyList-object and return to user.
+        Ok(PyList::new(py, &elements))
+    }
+
+    pub fn keys(slf: &PyCell<Self>) -> MapKeys {
+        // Lazy, set-like view over the stored keys -> Nothing is copied until it is iterated.
+        MapKeys::new(MapHandle::Quad(slf.into()))
+    }
+
+    pub fn values(slf: &PyCell<Self>) -> MapValues {
+        // Lazy view over the stored values.
+        MapValues::new(MapHandle::Quad(slf.into()))
+    }
+
+    pub fn items(slf: &PyCell<Self>) -> MapItems {
+        // Lazy, set-like view over the stored (key, value) pairs.
+        MapItems::new(MapHandle::Quad(slf.into()))
+    }
 
     pub fn copy(&self, py: Python<'_>) -> PyResult<PyObject> {
         // Initiate a new, empty QuadMap-class.
-        let mut new_map = QuadMap::new(Some(self.capacity));
+        let mut new_map = QuadMap::new(Some(self.capacity), Some(self.policy.max_load), Some(self.policy.min_load), None, None)?;
+        new_map.min_capacity = self.min_capacity;
+        new_map.hasher = self.hasher.clone();
 
         // Iterate through all stored items.
-        for tuple in self.items(py)?.iter() {
+        for tuple in self.item_list(py)?.iter() {
             // Downcast entry to PyTuple to extract key & value pairs safely.
             let tup = tuple.downcast::<PyTuple>()?;
             let key = tup.get_item(0)?;
@@ -391,8 +441,9 @@ impl QuadMap {
     pub fn info<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
         // Extract teh necessary metrcis from internal variables & methods
         let percentage = self.percentage()?;
-        let keys = self.keys(py)?.into();
-        let values = self.values(py)?.into();
+        let (avg_probe, max_probe) = self.probe_lengths();
+        let keys = self.key_list(py)?.into();
+        let values = self.value_list(py)?.into();
 
         // Construct a Rust Vector consisting of indvidual Tuples (String, PyObject).
         let key_vals: Vec<(&str, PyObject)> = vec![
@@ -400,6 +451,13 @@ impl QuadMap {
             ("capacity", self.capacity.to_object(py)),
             ("size", self.map_size.to_object(py)),
             ("percentage", percentage.to_object(py)),
+            ("max_load", self.policy.max_load.to_object(py)),
+            ("min_load", self.policy.min_load.to_object(py)),
+            ("hash_scheme", self.hasher.scheme().to_object(py)),
+            ("seed", self.hasher.seed().to_object(py)),
+            ("tombstones", self.tombstones.to_object(py)),
+            ("avg_probe_length", avg_probe.to_object(py)),
+            ("max_probe_length", max_probe.to_object(py)),
             ("keys", keys),
             ("values", values)
         ];
@@ -425,15 +483,197 @@ impl QuadMap {
         Ok(percent)
     }
 
+    pub fn reserve(&mut self, additional: usize) -> PyResult<()> {
+        // Pre-size the Series-array so 'additional' more entries fit without growing again.
+        let needed = self.map_size + additional;
+        if !self.policy.fits(needed + self.tombstones, self.capacity) {
+            self.resize(self.policy.capacity_for(needed).max(self.capacity));
+        }
+        Ok(())
+    }
+
+    pub fn shrink_to_fit(&mut self) -> PyResult<()> {
+        // Rebuild at the smallest capacity holding the current entries within the max load -> Drops every Tombstone too.
+        let capacity = self.policy.capacity_for(self.map_size);
+        self.min_capacity = self.min_capacity.min(capacity);
+        if capacity < self.capacity || self.tombstones > 0 {
+            self.resize(capacity.min(self.capacity));
+        }
+        Ok(())
+    }
+
+    pub fn compact(&mut self) -> PyResult<usize> {
+        // Rebuild the Series-array at its current capacity -> Returns the number of Tombstones purged.
+        let purged = self.tombstones;
+        if purged > 0 {
+            self.resize(self.capacity);
+        }
+        Ok(purged)
+    }
+
     pub fn is_empty(&self) -> PyResult<bool> {
         // Check if the internal Series-array contains no current entries. 
         Ok(self.map_size <= 0)
     }
 
     pub fn clear(&mut self) -> PyResult<()> {
-        // Set internal values in Series-array to Slot::Empty & reset variable 'map_size' to 0.
+        // Drop back to the initial capacity with every Slot set to Slot::Empty & reset variable 'map_size' to 0.
         self.map_size = 0;
+        self.tombstones = 0;
+        self.version += 1;
+        self.capacity = self.min_capacity;
         self.series = vec![Slot::Empty; self.capacity];
         Ok(())
     }
+
+    pub fn setdefault(&mut self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        // Return the stored value, inserting 'default' (or 'None') first if the key is missing.
+        if let Some(value) = self.lookup(py, key.as_ref(py))? {
+            return Ok(value);
+        }
+        let value = default.unwrap_or_else(|| py.None());
+        self.insert(py, key, value.clone_ref(py))?;
+        Ok(value)
+    }
+
+    #[pyo3(signature = (key, *default))]
+    pub fn pop(&mut self, py: Python, key: PyObject, default: &PyTuple) -> PyResult<PyObject> {
+        // Remove & return the value -> Fall back to 'default', or raise KeyError if none was given.
+        let default = pop_default(default)?;
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if let Some((_, value)) = self.take_at(index)? {
+                return Ok(value);
+            }
+        }
+        default.ok_or_else(|| PyKeyError::new_err(key))
+    }
+
+    pub fn popitem(&mut self) -> PyResult<(PyObject, PyObject)> {
+        // Remove the last occupied Slot of the Series-array & return it as a (key, value) pair.
+        if let Some(index) = self.series.iter().rposition(|slot| matches!(slot, Slot::Occupied(_))) {
+            if let Some(pair) = self.take_at(index)? {
+                return Ok(pair);
+            }
+        }
+        Err(PyKeyError::new_err("popitem(): QuadMap is empty"))
+    }
+
+    pub fn __getitem__(&self, py: Python, key: PyObject) -> PyResult<PyObject> {
+        // map[key] -> Raise KeyError if the key is not found.
+        match self.lookup(py, key.as_ref(py))? {
+            Some(value) => Ok(value),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    pub fn __setitem__(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<()> {
+        // map[key] = value -> Insert a new pair or replace the existing value.
+        self.insert(py, key, value)?;
+        Ok(())
+    }
+
+    pub fn __delitem__(&mut self, py: Python, key: PyObject) -> PyResult<()> {
+        // del map[key] -> Raise KeyError if the key is not found.
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if self.take_at(index)?.is_some() {
+                return Ok(());
+            }
+        }
+        Err(PyKeyError::new_err(key))
+    }
+
+    pub fn __contains__(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        self.contains(py, key)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.map_size
+    }
+
+    pub fn __iter__(slf: &PyCell<Self>) -> PyResult<MapIterator> {
+        // Iterate lazily over the stored keys, like iterating over a dict.
+        MapIterator::keys(slf.py(), MapHandle::Quad(slf.into()))
+    }
+
+    pub fn __richcmp__(&self, py: Python, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        // Equal to any mapping (dict, QuadMap, ...) holding the same key-value pairs.
+        mapping_compare(py, self, other, op)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Serialise the Series-array slot by slot -> Occupied slots as (key, value), everything else as None.
+        let mut slots = Vec::with_capacity(self.series.len());
+        let mut tombstones = Vec::new();
+        for (index, slot) in self.series.iter().enumerate() {
+            match slot {
+                Slot::Occupied((key, value, _)) => slots.push((key.clone_ref(py), value.clone_ref(py)).to_object(py)),
+                Slot::Tombstone => {
+                    tombstones.push(index);
+                    slots.push(py.None());
+                },
+                Slot::Empty => slots.push(py.None()),
+            }
+        }
+        // Tombstone positions are recorded for layout validation -> restoring re-inserts every entry, dropping them.
+        let sizing = (self.policy.max_load, self.policy.min_load, self.min_capacity);
+        let hashing = (self.hasher.scheme(), self.hasher.seed());
+        Ok((STATE_VERSION, self.capacity, self.map_size, slots, tombstones, sizing, hashing).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        // Validate the state-tuple & extract the internal variables.
+        let state = unpack_state(state, "QuadMap", 6)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let map_size: usize = state.get_item(2)?.extract()?;
+        let slots: Vec<Option<(PyObject, PyObject)>> = state.get_item(3)?.extract()?;
+        let tombstones: Vec<usize> = state.get_item(4)?.extract()?;
+        let (max_load, min_load, min_capacity): (f64, f64, usize) = state.get_item(5)?.extract()?;
+        // A SipHash map gets a fresh random key -> Every entry is re-inserted anyway.
+        let (scheme, seed): (String, Option<u64>) = state.get_item(6)?.extract()?;
+
+        // Raise Error if the Series-array does not match the stored capacity.
+        if slots.len() != capacity || map_size > capacity || min_capacity > capacity {
+            return Err(invalid_state("QuadMap", "inconsistent slot layout"));
+        }
+
+        // Tombstones must sit on slots that are otherwise empty.
+        if tombstones.iter().any(|&index| !matches!(slots.get(index), Some(None))) {
+            return Err(invalid_state("QuadMap", "tombstone on an occupied or missing slot"));
+        }
+
+        // Re-insert every entry -> Python's hash() is salted per process, so stored positions can't be reused.
+        let mut restored = QuadMap::new(Some(capacity), Some(max_load), Some(min_load), Some(&scheme), seed)
+            .map_err(|_| invalid_state("QuadMap", "invalid load factors or hasher"))?;
+        restored.min_capacity = min_capacity;
+        for (key, value) in slots.into_iter().flatten() {
+            restored.insert(py, key, value)?;
+        }
+        if restored.map_size != map_size {
+            return Err(invalid_state("QuadMap", "stored size does not match its entries"));
+        }
+
+        restored.version = self.version + 1;
+        *self = restored;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.capacity, self.policy.max_load, self.policy.min_load, self.hasher.scheme(), self.hasher.seed());
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        // Shallow copy -> Same slot layout, referencing the same keys & values.
+        let mut copied = QuadMap::new(Some(0), None, None, None, None)?;
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.into_py(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        // Create an empty QuadMap, then restore a recursively copied state into it.
+        let copied = Py::new(py, QuadMap::new(Some(0), None, None, None, None)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
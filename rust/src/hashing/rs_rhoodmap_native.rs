@@ -1,13 +1,25 @@
-use pyo3::exceptions::PyValueError;
+// pyo3 0.18 expands the __richcmp__ slot of the #[pymethods] block below into nested impls that newer
+// compilers flag. The generated wrappers sit outside the impl block, so the allow has to cover this module.
+#![allow(non_local_definitions)]
+
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyKeyError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{IntoPyDict, PyDict, PyList, PyTuple};
 use pyo3::PyObject;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::hashing::rs_map_key::{keys_equal, MapHasher};
+use crate::hashing::rs_map_views::{EntryWalk, MapHandle, MapItems, MapIterator, MapKeys, MapValues};
+use crate::hashing::rs_map_protocol::{legacy_update, mapping_compare, pop_default, update_pairs};
+use crate::hashing::rs_map_sizing::LoadPolicy;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 use std::mem::swap;
 
+// Robin Hood probing keeps chains short even when nearly full -> Grow at 90% & shrink below 10% by default.
+const DEFAULT_MAX_LOAD: f64 = 0.9;
+const DEFAULT_MIN_LOAD: f64 = 0.1;
+
 /// ---------------------------------------------------------------------------------
-/// Implementation of Enum types & Conversion of Python objects -> Rust data types
+/// Implementation of Slot Enum type
 /// ---------------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
@@ -16,24 +28,6 @@ enum Slot {
     Occupied(RobinBucket),
 }
 
-enum Hashable {
-    Int(i64),
-    Float(u64),
-    Str(String),
-    Bool(bool),
-}
-
-impl Hash for Hashable {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        match self {
-            Hashable::Int(i) => i.hash(state),
-            Hashable::Float(f) => f.hash(state),
-            Hashable::Str(s) => s.hash(state),
-            Hashable::Bool(b) => b.hash(state),
-        }
-    }
-}
-
 /// ---------------------------------------------------------------------------------
 /// Implementation of Robin Bucket structure/class & related operations
 /// ---------------------------------------------------------------------------------
@@ -62,112 +56,78 @@ impl RobinBucket {
 /// Implementation of RhoodMap structure/class & related operations
 /// ---------------------------------------------------------------------------------
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct RhoodMap {
     capacity: usize,
+    min_capacity: usize,
     map_size: usize,
+    version: u64,
+    policy: LoadPolicy,
+    hasher: MapHasher,
     series: Vec<Slot>,
 }
 
 impl RhoodMap {
-    fn python_to_rust(py: Python, item: &PyObject) -> PyResult<Hashable> {
-        // Converts Python native data types -> Rust native data types
-        if let Ok(i) = item.extract::<i64>(py) {
-            return Ok(Hashable::Int(i));
-        } else if let Ok(f) = item.extract::<f64>(py) {
-            return Ok(Hashable::Float(f.to_bits()));
-        } else if let Ok(s) = item.extract::<String>(py)  {
-            return Ok(Hashable::Str(s));
-        } else if let Ok(b) = item.extract::<bool>(py) {
-            return Ok(Hashable::Bool(b));
-        } else {
-            return Err(PyValueError::new_err("Unsupported data type for Rust conversion"));
-        }
-    }
-
-    fn generate_hash<T: Hash>(&self, key: &T) -> usize {
-        // Generates the intial Hash index for Robin Hood insertion
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        let hash_value = hasher.finish();
-        let map_capacity = self.capacity;
-        return (hash_value as usize) % map_capacity;
+    fn generate_hash(&self, key: &PyAny) -> PyResult<usize> {
+        // Generates the full Hash value -> Buckets keep it, so resizing never has to re-hash a key.
+        let hash_value = self.hasher.hash(key)?;
+        Ok(hash_value as usize)
     }
 
-    fn shift_slots(&mut self, mut index: usize) -> PyResult<()> {
-        // Loop until internal conditions are met -> Slot::Empty / Slot::Occupied
-        loop {
-            // Match Slot at the current index.
-            match self.series[index].clone() {
-                // If Slot == Empty -> Break from current Loop.
-                Slot::Empty => {
-                    break;
-                },
-                // If Slot == Occupied -> Check for further condiftions:
-                Slot::Occupied(mut bucket) => {
-                    // If Bucket's distance is 0 (Bucket is at 'Home' square), break from current loop.
-                    if bucket.distance == 0 {
-                        break;
-                    }
-
-                    // Decrement the current Bucket's probe distance by 1 & calculate previous index.
-                    bucket.distance -= 1;
-                    let prev_idx = if index == 0 {
-                        self.capacity - 1
-                    } else {
-                        index - 1
-                    };
-
-                    // Shift the Slots back 1 space & set the current to Empty.
-                    self.series[prev_idx] = Slot::Occupied(bucket);
-                    self.series[index] = Slot::Empty;
+    fn find(&self, py: Python, key: &PyAny) -> PyResult<Option<usize>> {
+        // An empty map has no Slots to probe -> Value can't be found.
+        if self.capacity == 0 {
+            return Ok(None);
+        }
+        let hash = self.generate_hash(key)?;
+        let mut index = hash % self.capacity;
 
-                    // Increment the index counter by 1 (Cyclical counter).
-                    index = (index + 1) % self.capacity;
+        // Walk the probe chain - Robin Hood ordering lets the search stop as soon as a Bucket sits closer to home than the key would.
+        for distance in 0..self.capacity {
+            match &self.series[index] {
+                Slot::Empty => return Ok(None),
+                Slot::Occupied(bucket) => {
+                    if bucket.distance < distance {
+                        return Ok(None);
+                    }
+                    if bucket.hash == hash && keys_equal(bucket.key.as_ref(py), key)? {
+                        return Ok(Some(index));
+                    }
                 }
             }
+            // Increment index by 1 (Cyclical counter).
+            index = (index + 1) % self.capacity;
         }
-        // Returns PyResult<Ok> when loop is finished shifting necessary Slots.
-        Ok(())
+        Ok(None)
     }
-}
 
-#[pymethods]
-impl RhoodMap {
-    #[new]
-    pub fn new(capacity: Option<usize>) -> Self {
-        let rhm_cap = capacity.unwrap_or(1024);
-        Self {
-            capacity: rhm_cap,
-            map_size: 0,
-            series: vec![Slot::Empty; rhm_cap],
+    fn take_at(&mut self, index: usize) -> PyResult<Option<(PyObject, PyObject)>> {
+        // Empty the Slot & shift the following Buckets back so every probe chain stays intact.
+        match std::mem::replace(&mut self.series[index], Slot::Empty) {
+            Slot::Occupied(bucket) => {
+                self.map_size -= 1;
+                self.version += 1;
+                self.shift_slots((index + 1) % self.capacity)?;
+                self.shrink_if_sparse();
+                Ok(Some((bucket.key, bucket.value)))
+            },
+            other => {
+                self.series[index] = other;
+                Ok(None)
+            },
         }
     }
 
-    pub fn insert(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<bool> {
-        // check if the map size is currently above or equal to capacity - Map is full!
-        if self.map_size >= self.capacity {
-            return Err(PyValueError::new_err(format!("Maximum capacity ({}) reached! Unable to insert key-value", self.capacity)));
-        }
-
-        // Convert key to Rust data type & produce hash-value.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let mut index = Self::generate_hash(&self, &rust_hash);
-
-        // Generate the new Bucket to insert.
-        let mut new_bucket = RobinBucket::new(key.clone_ref(py), value, index);
-        
-        // Iterate over internal Vectors starting at 'Index'.
-        for _ in 0..self.capacity {
-            // Match current Slot to determine action.
+    fn place(&mut self, mut new_bucket: RobinBucket) {
+        // Robin Hood insertion -> Walk from the home index, swapping with every Bucket sitting closer to its own home.
+        let mut index = new_bucket.hash % self.capacity;
+        new_bucket.distance = 0;
+        loop {
             match &mut self.series[index] {
-                // If Slot::Empty -> Insert new Bucket and increment Map size.
                 Slot::Empty => {
                     self.series[index] = Slot::Occupied(new_bucket);
-                    self.map_size += 1;
-                    return Ok(true);
+                    return;
                 },
-                // If Slot::Occupied -> If the probe distance is higher, then take the new slot.
                 Slot::Occupied(bucket) => {
                     if new_bucket.distance >= bucket.distance {
                         swap(bucket, &mut new_bucket);
@@ -178,139 +138,96 @@ impl RhoodMap {
             new_bucket.distance += 1;
             index = (index + 1) % self.capacity;
         }
-        // DEFAULT = Raise an Error if attempted to insert the value too many times without success.
-        return Err(PyValueError::new_err(format!("Could not insert key {} into RhoodMap", key)));
     }
 
-    pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<PyObject> {
-        // Convert key to Rust data type & produce hash-value.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let mut index = Self::generate_hash(&self, &rust_hash);
-
-        // Iterate over internal Rust Vectors to check Slots.
-        for _ in 0..self.capacity {
-            // Match Slot at current index -> Slot::Empty / Slot::Occupied.
-            match &mut self.series[index] {
-                // If current Slot is Empty -> Value could not be found!
-                Slot::Empty => {
-                    return Err(PyValueError::new_err(format!("Could not locate key {} in rhoodMap", key)));
-                },
-                // If current Slot is Occupied -> Check if the internal key matches.
-                // If it matches -> Extract and return internal value & shift slots to ensure probe chain.
-                Slot::Occupied(bucket) => {
-                    if bucket.key.as_ref(py).eq(key.as_ref(py))? {
-                        let removed_value = bucket.value.clone_ref(py);
-                        self.series[index] = Slot::Empty;
-                        self.map_size -= 1;
-                        self.shift_slots((index + 1) % self.capacity)?;
-                        return Ok(removed_value);
-                    }
-                }
+    fn resize(&mut self, capacity: usize) {
+        // Move every Bucket into a fresh Series-array of 'capacity' Slots -> Stored hashes make this free of Python calls.
+        let old_series = std::mem::replace(&mut self.series, vec![Slot::Empty; capacity]);
+        self.capacity = capacity;
+        self.version += 1;
+        for slot in old_series {
+            if let Slot::Occupied(bucket) = slot {
+                self.place(bucket);
             }
-            // Increment current 'Index' by 1 (Cylical counter).
-            index = (index + 1) % self.capacity;
         }
-        // DEFAULT = Raise an Error if attempted to insert the value too many times without success.
-        return Err(PyValueError::new_err(format!("Could not locate key {} in rhoodMap", key)));
     }
 
-    pub fn get(&self, py: Python, key: PyObject) -> PyResult<PyObject> {
-        // Convert key to Rust data type & produce hash-value for initial indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let mut index = Self::generate_hash(&self, &rust_hash);
-
-        // Iterate over internal rhoodMap Vector - If iterate full length and no return, Vector is full!
-        for _ in 0..self.capacity {
-            // Match Slot at 'Index'.
-            match &self.series[index] {
-                // If Slot::Empty -> Return 'None' (Value not found!).
-                Slot::Empty => {
-                    return Ok(py.None());
-                },
-                // If Slot::Occupied -> Return stored value from RobinBucket (Value found!).
-                Slot::Occupied(bucket) => {
-                    if bucket.key.as_ref(py).eq(key.as_ref(py))? {
-                        return Ok(bucket.value.clone_ref(py));
-                    }
-                }
-            }
-            // Increment index by 1 (Cyclical counter).
-            index = (index + 1) % self.capacity;
+    fn shrink_if_sparse(&mut self) {
+        // Halve the Series-array once the entries drop below the min load (never under the initial capacity).
+        if let Some(capacity) = self.policy.shrunk(self.capacity, self.map_size, self.min_capacity) {
+            self.resize(capacity);
         }
-        // DEFAULT = Iterated over entire .Series Vector and no value was found!
-        Ok(py.None())
     }
 
-    pub fn update(&mut self, py: Python, key: PyObject, new_value: PyObject) -> PyResult<bool> {
-        // Convert key to Rust data type & produce hash-value for initial indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let mut index = Self::generate_hash(&self, &rust_hash);
+    fn probe_lengths(&self) -> (f64, usize) {
+        // Average & longest number of Slots a lookup of a stored key has to inspect.
+        let lengths: Vec<usize> = self.series.iter().filter_map(|slot| match slot {
+            Slot::Occupied(bucket) => Some(bucket.distance + 1),
+            Slot::Empty => None,
+        }).collect();
+        let average = if lengths.is_empty() { 0.0 } else { lengths.iter().sum::<usize>() as f64 / lengths.len() as f64 };
+        (average, lengths.into_iter().max().unwrap_or(0))
+    }
 
-        // Internal loop to iterate over entries starting from hashed index
-        loop {
-            match &mut self.series[index] {
-                // If the loop encounters an 'Empty' slot -> No updating occurs.
-                Slot::Empty => {
-                    return Ok(false);
-                },
-                // If the loop encounters an 'Occupied' slot -> Check if 'Key' matches and then update.
-                Slot::Occupied(bucket) => {
-                    if bucket.key.as_ref(py).eq(key.as_ref(py))? {
-                        bucket.value = new_value.clone_ref(py);
-                        return Ok(true);
-                    }
-                }
+    fn lookup(&self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>> {
+        // Locate the key along its probe chain -> Return a reference to the stored value.
+        if let Some(index) = self.find(py, key)? {
+            if let Slot::Occupied(bucket) = &self.series[index] {
+                return Ok(Some(bucket.value.clone_ref(py)));
             }
-            // Increment the index value by 1 (Cyclical counter). 
-            index = (index + 1) % self.capacity;
         }
+        Ok(None)
     }
 
-    pub fn contains(&self, py: Python, key: PyObject) -> PyResult<bool> {
-        // Convert key to Rust data type & produce hash-value for initial indexing.
-        let rust_hash = Self::python_to_rust(py, &key)?;
-        let mut index = Self::generate_hash(&self, &rust_hash);
+    fn replace(&mut self, py: Python, key: &PyAny, new_value: PyObject) -> PyResult<bool> {
+        // Locate the key along its probe chain -> Update the value if the key is present.
+        if let Some(index) = self.find(py, key)? {
+            if let Slot::Occupied(bucket) = &mut self.series[index] {
+                bucket.value = new_value;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
 
-        // Internal loop to iterate over entries starting from hashed index
+    fn shift_slots(&mut self, mut index: usize) -> PyResult<()> {
+        // Loop until internal conditions are met -> Slot::Empty / Slot::Occupied
         loop {
-            match &self.series[index] {
-                // If the loop encounters an 'Empty' slot -> Return 'False'.
+            // Match Slot at the current index.
+            match self.series[index].clone() {
+                // If Slot == Empty -> Break from current Loop.
                 Slot::Empty => {
-                    return Ok(false)
+                    break;
                 },
-                // If the loop encounters an 'Occupied' slot -> Check the stored 'Key' value.
-                Slot::Occupied(bukcet) => {
-                    if bukcet.key.as_ref(py).eq(key.as_ref(py))? {
-                        return Ok(true);
+                // If Slot == Occupied -> Check for further condiftions:
+                Slot::Occupied(mut bucket) => {
+                    // If Bucket's distance is 0 (Bucket is at 'Home' square), break from current loop.
+                    if bucket.distance == 0 {
+                        break;
                     }
-                }
-            }
-            // Increment the index value by 1 (Cyclical counter)
-            index = (index - 1) % self.capacity;
-        }
-    }
 
-    pub fn from_keys<'py>(&self, py: Python<'py>, iterable: &PyAny) -> PyResult<&'py PyList> {
-        // Initiate new Vector list.
-        let mut elements = Vec::new();
+                    // Decrement the current Bucket's probe distance by 1 & calculate previous index.
+                    bucket.distance -= 1;
+                    let prev_idx = if index == 0 {
+                        self.capacity - 1
+                    } else {
+                        index - 1
+                    };
 
-        // Iterate over all key elements in Iterable parameter.
-        for key_object in iterable.iter()? {
-            // Extract the key from behind Result-type.
-            let key = key_object?;
-            // Use internal .get() method to extract final value.
-            let value = self.get(py, key.to_object(py))?;
+                    // Shift the Slots back 1 space & set the current to Empty.
+                    self.series[prev_idx] = Slot::Occupied(bucket);
+                    self.series[index] = Slot::Empty;
 
-            // Check if the value recieved is 'None' before adding to elements Vec.
-            if !value.is_none(py) {
-                elements.push(value);
+                    // Increment the index counter by 1 (Cyclical counter).
+                    index = (index + 1) % self.capacity;
+                }
             }
         }
-        // Convert and return the elements list.
-        Ok(PyList::new(py, &elements))
+        // Returns PyResult<Ok> when loop is finished shifting necessary Slots.
+        Ok(())
     }
 
-    pub fn keys<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+    fn key_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
         // Instantializa new Rust Vectors to store key-values.
         let mut elements = Vec::new();
 
@@ -330,7 +247,7 @@ impl RhoodMap {
         Ok(PyList::new(py, elements))
     }
 
-    pub fn values<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+    fn value_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
         // Instantializa new Rust Vectors to store values.
         let mut elements = Vec::new();
 
@@ -350,7 +267,7 @@ impl RhoodMap {
         Ok(PyList::new(py, elements))
     }
 
-    pub fn items<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+    fn item_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
         // Instantializa new Rust Vectors to store key-value pairs.
         let mut elements = Vec::new();
 
@@ -369,13 +286,156 @@ impl RhoodMap {
         // Convert 'Elements' vectors into a Python native list.
         Ok(PyList::new(py, elements))
     }
+}
+
+impl EntryWalk for RhoodMap {
+    fn name(&self) -> &'static str {
+        "RhoodMap"
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn entry_count(&self) -> usize {
+        self.map_size
+    }
+
+    fn entry_from(&self, py: Python, cursor: usize) -> Option<(usize, PyObject, PyObject)> {
+        // Cursor = index into the Series-array -> Skip ahead to the next occupied Slot.
+        self.series.iter().enumerate().skip(cursor).find_map(|(index, slot)| match slot {
+            Slot::Occupied(bucket) => Some((index, bucket.key.clone_ref(py), bucket.value.clone_ref(py))),
+            Slot::Empty => None,
+        })
+    }
+
+    fn value_of(&self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>> {
+        self.lookup(py, key)
+    }
+}
+
+#[pymethods]
+impl RhoodMap {
+    #[new]
+    pub fn new(capacity: Option<usize>, max_load: Option<f64>, min_load: Option<f64>, hasher: Option<&str>, seed: Option<u64>) -> PyResult<Self> {
+        let rhm_cap = capacity.unwrap_or(1024);
+        let policy = LoadPolicy::new(max_load, min_load, DEFAULT_MAX_LOAD, DEFAULT_MIN_LOAD)?;
+        Ok(Self {
+            capacity: rhm_cap,
+            min_capacity: rhm_cap,
+            map_size: 0,
+            version: 0,
+            policy,
+            hasher: MapHasher::new(hasher, seed)?,
+            series: vec![Slot::Empty; rhm_cap],
+        })
+    }
+
+    pub fn insert(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<bool> {
+        // If the key is already stored -> Replace its value in place (dict semantics, no duplicate entries).
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if let Slot::Occupied(bucket) = &mut self.series[index] {
+                bucket.value = value;
+            }
+            return Ok(false);
+        }
+
+        // Hash the key before touching the layout -> A failing __hash__ leaves the map unchanged.
+        let hash = self.generate_hash(key.as_ref(py))?;
+
+        // Grow the Series-array first if the new entry would push it above the max load.
+        if !self.policy.fits(self.map_size + 1, self.capacity) {
+            self.resize(self.policy.grown(self.capacity, self.map_size + 1));
+        }
+
+        // Place the new Bucket along its probe chain & increment Map size.
+        self.place(RobinBucket::new(key, value, hash));
+        self.map_size += 1;
+        self.version += 1;
+        Ok(true)
+    }
+
+    pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<PyObject> {
+        // Locate the key along its probe chain -> Extract and return the stored value.
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if let Some((_, value)) = self.take_at(index)? {
+                return Ok(value);
+            }
+        }
+        // If not found -> Value could not be located!
+        Err(PyValueError::new_err(format!("Could not locate key {} in RhoodMap", key)))
+    }
+
+    pub fn get(&self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        // Return the stored value, or 'default' (or 'None') if the key is not found.
+        match self.lookup(py, key.as_ref(py))? {
+            Some(value) => Ok(value),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    pub fn update(&mut self, py: Python, args: &PyTuple, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        // update(key, new_value) -> Replace a single value, returning 'True' if the key was found.
+        if let Some((key, new_value)) = legacy_update(args, kwargs)? {
+            return Ok(self.replace(py, key.as_ref(py), new_value)?.into_py(py));
+        }
+
+        // update(mapping_or_iterable, **kwargs) -> Insert or replace every pair, exactly like dict.update().
+        for (key, value) in update_pairs(args, kwargs)? {
+            self.insert(py, key, value)?;
+        }
+        Ok(py.None())
+    }
+
+    pub fn contains(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        // Locate the key along its probe chain.
+        Ok(self.find(py, key.as_ref(py))?.is_some())
+    }
+
+    pub fn from_keys<'py>(&self, py: Python<'py>, iterable: &PyAny) -> PyResult<&'py PyList> {
+        // Initiate new Vector list.
+        let mut elements = Vec::new();
+
+        // Iterate over all key elements in Iterable parameter.
+        for key_object in iterable.iter()? {
+            // Extract the key from behind Result-type.
+            let key = key_object?;
+            // Use internal .get() method to extract final value.
+            let value = self.get(py, key.to_object(py), None)?;
+
+            // Check if the value recieved is 'None' before adding to elements Vec.
+            if !value.is_none(py) {
+                elements.push(value);
+            }
+        }
+        // Convert and return the elements list.
+        Ok(PyList::new(py, &elements))
+    }
+
+    pub fn keys(slf: &PyCell<Self>) -> MapKeys {
+        // Lazy, set-like view over the stored keys -> Nothing is copied until it is iterated.
+        MapKeys::new(MapHandle::Rhood(slf.into()))
+    }
+
+    pub fn values(slf: &PyCell<Self>) -> MapValues {
+        // Lazy view over the stored values.
+        MapValues::new(MapHandle::Rhood(slf.into()))
+    }
+
+    pub fn items(slf: &PyCell<Self>) -> MapItems {
+        // Lazy, set-like view over the stored (key, value) pairs.
+        MapItems::new(MapHandle::Rhood(slf.into()))
+    }
 
     pub fn copy(&self, py: Python<'_>) -> PyResult<PyObject> {
         // Instantiate an empty rhoodMap variable.
-        let mut new_map = RhoodMap::new(Some(self.capacity));
+        let mut new_map = RhoodMap::new(Some(self.capacity), Some(self.policy.max_load), Some(self.policy.min_load), None, None)?;
+        new_map.min_capacity = self.min_capacity;
+        new_map.hasher = self.hasher.clone();
 
         // Iterate through all stored items.
-        for tuple in self.items(py)?.iter() {
+        for tuple in self.item_list(py)?.iter() {
             // Downcast entry to PyTuple to extract key & value pairs safely.
             let tup = tuple.downcast::<PyTuple>()?;
             let key = tup.get_item(0)?;
@@ -391,8 +451,9 @@ impl RhoodMap {
     pub fn info<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
         // Extract the necessary metrics from internal variables
         let percentage = self.percentage()?;
-        let keys = self.keys(py)?.into();
-        let values = self.values(py)?.into();
+        let (avg_probe, max_probe) = self.probe_lengths();
+        let keys = self.key_list(py)?.into();
+        let values = self.value_list(py)?.into();
 
         // Contruct a Rust Vector consisting of individual Tuples(String, Object).
         let key_vals: Vec<(&str, PyObject)> = vec![
@@ -400,6 +461,13 @@ impl RhoodMap {
             ("capacity", self.capacity.to_object(py)),
             ("size", self.map_size.to_object(py)),
             ("percentage", percentage.to_object(py)),
+            ("max_load", self.policy.max_load.to_object(py)),
+            ("min_load", self.policy.min_load.to_object(py)),
+            ("hash_scheme", self.hasher.scheme().to_object(py)),
+            ("seed", self.hasher.seed().to_object(py)),
+            ("tombstones", 0usize.to_object(py)),
+            ("avg_probe_length", avg_probe.to_object(py)),
+            ("max_probe_length", max_probe.to_object(py)),
             ("keys", keys),
             ("values", values),
         ];
@@ -425,15 +493,184 @@ impl RhoodMap {
         Ok(precentage)
     }
 
+    pub fn reserve(&mut self, additional: usize) -> PyResult<()> {
+        // Pre-size the Series-array so 'additional' more entries fit without growing again.
+        let needed = self.map_size + additional;
+        if !self.policy.fits(needed, self.capacity) {
+            self.resize(self.policy.capacity_for(needed));
+        }
+        Ok(())
+    }
+
+    pub fn shrink_to_fit(&mut self) -> PyResult<()> {
+        // Shrink the Series-array to the smallest capacity holding the current entries within the max load.
+        let capacity = self.policy.capacity_for(self.map_size);
+        self.min_capacity = self.min_capacity.min(capacity);
+        if capacity < self.capacity {
+            self.resize(capacity);
+        }
+        Ok(())
+    }
+
+    pub fn compact(&mut self) -> PyResult<usize> {
+        // Backward-shift deletion never leaves Tombstones behind -> Rebuild the layout in place & report 0 purged.
+        self.resize(self.capacity);
+        Ok(0)
+    }
+
     pub fn is_empty(&self) -> PyResult<bool> {
         // Returns 'True' if the internal var: 'map_size' is less than or equal to 0. 
         Ok(self.map_size == 0)
     }
 
     pub fn clear(&mut self) -> PyResult<()> {
-        // Set Slots in internal Rust Vectors to Slot::Empty & reset variable 'map_size' to 0.
+        // Drop back to the initial capacity with every Slot set to Slot::Empty & reset variable 'map_size' to 0.
         self.map_size = 0;
+        self.version += 1;
+        self.capacity = self.min_capacity;
         self.series = vec![Slot::Empty; self.capacity];
         Ok(())
     }
+
+    pub fn setdefault(&mut self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        // Return the stored value, inserting 'default' (or 'None') first if the key is missing.
+        if let Some(value) = self.lookup(py, key.as_ref(py))? {
+            return Ok(value);
+        }
+        let value = default.unwrap_or_else(|| py.None());
+        self.insert(py, key, value.clone_ref(py))?;
+        Ok(value)
+    }
+
+    #[pyo3(signature = (key, *default))]
+    pub fn pop(&mut self, py: Python, key: PyObject, default: &PyTuple) -> PyResult<PyObject> {
+        // Remove & return the value -> Fall back to 'default', or raise KeyError if none was given.
+        let default = pop_default(default)?;
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if let Some((_, value)) = self.take_at(index)? {
+                return Ok(value);
+            }
+        }
+        default.ok_or_else(|| PyKeyError::new_err(key))
+    }
+
+    pub fn popitem(&mut self) -> PyResult<(PyObject, PyObject)> {
+        // Remove the last occupied Slot of the Series-array & return it as a (key, value) pair.
+        if let Some(index) = self.series.iter().rposition(|slot| matches!(slot, Slot::Occupied(_))) {
+            if let Some(pair) = self.take_at(index)? {
+                return Ok(pair);
+            }
+        }
+        Err(PyKeyError::new_err("popitem(): RhoodMap is empty"))
+    }
+
+    pub fn __getitem__(&self, py: Python, key: PyObject) -> PyResult<PyObject> {
+        // map[key] -> Raise KeyError if the key is not found.
+        match self.lookup(py, key.as_ref(py))? {
+            Some(value) => Ok(value),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    pub fn __setitem__(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<()> {
+        // map[key] = value -> Insert a new pair or replace the existing value.
+        self.insert(py, key, value)?;
+        Ok(())
+    }
+
+    pub fn __delitem__(&mut self, py: Python, key: PyObject) -> PyResult<()> {
+        // del map[key] -> Raise KeyError if the key is not found.
+        if let Some(index) = self.find(py, key.as_ref(py))? {
+            if self.take_at(index)?.is_some() {
+                return Ok(());
+            }
+        }
+        Err(PyKeyError::new_err(key))
+    }
+
+    pub fn __contains__(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        self.contains(py, key)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.map_size
+    }
+
+    pub fn __iter__(slf: &PyCell<Self>) -> PyResult<MapIterator> {
+        // Iterate lazily over the stored keys, like iterating over a dict.
+        MapIterator::keys(slf.py(), MapHandle::Rhood(slf.into()))
+    }
+
+    pub fn __richcmp__(&self, py: Python, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        // Equal to any mapping (dict, RhoodMap, ...) holding the same key-value pairs.
+        mapping_compare(py, self, other, op)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Serialise the Series-array slot by slot -> Occupied slots keep their stored hash & probe distance.
+        let mut slots = Vec::with_capacity(self.series.len());
+        for slot in self.series.iter() {
+            match slot {
+                Slot::Occupied(bucket) => {
+                    let entry = (bucket.key.clone_ref(py), bucket.value.clone_ref(py), bucket.hash, bucket.distance);
+                    slots.push(entry.to_object(py));
+                },
+                Slot::Empty => slots.push(py.None()),
+            }
+        }
+        let sizing = (self.policy.max_load, self.policy.min_load, self.min_capacity);
+        let hashing = (self.hasher.scheme(), self.hasher.seed());
+        Ok((STATE_VERSION, self.capacity, self.map_size, slots, sizing, hashing).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        // Validate the state-tuple & extract the internal variables.
+        let state = unpack_state(state, "RhoodMap", 5)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let map_size: usize = state.get_item(2)?.extract()?;
+        let slots: Vec<Option<(PyObject, PyObject, usize, usize)>> = state.get_item(3)?.extract()?;
+        let (max_load, min_load, min_capacity): (f64, f64, usize) = state.get_item(4)?.extract()?;
+        // A SipHash map gets a fresh random key -> Every entry is re-inserted anyway.
+        let (scheme, seed): (String, Option<u64>) = state.get_item(5)?.extract()?;
+
+        // Raise Error if the Series-array does not match the stored capacity.
+        if slots.len() != capacity || map_size > capacity || min_capacity > capacity {
+            return Err(invalid_state("RhoodMap", "inconsistent slot layout"));
+        }
+
+        // Re-insert every entry -> Python's hash() is salted per process, so stored positions can't be reused.
+        let mut restored = RhoodMap::new(Some(capacity), Some(max_load), Some(min_load), Some(&scheme), seed)
+            .map_err(|_| invalid_state("RhoodMap", "invalid load factors or hasher"))?;
+        restored.min_capacity = min_capacity;
+        for (key, value, _, _) in slots.into_iter().flatten() {
+            restored.insert(py, key, value)?;
+        }
+        if restored.map_size != map_size {
+            return Err(invalid_state("RhoodMap", "stored size does not match its entries"));
+        }
+
+        restored.version = self.version + 1;
+        *self = restored;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.capacity, self.policy.max_load, self.policy.min_load, self.hasher.scheme(), self.hasher.seed());
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        // Shallow copy -> Same slot layout, referencing the same keys & values.
+        let mut copied = RhoodMap::new(Some(0), None, None, None, None)?;
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.into_py(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        // Create an empty RhoodMap, then restore a recursively copied state into it.
+        let copied = Py::new(py, RhoodMap::new(Some(0), None, None, None, None)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
 }
@@ -1,32 +1,18 @@
-use pyo3::exceptions::PyValueError;
+// pyo3 0.18 expands the __richcmp__ slot of the #[pymethods] block below into nested impls that newer
+// compilers flag. The generated wrappers sit outside the impl block, so the allow has to cover this module.
+#![allow(non_local_definitions)]
+
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyKeyError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{IntoPyDict, PyDict, PyList, PyTuple};
 use pyo3::PyObject;
-use rustc_hash::{FxHashMap, FxHasher};
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-
-/// ---------------------------------------------------------------------------------
-/// Implementation Hashable Enum & Conversion of Python objects -> Rust data types
-/// ---------------------------------------------------------------------------------
-
-enum Hashable {
-    Int(i64),
-    Float(u64),
-    Str(String),
-    Bool(bool),
-}
-
-impl Hash for Hashable {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        match self {
-            Hashable::Int(i) => i.hash(state),
-            Hashable::Float(f) => f.hash(state),
-            Hashable::Str(s) => s.hash(state),
-            Hashable::Bool(b) => b.hash(state),
-        }
-    }
-}
+use crate::hashing::rs_map_key::{keys_equal, MapHasher};
+use crate::hashing::rs_map_views::{EntryWalk, MapHandle, MapItems, MapIterator, MapKeys, MapValues};
+use crate::hashing::rs_map_protocol::{legacy_update, mapping_compare, pop_default, update_pairs};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_hash::mix;
+use rustc_hash::FxHashMap;
 
 /// ---------------------------------------------------------------------------------
 /// Implementation of Cuckoo Bucket structure/class & related operations
@@ -80,6 +66,22 @@ impl CuckooBucket {
         return elements;
     }
 
+    fn find(&self, py: Python, key: &PyAny, idx_value: u64) -> PyResult<Option<usize>> {
+        // Fast path -> the position indexed under the key's hash, confirmed with dict-style equality.
+        if let Some(&position) = self.index.get(&idx_value) {
+            if keys_equal(self.slots[position].0.as_ref(py), key)? {
+                return Ok(Some(position));
+            }
+        }
+        // Slow path -> two distinct keys shared a hash, so scan the (small) bucket.
+        for (position, (k, _)) in self.slots.iter().enumerate() {
+            if keys_equal(k.as_ref(py), key)? {
+                return Ok(Some(position));
+            }
+        }
+        Ok(None)
+    }
+
     fn shift_indices(&mut self, position: usize) {
         for (_, pos) in self.index.iter_mut() {
             if *pos > position {
@@ -93,11 +95,13 @@ impl CuckooBucket {
 /// Implementation of SnapMap structure/class & related operations
 /// ---------------------------------------------------------------------------------
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct SnapMap {
     capacity: usize,
     map_size: usize,
+    version: u64,
     bucket_size: usize,
+    hasher: MapHasher,
     first_layer: Vec<CuckooBucket>,
     second_layer: Vec<CuckooBucket>,
 }
@@ -111,57 +115,172 @@ impl SnapMap {
         return capacity / size as usize;
     }
 
-    fn python_to_rust(py: Python, item: &PyObject) -> PyResult<Hashable> {
-        // Converts Python native data types -> Rust native data types
-        if let Ok(i) = item.extract::<i64>(py) {
-            return Ok(Hashable::Int(i));
-        } else if let Ok(f) = item.extract::<f64>(py) {
-            return Ok(Hashable::Float(f.to_bits()));
-        } else if let Ok(s) = item.extract::<String>(py)  {
-            return Ok(Hashable::Str(s));
-        } else if let Ok(b) = item.extract::<bool>(py) {
-            return Ok(Hashable::Bool(b));
-        } else {
-            return Err(PyValueError::new_err("Unsupported data type for Rust conversion"));
+    fn locate(&self, key: &PyAny) -> PyResult<(usize, usize, u64)> {
+        // Produce both layer indices & the full hash used for indexing inside a CuckooBucket.
+        // The second index comes from re-mixing the same hash -> One pass over the key serves both layers.
+        let idx_value = self.hasher.hash(key)?;
+        let idx1 = (idx_value as usize) % self.first_layer.len();
+        let idx2 = (mix(idx_value) as usize) % self.second_layer.len();
+        Ok((idx1, idx2, idx_value))
+    }
+
+    fn lookup(&self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>> {
+        // Hash the key & produce both bucket indices plus the in-bucket index value
+        let (idx1, idx2, idx_value) = self.locate(key)?;
+
+        // Attempt to find key-value in first layer, then in second layer
+        for bucket in [&self.first_layer[idx1], &self.second_layer[idx2]] {
+            if let Some(position) = bucket.find(py, key, idx_value)? {
+                return Ok(Some(bucket.slots[position].1.clone_ref(py)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn take(&mut self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>> {
+        // Hash the key & produce both bucket indices plus the in-bucket index value
+        let (idx1, idx2, idx_value) = self.locate(key)?;
+
+        // Check both Buckets in turn - If one holds the key remove it, update internal variables and return it.
+        for bucket in [&mut self.first_layer[idx1], &mut self.second_layer[idx2]] {
+            if let Some(position) = bucket.find(py, key, idx_value)? {
+                let (_, rem_val) = bucket.slots.remove(position);
+
+                bucket.index.retain(|_, pos| *pos != position);
+                bucket.shift_indices(position);
+
+                self.map_size -= 1;
+
+                self.version += 1;
+                return Ok(Some(rem_val));
+            }
         }
+        Ok(None)
     }
 
-    fn generate_first_hash<T: Hash>(&self, key: &T) -> usize {
-        // Generates the intial Hash index for Cuckoo insertion
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        let hash_value = hasher.finish();
-        let map_capacity = self.first_layer.len();
-        return (hash_value as usize) % map_capacity;
+    fn replace(&mut self, py: Python, key: &PyAny, new_value: PyObject) -> PyResult<bool> {
+        // Hash the key & produce both bucket indices plus the in-bucket index value
+        let (idx1, idx2, idx_value) = self.locate(key)?;
+
+        // Check both Buckets in turn & replace the value stored next to a matching key.
+        for bucket in [&mut self.first_layer[idx1], &mut self.second_layer[idx2]] {
+            if let Some(position) = bucket.find(py, key, idx_value)? {
+                bucket.slots[position].1 = new_value;
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 
-    fn generate_second_hash<T: Hash>(&self, key: &T) -> usize {
-        // Generates the secondary Hash index for Cuckoo insertion
-        let mut hasher = FxHasher::default();
-        key.hash(&mut hasher);
-        let hash_value = hasher.finish();
-        let map_capacity = self.second_layer.len();
-        return (hash_value as usize) % map_capacity;
+    fn key_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        // Initiate new Vector list
+        let mut elements = Vec::new();
+
+        // Iterate over Buckets in both internal Layers
+        for layer in [&self.first_layer, &self.second_layer] {
+            for bucket in layer {
+                if !bucket.slots.is_empty() {
+                    // Added cloned keys to Elements Vector
+                    elements.extend(bucket.get_keys(py).iter().cloned());
+                }
+            }
+        }
+        // Convert Rust vector into PyList
+        Ok(PyList::new(py, &elements))
+    }
+
+    fn value_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        // Initiate new Vector list
+        let mut elements = Vec::new();
+
+        // Iterate over Buckets in both internal Layers
+        for layer in [&self.first_layer, &self.second_layer] {
+            for bucket in layer {
+                if !bucket.slots.is_empty() {
+                    // Added cloned values to Elements Vector
+                    elements.extend(bucket.get_values(py).iter().cloned());
+                }
+            }
+        }
+        // Convert Rust vector into PyList
+        Ok(PyList::new(py, &elements))
+    }
+
+    fn item_list<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        // Initiate new Vector list
+        let mut elements = Vec::new();
+
+        // Iterate over Buckets in both internal Layers
+        for layer in [&self.first_layer, &self.second_layer] {
+            for bucket in layer {
+                if !bucket.slots.is_empty() {
+                    // Added cloned values to Elements Vector
+                    elements.extend(bucket.get_items(py).iter().cloned());
+                }
+            }
+        }
+        // Convert Rust vector into PyList
+        Ok(PyList::new(py, &elements))
+    }
+}
+
+impl EntryWalk for SnapMap {
+    fn name(&self) -> &'static str {
+        "SnapMap"
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn entry_count(&self) -> usize {
+        self.map_size
+    }
+
+    fn entry_from(&self, py: Python, cursor: usize) -> Option<(usize, PyObject, PyObject)> {
+        // Cursor = global bucket index (1st layer, then 2nd layer) * bucket_size + slot position.
+        let buckets = self.first_layer.len();
+        let (mut bucket, mut slot) = (cursor / self.bucket_size, cursor % self.bucket_size);
+        while bucket < 2 * buckets {
+            let current = if bucket < buckets { &self.first_layer[bucket] } else { &self.second_layer[bucket - buckets] };
+            if let Some((key, value)) = current.slots.get(slot) {
+                return Some((bucket * self.bucket_size + slot, key.clone_ref(py), value.clone_ref(py)));
+            }
+            bucket += 1;
+            slot = 0;
+        }
+        None
+    }
+
+    fn value_of(&self, py: Python, key: &PyAny) -> PyResult<Option<PyObject>> {
+        self.lookup(py, key)
     }
 }
 
 #[pymethods]
 impl SnapMap {
     #[new]
-    pub fn new(capacity: Option<usize>, bucket_size: Option<usize>) -> Self {
+    pub fn new(capacity: Option<usize>, bucket_size: Option<usize>, hasher: Option<&str>, seed: Option<u64>) -> PyResult<Self> {
         let sm_cap = capacity.unwrap_or(1024);
         let sm_buc = bucket_size.unwrap_or(4);
         let final_size = Self::generate_map_capacity(sm_cap, sm_buc);
-        Self {
+        Ok(Self {
             capacity: sm_cap,
             map_size: 0,
+            version: 0,
             bucket_size: sm_buc,
+            hasher: MapHasher::new(hasher, seed)?,
             first_layer: vec![CuckooBucket::new(sm_buc); final_size],
             second_layer: vec![CuckooBucket::new(sm_buc); final_size],
-        }
+        })
     }
 
     pub fn insert(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<bool> {
+        // If the key is already stored -> Replace its value in place (dict semantics, no duplicate entries).
+        if self.replace(py, key.as_ref(py), value.clone_ref(py))? {
+            return Ok(false);
+        }
+
         let mut key = key;
         let mut value = value;
 
@@ -172,35 +291,20 @@ impl SnapMap {
         // Try inserting key-value pair in Map-structure (100 attempts)
         for _ in 0..Self::MAX_EVICTIONS {
 
-            // Convert key to Rust data type & produce 2 hash-values
-            let rust_hash = SnapMap::python_to_rust(py, &key)?;
-
-            let idx1 = SnapMap::generate_first_hash(&self, &rust_hash);
-            let idx2 = SnapMap::generate_second_hash(&self, &rust_hash);
-
-            // Compute a new hash value for indexing in CuckooBucket
-            let mut h = DefaultHasher::new();
-            rust_hash.hash(&mut h);
-            let idx_value = h.finish();
+            // Hash the key & produce both bucket indices plus the in-bucket index value
+            let (idx1, idx2, idx_value) = self.locate(key.as_ref(py))?;
 
             // Extract mutable references to the 2 Buckets
             let first_bucket = &mut self.first_layer[idx1];
             let second_bucket = &mut self.second_layer[idx2];
 
-            // Duplicate check for both buckets.
-            if first_bucket.slots.iter().any(|(k, _)| k.as_ref(py).eq(key.as_ref(py)).unwrap_or(false)) {
-                return Ok(false);
-            }
-            if second_bucket.slots.iter().any(|(k, _)| k.as_ref(py).eq(key.as_ref(py)).unwrap_or(false)) {
-                return Ok(false);
-            }
-
             // Attempt to insert key-value pair in first layer
             if !first_bucket.is_full() {
                 first_bucket.slots.push((key.clone_ref(py), value.clone_ref(py)));
                 let position = first_bucket.slots.len() - 1;
                 first_bucket.index.insert(idx_value, position);
                 self.map_size += 1;
+                self.version += 1;
                 return Ok(true);
             } 
 
@@ -210,14 +314,17 @@ impl SnapMap {
                 let position = second_bucket.slots.len() - 1;
                 second_bucket.index.insert(idx_value, position);
                 self.map_size += 1;
+                self.version += 1;
                 return Ok(true);
             }
 
             // If both insertions fail - Push out oldest key-value pair and forcibly insert new pair.
             let evicted_pair = first_bucket.slots.pop().expect("Slot should be full!");
+            let position = first_bucket.slots.len();
+            first_bucket.index.retain(|_, pos| *pos != position);
             first_bucket.slots.push((key, value));
-            let position = first_bucket.slots.len() - 1;
             first_bucket.index.insert(idx_value, position);
+            self.version += 1;
 
             // Reassign the eviced key and value to retry
             key = evicted_pair.0;
@@ -229,122 +336,41 @@ impl SnapMap {
     }
 
     pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<PyObject> {
-        // Convert key to Rust data type & produce 2 hash-values
-        let rust_hash = SnapMap::python_to_rust(py, &key)?;
-
-        let idx1 = SnapMap::generate_first_hash(&self, &rust_hash);
-        let idx2 = SnapMap::generate_second_hash(&self, &rust_hash);
-
-        // Compute a new hash value for indexing in CuckooBucket
-        let mut h = DefaultHasher::new();
-        rust_hash.hash(&mut h);
-        let idx_value = h.finish();
-
-        // Extract mutable references to the 2 Buckets
-        let first_bucket = &mut self.first_layer[idx1];
-        let second_bucket = &mut self.second_layer[idx2];
-
-        // Check if the 1st Bucket holds value - If so remove it, update internal variables and return it.
-        let pos_option1 = first_bucket.index.get(&idx_value).copied();
-        if let Some(positon) = pos_option1 {
-            let (_, rem_val) = first_bucket.slots.remove(positon);
-            
-            first_bucket.index.remove(&idx_value);
-            first_bucket.shift_indices(positon);
-
-            self.map_size -= 1;
-            return Ok(rem_val);
-        }
-
-        // Check if the 2nd Bucket holds value - If so remove it, update internal variables and return it.
-        let pos_option2 = second_bucket.index.get(&idx_value).copied();
-        if let Some(positon) = pos_option2 {
-            let (_, rem_val) = second_bucket.slots.remove(positon);
-
-            second_bucket.index.remove(&idx_value);
-            second_bucket.shift_indices(positon);
-
-            self.map_size -= 1;
-            return Ok(rem_val);
-        }
         // DEFAULT = Returns 'None' value if key-value is not found in both layers.
-        Ok(py.None())
+        Ok(self.take(py, key.as_ref(py))?.unwrap_or_else(|| py.None()))
     }
 
-    pub fn get(&self, py: Python, key: PyObject) -> PyResult<PyObject> {
-        // Convert key to Rust data type & produce 2 hash-values
-        let rust_hash = SnapMap::python_to_rust(py, &key)?;
-
-        let idx1 = SnapMap::generate_first_hash(&self, &rust_hash);
-        let idx2 = SnapMap::generate_second_hash(&self, &rust_hash);
-
-        // Attempt to find key-value in first layer
-        for (k, v) in &self.first_layer[idx1].slots {
-            if k.as_ref(py).eq(key.as_ref(py))? {
-                return Ok(v.clone_ref(py));
-            }
-        }
-
-        // Attempt to find key-value in second layer
-        for (k, v) in &self.second_layer[idx2].slots {
-            if k.as_ref(py).eq(key.as_ref(py))? {
-                return Ok(v.clone_ref(py));
-            }
+    pub fn get(&self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        // DEFAULT = Returns 'default' (or 'None') if key-value is not found in both layers.
+        match self.lookup(py, key.as_ref(py))? {
+            Some(value) => Ok(value),
+            None => Ok(default.unwrap_or_else(|| py.None())),
         }
-        // DEFAULT = Returns 'None' value if key-value is not found in both layers.
-        return Ok(py.None());
     }
 
-    pub fn update(&mut self, py: Python, key: PyObject, new_value: PyObject) -> PyResult<bool> {
-        // Convert key to Rust data type & produce 2 hash-values
-        let rust_hash = SnapMap::python_to_rust(py, &key)?;
-
-        let idx1 = SnapMap::generate_first_hash(&self, &rust_hash);
-        let idx2 = SnapMap::generate_second_hash(&self, &rust_hash);
-
-        // Extract mutable references to the 2 Buckets
-        let first_bucket = &mut self.first_layer[idx1];
-        let second_bucket = &mut self.second_layer[idx2];
-
-        // Iterate through the first bucket slots and compare keys.
-        for (k, v) in &mut first_bucket.slots {
-            if k.as_ref(py).eq(key.as_ref(py))? {
-                *v = new_value.clone_ref(py);
-                return Ok(true);
-            }
+    #[pyo3(signature = (*args, **kwargs))]
+    pub fn update(&mut self, py: Python, args: &PyTuple, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        // update(key, new_value) -> Replace a single value, returning 'True' if the key was found.
+        if let Some((key, new_value)) = legacy_update(args, kwargs)? {
+            return Ok(self.replace(py, key.as_ref(py), new_value)?.into_py(py));
         }
 
-        // Iterate through the second bucket slots and compare keys.
-        for (k, v) in &mut second_bucket.slots {
-            if k.as_ref(py).eq(key.as_ref(py))? {
-                *v = new_value.clone_ref(py);
-                return Ok(true);
-            }
+        // update(mapping_or_iterable, **kwargs) -> Assign every pair, exactly like dict.update().
+        for (key, value) in update_pairs(args, kwargs)? {
+            self.insert(py, key, value)?;
         }
-        // DEFAULT = Returns 'False' value if key-value is not found in both layers.
-        Ok(false)
+        Ok(py.None())
     }
 
     pub fn contains(&self, py: Python, key: PyObject) -> PyResult<bool> {
-        // Convert key to Rust data type & produce 2 hash-values
-        let rust_hash = SnapMap::python_to_rust(py, &key)?;
-
-        let idx1 = SnapMap::generate_first_hash(&self, &rust_hash);
-        let idx2 = SnapMap::generate_second_hash(&self, &rust_hash);
-
-        // Compute a new hash value for indexing in CuckooBucket
-        let mut h = DefaultHasher::new();
-        rust_hash.hash(&mut h);
-        let idx_value = h.finish();
-
-        // Check if value exists in first layer
-        if self.first_layer[idx1].index.contains_key(&idx_value) {
-            return Ok(true);
-        }
+        // Hash the key & produce both bucket indices plus the in-bucket index value
+        let (idx1, idx2, idx_value) = self.locate(key.as_ref(py))?;
 
-        // Check if value exists in second layer
-        if self.second_layer[idx2].index.contains_key(&idx_value) {
-            return Ok(true);
+        // Check if the key exists in either layer
+        for bucket in [&self.first_layer[idx1], &self.second_layer[idx2]] {
+            if bucket.find(py, key.as_ref(py), idx_value)?.is_some() {
+                return Ok(true);
+            }
         }
 
         // If key doesn't exist in both layers return false to user.
@@ -360,7 +386,7 @@ impl SnapMap {
             // Extract the key from behind Result-type.
             let key = key_object?;
             // Use internal .get() method to extract final value.
-            let value = self.get(py, key.to_object(py))?;
+            let value = self.get(py, key.to_object(py), None)?;
 
             // Check if the value recieved is 'None' before adding to elements Vec.
             if !value.is_none(py) {
@@ -371,63 +397,28 @@ impl SnapMap {
         Ok(PyList::new(py, &elements))
     }
 
-    pub fn keys<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
-        // Initiate new Vector list
-        let mut elements = Vec::new();
-
-        // Iterate over Buckets in both internal Layers
-        for layer in [&self.first_layer, &self.second_layer] {
-            for bucket in layer {
-                if !bucket.slots.is_empty() {
-                    // Added cloned keys to Elements Vector
-                    elements.extend(bucket.get_keys(py).iter().cloned());
-                }
-            }
-        }
-        // Convert Rust vector into PyList
-        Ok(PyList::new(py, &elements))
+    pub fn keys(slf: &PyCell<Self>) -> MapKeys {
+        // Lazy, set-like view over the stored keys -> Nothing is copied until it is iterated.
+        MapKeys::new(MapHandle::Snap(slf.into()))
     }
 
-    pub fn values<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
-        // Initiate new Vector list
-        let mut elements = Vec::new();
-
-        // Iterate over Buckets in both internal Layers
-        for layer in [&self.first_layer, &self.second_layer] {
-            for bucket in layer {
-                if !bucket.slots.is_empty() {
-                    // Added cloned values to Elements Vector
-                    elements.extend(bucket.get_values(py).iter().cloned());
-                }
-            }
-        }
-        // Convert Rust vector into PyList
-        Ok(PyList::new(py, &elements))
+    pub fn values(slf: &PyCell<Self>) -> MapValues {
+        // Lazy view over the stored values.
+        MapValues::new(MapHandle::Snap(slf.into()))
     }
 
-    pub fn items<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
-        // Initiate new Vector list
-        let mut elements = Vec::new();
-
-        // Iterate over Buckets in both internal Layers
-        for layer in [&self.first_layer, &self.second_layer] {
-            for bucket in layer {
-                if !bucket.slots.is_empty() {
-                    // Added cloned values to Elements Vector
-                    elements.extend(bucket.get_items(py).iter().cloned());
-                }
-            }
-        }
-        // Convert Rust vector into PyList
-        Ok(PyList::new(py, &elements))
+    pub fn items(slf: &PyCell<Self>) -> MapItems {
+        // Lazy, set-like view over the stored (key, value) pairs.
+        MapItems::new(MapHandle::Snap(slf.into()))
     }
 
     pub fn copy(&self, py: Python<'_>) -> PyResult<PyObject> {
         // Instantiate an empty SnapMap variable.
-        let mut new_map = SnapMap::new(Some(self.capacity), Some(self.bucket_size));
+        let mut new_map = SnapMap::new(Some(self.capacity), Some(self.bucket_size), None, None)?;
+        new_map.hasher = self.hasher.clone();
 
         // Iterate through all stored items.
-        for tuple in self.items(py)?.iter() {
+        for tuple in self.item_list(py)?.iter() {
             // Downcast entry to PyTuple to extract key & value pairs safely.
             let tup = tuple.downcast::<PyTuple>()?;
             let key = tup.get_item(0)?;
@@ -443,8 +434,8 @@ impl SnapMap {
     pub fn info<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
         // Extract the necessary metrics from internal variables
         let percentage = self.percentage()?;
-        let keys = self.keys(py)?.into();
-        let values = self.values(py)?.into();
+        let keys = self.key_list(py)?.into();
+        let values = self.value_list(py)?.into();
 
         // Contruct a Rust Vector consisting of individual Tuples(String, Object).
         let key_vals: Vec<(&str, PyObject)> = vec![
@@ -452,6 +443,8 @@ impl SnapMap {
             ("capacity", self.capacity.to_object(py)),
             ("size", self.map_size.to_object(py)),
             ("percentage", percentage.to_object(py)),
+            ("hash_scheme", self.hasher.scheme().to_object(py)),
+            ("seed", self.hasher.seed().to_object(py)),
             ("keys", keys),
             ("values", values),
         ];
@@ -495,6 +488,145 @@ impl SnapMap {
         }
 
         self.map_size = 0;
+
+        self.version += 1;
+        Ok(())
+    }
+
+    pub fn setdefault(&mut self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        // Return the stored value, inserting 'default' (or 'None') first if the key is missing.
+        if let Some(value) = self.lookup(py, key.as_ref(py))? {
+            return Ok(value);
+        }
+        let value = default.unwrap_or_else(|| py.None());
+        self.insert(py, key, value.clone_ref(py))?;
+        Ok(value)
+    }
+
+    #[pyo3(signature = (key, *default))]
+    pub fn pop(&mut self, py: Python, key: PyObject, default: &PyTuple) -> PyResult<PyObject> {
+        // Remove & return the value -> Fall back to 'default', or raise KeyError if none was given.
+        let default = pop_default(default)?;
+        match self.take(py, key.as_ref(py))? {
+            Some(value) => Ok(value),
+            None => default.ok_or_else(|| PyKeyError::new_err(key)),
+        }
+    }
+
+    pub fn popitem(&mut self) -> PyResult<(PyObject, PyObject)> {
+        // Remove the last slot of the last occupied Bucket (2nd layer first) & return it as a (key, value) pair.
+        for layer in [&mut self.second_layer, &mut self.first_layer] {
+            if let Some(bucket) = layer.iter_mut().rev().find(|bucket| !bucket.slots.is_empty()) {
+                let (key, value) = bucket.slots.pop().expect("Bucket should hold a slot!");
+                let position = bucket.slots.len();
+                bucket.index.retain(|_, pos| *pos != position);
+                self.map_size -= 1;
+                self.version += 1;
+                return Ok((key, value));
+            }
+        }
+        Err(PyKeyError::new_err("popitem(): SnapMap is empty"))
+    }
+
+    pub fn __getitem__(&self, py: Python, key: PyObject) -> PyResult<PyObject> {
+        // map[key] -> Raise KeyError if key-value is not found in both layers.
+        match self.lookup(py, key.as_ref(py))? {
+            Some(value) => Ok(value),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    pub fn __setitem__(&mut self, py: Python, key: PyObject, value: PyObject) -> PyResult<()> {
+        // map[key] = value -> Insert a new pair or replace the existing value.
+        self.insert(py, key, value)?;
         Ok(())
     }
+
+    pub fn __delitem__(&mut self, py: Python, key: PyObject) -> PyResult<()> {
+        // del map[key] -> Raise KeyError if key-value is not found in both layers.
+        match self.take(py, key.as_ref(py))? {
+            Some(_) => Ok(()),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    pub fn __contains__(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        self.contains(py, key)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.map_size
+    }
+
+    pub fn __iter__(slf: &PyCell<Self>) -> PyResult<MapIterator> {
+        // Iterate lazily over the stored keys, like iterating over a dict.
+        MapIterator::keys(slf.py(), MapHandle::Snap(slf.into()))
+    }
+
+    pub fn __richcmp__(&self, py: Python, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        // Equal to any mapping (dict, SnapMap, ...) holding the same key-value pairs.
+        mapping_compare(py, self, other, op)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Serialise both layers bucket by bucket, keeping the slot order inside each CuckooBucket.
+        let first_layer: Vec<Vec<(PyObject, PyObject)>> = self.first_layer.iter().map(|bucket| bucket.get_items(py)).collect();
+        let second_layer: Vec<Vec<(PyObject, PyObject)>> = self.second_layer.iter().map(|bucket| bucket.get_items(py)).collect();
+        let hashing = (self.hasher.scheme(), self.hasher.seed());
+        Ok((STATE_VERSION, self.capacity, self.bucket_size, self.map_size, first_layer, second_layer, hashing).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        // Validate the state-tuple & extract the internal variables.
+        let state = unpack_state(state, "SnapMap", 6)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let bucket_size: usize = state.get_item(2)?.extract()?;
+        let map_size: usize = state.get_item(3)?.extract()?;
+        let first_items: Vec<Vec<(PyObject, PyObject)>> = state.get_item(4)?.extract()?;
+        let second_items: Vec<Vec<(PyObject, PyObject)>> = state.get_item(5)?.extract()?;
+        // A SipHash map gets a fresh random key -> Every entry is re-inserted anyway.
+        let (scheme, seed): (String, Option<u64>) = state.get_item(6)?.extract()?;
+
+        // Validate the stored bucket layout before trusting any of its entries.
+        if bucket_size == 0 || first_items.len() != second_items.len() || (map_size > 0 && first_items.is_empty()) {
+            return Err(invalid_state("SnapMap", "inconsistent bucket layout"));
+        }
+        if first_items.iter().chain(second_items.iter()).any(|slots| slots.len() > bucket_size) {
+            return Err(invalid_state("SnapMap", "bucket holds more slots than bucket_size"));
+        }
+
+        // Re-insert every entry -> Python's hash() is salted per process, so stored positions can't be reused.
+        let mut restored = SnapMap::new(Some(capacity), Some(bucket_size), Some(&scheme), seed)
+            .map_err(|_| invalid_state("SnapMap", "invalid hasher"))?;
+        for (key, value) in first_items.into_iter().chain(second_items).flatten() {
+            restored.insert(py, key, value)?;
+        }
+        if restored.map_size != map_size {
+            return Err(invalid_state("SnapMap", "stored size does not match its entries"));
+        }
+
+        restored.version = self.version + 1;
+        *self = restored;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.capacity, self.bucket_size, self.hasher.scheme(), self.hasher.seed());
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        // Shallow copy -> Same bucket layout, referencing the same keys & values.
+        let mut copied = SnapMap::new(Some(0), Some(1), None, None)?;
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.into_py(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        // Create an empty SnapMap, then restore a recursively copied state into it.
+        let copied = Py::new(py, SnapMap::new(Some(0), Some(1), None, None)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
 }
@@ -35,15 +35,25 @@ use graph::rs_hypergraph_native::HyperGraph;
 use hashing::rs_snapmap_native::SnapMap;
 use hashing::rs_rhoodmap_native::RhoodMap;
 use hashing::rs_quadmap_native::QuadMap;
+use hashing::rs_map_views::{MapItems, MapIterator, MapKeys, MapValues};
 
 // imports from rust folders (Probability)
+use probability::rs_binary_fuse_filter_native::BinaryFuseFilter;
 use probability::rs_bloom_filter_native::BloomFilter;
+use probability::rs_counting_bloom_filter_native::CountingBloomFilter;
+use probability::rs_count_min_sketch_native::CountMinSketch;
 use probability::rs_cuckoo_filter_native::CuckooFilter;
+use probability::rs_scalable_bloom_filter_native::ScalableBloomFilter;
 use probability::rs_flatlist_native::Flatlist;
+use probability::rs_hyperloglog_native::HyperLogLog;
+use probability::rs_tdigest_native::TDigest;
+use probability::rs_top_k_native::TopK;
+use probability::rs_lsh_index_native::LSHIndex;
+use probability::rs_minhash_native::MinHash;
 
 // Final export to Python
 #[pymodule]
-pub fn _rust_snaplit(_py: Python, m: &PyModule) -> PyResult<()> {
+pub fn _rust_snaplit(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<LinkedList>()?;
     m.add_class::<Stack>()?;
     m.add_class::<ArrayStack>()?;
@@ -59,9 +69,24 @@ pub fn _rust_snaplit(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<SnapMap>()?;
     m.add_class::<RhoodMap>()?;
     m.add_class::<QuadMap>()?;
+    m.add_class::<MapKeys>()?;
+    m.add_class::<MapValues>()?;
+    m.add_class::<MapItems>()?;
+    m.add_class::<MapIterator>()?;
+    hashing::rs_map_views::register_views(py, m)?;
+    hashing::rs_map_protocol::register_mappings(py, m, &["SnapMap", "RhoodMap", "QuadMap"])?;
     m.add_class::<BloomFilter>()?;
+    m.add_class::<CountingBloomFilter>()?;
+    m.add_class::<ScalableBloomFilter>()?;
     m.add_class::<CuckooFilter>()?;
     m.add_class::<Flatlist>()?;
+    m.add_class::<HyperLogLog>()?;
+    m.add_class::<BinaryFuseFilter>()?;
+    m.add_class::<MinHash>()?;
+    m.add_class::<LSHIndex>()?;
+    m.add_class::<TDigest>()?;
+    m.add_class::<TopK>()?;
+    m.add_class::<CountMinSketch>()?;
     m.add_class::<BaseGraph>()?;
     m.add_class::<Digraph>()?;
     m.add_class::<WeightedGraph>()?;
@@ -1,7 +1,8 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use pyo3::PyObject;
-use pyo3::types::PyList;
+use pyo3::types::{PyList, PyTuple};
+use crate::other::rs_pickle::{deep_copy_state, unpack_state, STATE_VERSION};
 
 /// ---------------------------------------------------------------------------------
 /// Internal WagonNode structures & public LinkedList
@@ -12,7 +13,7 @@ struct WagonNode {
     next: Option<Box<WagonNode>>
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct LinkedList {
     head: Option<Box<WagonNode>>,
     count: usize
@@ -265,5 +266,47 @@ impl LinkedList {
         self.head = None;
         self.count = 0;
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Serialise the stored values in order from 'head' to the final WagonNode.
+        let values = self.to_list(py)?;
+        Ok((STATE_VERSION, values).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        // Validate the state-tuple & extract the stored values.
+        let state = unpack_state(state, "LinkedList", 1)?;
+        let values: Vec<PyObject> = state.get_item(1)?.extract()?;
+
+        // Rebuild the WagonNode chain back-to-front so the original order is kept.
+        self.head = None;
+        self.count = values.len();
+        for value in values.into_iter().rev() {
+            self.head = Some(Box::new(WagonNode {
+                data: value,
+                next: self.head.take()
+            }));
+        }
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        // Shallow copy -> Same internal layout, referencing the same Python objects.
+        let mut copied = LinkedList::new();
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        // Deep copy -> Values are copied recursively through Python's copy-module.
+        let copied = Py::new(py, LinkedList::new())?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
 
@@ -2,6 +2,7 @@ use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, unpack_state, STATE_VERSION};
 
 
 #[derive(Clone, Copy)]
@@ -24,7 +25,7 @@ impl HeapItem {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct PriorityQueue {
     priority: HeapType,
     array: Vec<HeapItem>,
@@ -226,6 +227,35 @@ impl PriorityQueue {
         self.copy(py)
     }
 
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let kind = if self.is_min_heap() { "min" } else { "max" };
+        let heap = self.to_list(py)?;
+        Ok((STATE_VERSION, kind, heap).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "PriorityQueue", 2)?;
+        let kind: &str = state.get_item(1)?.extract()?;
+        let heap: Vec<(PyObject, i32)> = state.get_item(2)?.extract()?;
+
+        // The stored array is already in heap order, so it is restored as-is without re-heapifying.
+        self.priority = Self::new(kind)?.priority;
+        self.array = heap.into_iter().map(|(value, priority)| HeapItem::new(priority, value)).collect();
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let kind = if self.is_min_heap() { "min" } else { "max" };
+        Ok((py.get_type::<Self>(), (kind,), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, PriorityQueue::new("min")?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+
     fn heapify_up(&mut self, mut index: usize) {
         while index > 0 {
             let parent_idx = (index - 1) / 2;
@@ -1,10 +1,11 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::PyIndexError;
 use pyo3::PyObject;
-use pyo3::types::PyList;
+use pyo3::types::{PyList, PyTuple};
+use crate::other::rs_pickle::{deep_copy_state, unpack_state, STATE_VERSION};
 use std::collections::VecDeque;
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct Queue {
     array: VecDeque<PyObject>,
 }
@@ -96,4 +97,28 @@ impl Queue {
     pub fn __contains__(&self, py: Python, value: PyObject) -> bool {
         self.contains(py, value)
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let values = self.to_list(py)?;
+        Ok((STATE_VERSION, values).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "Queue", 1)?;
+        let values: Vec<PyObject> = state.get_item(1)?.extract()?;
+
+        self.array = values.into_iter().collect();
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, Queue::new())?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
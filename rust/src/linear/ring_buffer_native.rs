@@ -2,8 +2,9 @@ use pyo3::exceptions::PyIndexError;
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct RingBuffer {
     head: usize,
     tail: usize,
@@ -176,4 +177,40 @@ impl RingBuffer {
     pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
         self.copy(py)
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let slots = PyList::new(py, self.array.iter().map(|item| item.clone_ref(py)));
+        Ok((STATE_VERSION, self.total, self.head, self.tail, self.count, slots).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "RingBuffer", 5)?;
+        let total: usize = state.get_item(1)?.extract()?;
+        let head: usize = state.get_item(2)?.extract()?;
+        let tail: usize = state.get_item(3)?.extract()?;
+        let count: usize = state.get_item(4)?.extract()?;
+        let array: Vec<PyObject> = state.get_item(5)?.extract()?;
+
+        if array.len() != total || count > total || (total > 0 && (head >= total || tail >= total)) {
+            return Err(invalid_state("RingBuffer", "inconsistent buffer layout"));
+        }
+
+        self.total = total;
+        self.head = head;
+        self.tail = tail;
+        self.count = count;
+        self.array = array;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.total,), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, RingBuffer::new(py, 0))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
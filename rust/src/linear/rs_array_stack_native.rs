@@ -2,8 +2,9 @@ use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use pyo3::PyObject;
 use pyo3::types::PyList;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct ArrayStack {
     capacity: usize,
     stack: Vec<PyObject>,
@@ -162,4 +163,40 @@ impl ArrayStack {
     pub fn clear(&mut self) {
         self.stack.clear();
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let values = self.to_list(py)?;
+        Ok((STATE_VERSION, self.capacity, values).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "ArrayStack", 2)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let values: Vec<PyObject> = state.get_item(2)?.extract()?;
+
+        if capacity != 0 && values.len() > capacity {
+            return Err(invalid_state("ArrayStack", "more values than capacity"));
+        }
+
+        self.capacity = capacity;
+        self.stack = values;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.capacity,), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = ArrayStack::new(None);
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, ArrayStack::new(None))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
@@ -4,6 +4,7 @@ use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use pyo3::PyObject;
 use pyo3::types::{IntoPyDict, PyDict, PyList};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
 /// ---------------------------------------------------------------------------------
 /// Implementation of ChainLink helper class & Slot Enum
@@ -38,7 +39,7 @@ impl ChainLink {
 /// Implementation of main ChainList-class -> Array-based Linked List 
 /// ---------------------------------------------------------------------------------
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct ChainList {
     capacity: usize,
     list_size: usize,
@@ -560,6 +561,75 @@ impl ChainList {
         self.free_list = VecDeque::new();
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Serialise every Slot in array order -> None for Slot::Empty, (data, next, previous) for Slot::Occupied.
+        let mut slots = Vec::with_capacity(self.list_array.len());
+        for slot in self.list_array.iter() {
+            match slot {
+                Slot::Occupied(link) => slots.push((link.data.clone_ref(py), link.next, link.previous).to_object(py)),
+                Slot::Empty => slots.push(py.None()),
+            }
+        }
+
+        // Keep the free_list order so re-used indices match the original ChainList.
+        let free: Vec<usize> = self.free_list.iter().copied().collect();
+        Ok((STATE_VERSION, self.capacity, self.list_size, self.head, self.tail, self.next_index, slots, free).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        // Validate the state-tuple & extract the internal variables.
+        let state = unpack_state(state, "ChainList", 7)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let list_size: usize = state.get_item(2)?.extract()?;
+        let head: usize = state.get_item(3)?.extract()?;
+        let tail: usize = state.get_item(4)?.extract()?;
+        let next_index: usize = state.get_item(5)?.extract()?;
+        let slots: Vec<Option<(PyObject, usize, usize)>> = state.get_item(6)?.extract()?;
+        let free: Vec<usize> = state.get_item(7)?.extract()?;
+
+        // Raise Error if the Slot array does not match the stored capacity.
+        if slots.len() != capacity || list_size > capacity || next_index > capacity {
+            return Err(invalid_state("ChainList", "inconsistent slot layout"));
+        }
+
+        // Rebuild each Slot at its original index.
+        self.list_array = slots
+            .into_iter()
+            .enumerate()
+            .map(|(index, slot)| match slot {
+                Some((data, next, previous)) => Slot::Occupied(ChainLink::new(data, next, previous, index)),
+                None => Slot::Empty,
+            })
+            .collect();
+
+        self.capacity = capacity;
+        self.list_size = list_size;
+        self.head = head;
+        self.tail = tail;
+        self.next_index = next_index;
+        self.free_list = free.into_iter().collect();
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.capacity,), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        // Shallow copy -> Same internal layout, referencing the same Python objects.
+        let mut copied = ChainList::new(Some(0));
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        // Create an empty ChainList, then restore a recursively copied state into it.
+        let copied = Py::new(py, ChainList::new(Some(0)))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
 
 
@@ -2,13 +2,14 @@ use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use pyo3::PyObject;
 use pyo3::types::{IntoPyDict, PyDict, PyList};
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
 /// ---------------------------------------------------------------------------------
 /// Implementation of TinySet main class & general methods/operations
 /// ---------------------------------------------------------------------------------
 
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct TinySet {
     capacity: usize,
     size: usize,
@@ -302,4 +303,51 @@ impl TinySet {
         self.array = vec![py.None(); self.capacity];
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Serialise the sorted values that are currently stored.
+        let values = PyList::new(py, self.array[..self.size].iter().map(|item| item.clone_ref(py)));
+        Ok((STATE_VERSION, self.capacity, self.threshold, values).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        // Validate the state-tuple & extract the internal variables.
+        let state = unpack_state(state, "TinySet", 3)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let threshold: f64 = state.get_item(2)?.extract()?;
+        let values: Vec<PyObject> = state.get_item(3)?.extract()?;
+
+        // Raise Error if the values cannot fit into the stored capacity.
+        if values.len() > capacity {
+            return Err(invalid_state("TinySet", "more values than capacity"));
+        }
+
+        // Restore values in their stored (sorted) order & pad remaining array with None.
+        self.capacity = capacity;
+        self.threshold = threshold;
+        self.size = values.len();
+        self.none = py.None();
+        self.array = values;
+        self.array.resize(capacity, py.None());
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.capacity, self.threshold), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        // Shallow copy -> Same internal layout, referencing the same Python objects.
+        let mut copied = TinySet::new(py, Some(0), None);
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        // Create an empty TinySet, then restore a recursively copied state into it.
+        let copied = Py::new(py, TinySet::new(py, Some(0), None))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
@@ -1 +1,2 @@
-pub mod rs_bit_array;
\ No newline at end of file
+pub mod rs_bit_array;
+pub mod rs_pickle;
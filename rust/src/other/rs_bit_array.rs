@@ -15,10 +15,38 @@ impl BitArray {
         }
     }
 
+    pub fn from_bytes(len: usize, bytes: &[u8]) -> Option<Self> {
+        let mut array = Self::new(len);
+        if bytes.len() != array.data.len() * 8 {
+            return None;
+        }
+
+        for (word, chunk) in array.data.iter_mut().zip(bytes.chunks_exact(8)) {
+            let mut buffer = [0u8; 8];
+            buffer.copy_from_slice(chunk);
+            *word = u64::from_le_bytes(buffer);
+        }
+
+        // Bits past 'len' must stay clear so equality & popcounts are not skewed.
+        let spare = array.data.len() * 64 - len;
+        if spare > 0 {
+            if let Some(last) = array.data.last() {
+                if last >> (64 - spare) != 0 {
+                    return None;
+                }
+            }
+        }
+        Some(array)
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
 
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
     pub fn set(&mut self, index: usize) {
         assert!(index < self.len, "index out of bounds");
         let word = index / 64;
@@ -58,4 +86,22 @@ impl BitArray {
             *word = !0;
         }
     }
+
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn union_with(&mut self, other: &BitArray) {
+        assert!(self.len == other.len, "bit arrays must have equal length");
+        for (word, other_word) in self.data.iter_mut().zip(other.data.iter()) {
+            *word |= *other_word;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitArray) {
+        assert!(self.len == other.len, "bit arrays must have equal length");
+        for (word, other_word) in self.data.iter_mut().zip(other.data.iter()) {
+            *word &= *other_word;
+        }
+    }
 }
\ No newline at end of file
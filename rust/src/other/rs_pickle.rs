@@ -0,0 +1,48 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use pyo3::{AsPyPointer, PyObject};
+
+// ---------------------------------------------------------------------------------
+// Shared helpers for the pickle & copy protocol of every Snaplit structure.
+//
+// Each structure serialises itself into a tuple `(STATE_VERSION, field, ...)`.
+// The leading version number is checked on restore, so the layout of a state
+// tuple may only change together with a bump of STATE_VERSION.
+// ---------------------------------------------------------------------------------
+
+pub const STATE_VERSION: u32 = 1;
+
+pub fn unpack_state<'py>(state: &'py PyAny, name: &str, fields: usize) -> PyResult<&'py PyTuple> {
+    let tuple = state
+        .downcast::<PyTuple>()
+        .map_err(|_| PyValueError::new_err(format!("Invalid {} state: expected a tuple", name)))?;
+
+    let version: u32 = match tuple.get_item(0) {
+        Ok(item) => item.extract()?,
+        Err(_) => return Err(PyValueError::new_err(format!("Invalid {} state: missing version", name))),
+    };
+    if version != STATE_VERSION {
+        return Err(PyValueError::new_err(format!(
+            "Unsupported {} state version {} (expected {})", name, version, STATE_VERSION
+        )));
+    }
+
+    if tuple.len() != fields + 1 {
+        return Err(PyValueError::new_err(format!(
+            "Invalid {} state: expected {} fields, found {}", name, fields, tuple.len() - 1
+        )));
+    }
+    Ok(tuple)
+}
+
+pub fn deep_copy_state(py: Python, source: &PyAny, target: PyObject, state: PyObject, memo: &PyAny) -> PyResult<PyObject> {
+    // Register the new instance before descending, so payloads referring back to the source resolve to the copy.
+    memo.set_item(source.as_ptr() as usize, target)?;
+    let copy = py.import("copy")?;
+    Ok(copy.call_method1("deepcopy", (state, memo))?.to_object(py))
+}
+
+pub fn invalid_state(name: &str, reason: &str) -> PyErr {
+    PyValueError::new_err(format!("Invalid {} state: {}", name, reason))
+}
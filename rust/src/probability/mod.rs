@@ -1,3 +1,14 @@
+pub mod rs_binary_fuse_filter_native;
 pub mod rs_bloom_filter_native;
+pub mod rs_count_min_sketch_native;
+pub mod rs_counting_bloom_filter_native;
 pub mod rs_cuckoo_filter_native;
-pub mod rs_flatlist_native;
\ No newline at end of file
+pub mod rs_flatlist_native;
+pub mod rs_hyperloglog_native;
+pub mod rs_lsh_index_native;
+pub mod rs_minhash_native;
+pub mod rs_scalable_bloom_filter_native;
+pub mod rs_tdigest_native;
+pub mod rs_top_k_native;
+pub mod rs_filter_format;
+pub mod rs_filter_hash;
@@ -0,0 +1,408 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyList};
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_format::{read_file, write_file, ByteReader, ByteWriter};
+use crate::probability::rs_filter_hash::{mix, FilterHasher};
+
+const MAX_ITERATIONS: usize = 100;
+
+// ---------------------------------------------------------------------------------
+// 3-wise binary fuse filter (Graf & Lemire, 2022).
+//
+// Every key maps to three slots in consecutive segments, and the XOR of those
+// three fingerprints equals the key's fingerprint. Construction peels keys off
+// slots that only one key touches; the reverse peeling order then assigns every
+// slot. The result is immutable and uses ~1.13 fingerprints per key.
+// ---------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+enum Fingerprints {
+    Eight(Vec<u8>),
+    Sixteen(Vec<u16>),
+}
+
+impl Fingerprints {
+    fn get(&self, index: usize) -> u16 {
+        match self {
+            Fingerprints::Eight(values) => values[index] as u16,
+            Fingerprints::Sixteen(values) => values[index],
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Fingerprints::Eight(values) => values.len(),
+            Fingerprints::Sixteen(values) => values.len(),
+        }
+    }
+
+    fn bits(&self) -> u8 {
+        match self {
+            Fingerprints::Eight(_) => 8,
+            Fingerprints::Sixteen(_) => 16,
+        }
+    }
+
+    fn to_vec(&self) -> Vec<u16> {
+        (0..self.len()).map(|index| self.get(index)).collect()
+    }
+
+    fn from_vec(values: Vec<u16>, bits: u8) -> Self {
+        if bits == 8 {
+            Fingerprints::Eight(values.into_iter().map(|value| value as u8).collect())
+        } else {
+            Fingerprints::Sixteen(values)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FuseLayout {
+    segment_length: usize,
+    segment_count_length: usize,
+    array_length: usize,
+}
+
+impl FuseLayout {
+    fn new(size: usize) -> Self {
+        let mut segment_length = if size == 0 {
+            4
+        } else {
+            1usize << ((size as f64).ln() / 3.33f64.ln() + 2.25).floor() as u32
+        };
+        segment_length = segment_length.min(262144);
+
+        let size_factor = if size <= 1 { 0.0 } else { (0.875 + 0.25 * 1_000_000f64.ln() / (size as f64).ln()).max(1.125) };
+        let capacity = (size as f64 * size_factor).round() as usize;
+        let initial_segments = capacity.div_ceil(segment_length).saturating_sub(2);
+        let array_length = (initial_segments + 2) * segment_length;
+        let mut segment_count = array_length / segment_length;
+        segment_count = if segment_count <= 2 { 1 } else { segment_count - 2 };
+
+        Self {
+            segment_length,
+            segment_count_length: segment_count * segment_length,
+            array_length: (segment_count + 2) * segment_length,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.segment_length.is_power_of_two()
+            && self.segment_count_length > 0
+            && self.segment_count_length % self.segment_length == 0
+            && self.array_length == self.segment_count_length + 2 * self.segment_length
+    }
+
+    fn slot(&self, index: usize, hash: u64) -> usize {
+        // The high product picks a start inside the first segment_count segments; slot i moves i segments on.
+        let start = ((hash as u128 * self.segment_count_length as u128) >> 64) as usize;
+        let offset = ((hash & ((1 << 36) - 1)) >> (36 - 18 * index)) as usize & (self.segment_length - 1);
+        (start + index * self.segment_length) ^ offset
+    }
+
+    fn slots(&self, hash: u64) -> [usize; 3] {
+        [self.slot(0, hash), self.slot(1, hash), self.slot(2, hash)]
+    }
+}
+
+fn fingerprint(hash: u64, bits: u8) -> u16 {
+    let folded = hash ^ (hash >> 32);
+    if bits == 8 { (folded & 0xFF) as u16 } else { (folded & 0xFFFF) as u16 }
+}
+
+fn construct(keys: &[u64], layout: FuseLayout, bits: u8) -> Option<(u64, Vec<u16>)> {
+    let capacity = layout.array_length;
+    let mut rng_counter: u64 = 0x726b2b9d438b9d4d;
+
+    for _ in 0..MAX_ITERATIONS {
+        rng_counter = rng_counter.wrapping_add(0x9e3779b97f4a7c15);
+        let seed = mix(rng_counter);
+
+        // t2count holds (keys touching the slot << 2) | XOR of the slot positions (0, 1, 2) within each key.
+        let mut t2count = vec![0u8; capacity];
+        let mut t2hash = vec![0u64; capacity];
+        let mut overflow = false;
+        for &key in keys {
+            let hash = mix(key.wrapping_add(seed));
+            for (position, slot) in layout.slots(hash).into_iter().enumerate() {
+                t2count[slot] = t2count[slot].wrapping_add(4) ^ position as u8;
+                t2hash[slot] ^= hash;
+                overflow |= t2count[slot] < 4;
+            }
+        }
+        if overflow {
+            continue;
+        }
+
+        let mut alone: Vec<usize> = (0..capacity).filter(|&slot| t2count[slot] >> 2 == 1).collect();
+        let mut order: Vec<(u64, u8)> = Vec::with_capacity(keys.len());
+        while let Some(slot) = alone.pop() {
+            if t2count[slot] >> 2 != 1 {
+                continue;
+            }
+            let hash = t2hash[slot];
+            let found = t2count[slot] & 3;
+            order.push((hash, found));
+
+            let slots = layout.slots(hash);
+            for step in 1..3 {
+                let position = (found as usize + step) % 3;
+                let other = slots[position];
+                t2count[other] = t2count[other].wrapping_sub(4) ^ position as u8;
+                t2hash[other] ^= hash;
+                if t2count[other] >> 2 == 1 {
+                    alone.push(other);
+                }
+            }
+            t2count[slot] = 0;
+        }
+        if order.len() != keys.len() {
+            continue;
+        }
+
+        // Assign slots in reverse peeling order -> each key's free slot is the last of its three to be written.
+        let mut values = vec![0u16; capacity];
+        for &(hash, found) in order.iter().rev() {
+            let slots = layout.slots(hash);
+            let found = found as usize;
+            values[slots[found]] = fingerprint(hash, bits) ^ values[slots[(found + 1) % 3]] ^ values[slots[(found + 2) % 3]];
+        }
+        return Some((seed, values));
+    }
+    None
+}
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct BinaryFuseFilter {
+    size: usize,
+    seed: u64,
+    hash_seed: u64,
+    layout: FuseLayout,
+    fingerprints: Fingerprints,
+}
+
+impl BinaryFuseFilter {
+    const MAGIC: &'static [u8; 4] = b"SNXF";
+    const FORMAT_VERSION: u16 = 1;
+
+    fn check_fingerprint_bits(bits: u8) -> PyResult<()> {
+        if bits != 8 && bits != 16 {
+            return Err(PyValueError::new_err(format!("Fingerprint bits must be 8 or 16, not {}", bits)));
+        }
+        Ok(())
+    }
+
+    fn build(keys: &mut Vec<u64>, bits: u8, seed: u64) -> PyResult<Self> {
+        keys.sort_unstable();
+        keys.dedup();
+
+        let layout = FuseLayout::new(keys.len());
+        match construct(keys, layout, bits) {
+            Some((hash_seed, values)) => Ok(Self {
+                size: keys.len(),
+                seed,
+                hash_seed,
+                layout,
+                fingerprints: Fingerprints::from_vec(values, bits),
+            }),
+            None => Err(PyValueError::new_err("Unable to build BinaryFuseFilter from the given keys")),
+        }
+    }
+
+    fn restore(size: usize, seed: u64, hash_seed: u64, layout: FuseLayout, fingerprints: Fingerprints) -> Option<Self> {
+        if !layout.is_valid() || fingerprints.len() != layout.array_length {
+            return None;
+        }
+        Some(Self { size, seed, hash_seed, layout, fingerprints })
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        // Header -> fingerprint width, hash scheme, key count, construction seed & segment layout, followed by every slot.
+        let mut writer = ByteWriter::new(Self::MAGIC, Self::FORMAT_VERSION);
+        writer.put_u8(self.fingerprints.bits());
+        writer.put_hasher(FilterHasher::Xxh3(self.seed));
+        writer.put_usize(self.size);
+        writer.put_u64(self.hash_seed);
+        writer.put_usize(self.layout.segment_length);
+        writer.put_usize(self.layout.segment_count_length);
+        writer.put_usize(self.layout.array_length);
+        match &self.fingerprints {
+            Fingerprints::Eight(values) => writer.put_bytes(values),
+            Fingerprints::Sixteen(values) => values.iter().for_each(|&value| writer.put_u16(value)),
+        }
+        writer.finish()
+    }
+
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        let mut reader = ByteReader::new(data, Self::MAGIC, Self::FORMAT_VERSION, "BinaryFuseFilter")?;
+        let bits = reader.get_u8()?;
+        let seed = match reader.get_hasher()? {
+            FilterHasher::Xxh3(seed) => seed,
+            FilterHasher::Python => return Err(reader.invalid("filters must use the seeded hash scheme")),
+        };
+        let size = reader.get_usize()?;
+        let hash_seed = reader.get_u64()?;
+        let layout = FuseLayout {
+            segment_length: reader.get_usize()?,
+            segment_count_length: reader.get_usize()?,
+            array_length: reader.get_usize()?,
+        };
+
+        if bits != 8 && bits != 16 {
+            return Err(reader.invalid("unsupported fingerprint width"));
+        }
+        if !layout.is_valid() || layout.array_length > data.len() {
+            return Err(reader.invalid("inconsistent segment layout"));
+        }
+
+        let fingerprints = if bits == 8 {
+            Fingerprints::Eight(reader.take(layout.array_length)?.to_vec())
+        } else {
+            let mut values = Vec::with_capacity(layout.array_length);
+            for _ in 0..layout.array_length {
+                values.push(reader.get_u16()?);
+            }
+            Fingerprints::Sixteen(values)
+        };
+        reader.finish()?;
+
+        Ok(Self { size, seed, hash_seed, layout, fingerprints })
+    }
+}
+
+#[pymethods]
+impl BinaryFuseFilter {
+    #[new]
+    pub fn new(items: &PyAny, fingerprint_bits: Option<u8>, seed: Option<u64>) -> PyResult<Self> {
+        let bits = fingerprint_bits.unwrap_or(8);
+        Self::check_fingerprint_bits(bits)?;
+
+        let seed = seed.unwrap_or(0);
+        let hasher = FilterHasher::Xxh3(seed);
+        let mut keys = Vec::new();
+        for item in items.iter()? {
+            keys.push(hasher.hash(item?)? as u64);
+        }
+        Self::build(&mut keys, bits, seed)
+    }
+
+    pub fn contains(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        if self.size == 0 {
+            return Ok(false);
+        }
+        let key = FilterHasher::Xxh3(self.seed).hash(item.as_ref(py))? as u64;
+        let hash = mix(key.wrapping_add(self.hash_seed));
+
+        let [slot_0, slot_1, slot_2] = self.layout.slots(hash);
+        let combined = self.fingerprints.get(slot_0) ^ self.fingerprints.get(slot_1) ^ self.fingerprints.get(slot_2);
+        Ok(combined == fingerprint(hash, self.fingerprints.bits()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn fingerprint_bits(&self) -> u8 {
+        self.fingerprints.bits()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn size_in_bytes(&self) -> usize {
+        self.fingerprints.len() * self.fingerprints.bits() as usize / 8
+    }
+
+    pub fn bits_per_entry(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        (self.size_in_bytes() * 8) as f64 / self.size as f64
+    }
+
+    pub fn false_positive_rate(&self) -> f64 {
+        2f64.powi(-(self.fingerprints.bits() as i32))
+    }
+
+    pub fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Self::decode(data)
+    }
+
+    pub fn save(&self, path: &str) -> PyResult<()> {
+        write_file(path, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn load(path: &str) -> PyResult<Self> {
+        Self::decode(&read_file(path)?)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.size
+    }
+
+    pub fn __contains__(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        self.contains(py, item)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let layout = (self.layout.segment_length, self.layout.segment_count_length, self.layout.array_length);
+        let state = (STATE_VERSION, self.fingerprints.bits(), self.seed, self.size, self.hash_seed, layout, self.fingerprints.to_vec());
+        Ok(state.to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "BinaryFuseFilter", 6)?;
+        let bits: u8 = state.get_item(1)?.extract()?;
+        let seed: u64 = state.get_item(2)?.extract()?;
+        let size: usize = state.get_item(3)?.extract()?;
+        let hash_seed: u64 = state.get_item(4)?.extract()?;
+        let (segment_length, segment_count_length, array_length): (usize, usize, usize) = state.get_item(5)?.extract()?;
+        let values: Vec<u16> = state.get_item(6)?.extract()?;
+
+        if bits != 8 && bits != 16 {
+            return Err(invalid_state("BinaryFuseFilter", "unsupported fingerprint width"));
+        }
+        let layout = FuseLayout { segment_length, segment_count_length, array_length };
+        match Self::restore(size, seed, hash_seed, layout, Fingerprints::from_vec(values, bits)) {
+            Some(restored) => *self = restored,
+            None => return Err(invalid_state("BinaryFuseFilter", "fingerprints do not match the segment layout")),
+        }
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (PyList::empty(py), self.fingerprints.bits(), self.seed);
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let copied = BinaryFuseFilter {
+            size: self.size,
+            seed: self.seed,
+            hash_seed: self.hash_seed,
+            layout: self.layout,
+            fingerprints: self.fingerprints.clone(),
+        };
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, BinaryFuseFilter::build(&mut Vec::new(), 8, 0)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+}
@@ -1,69 +1,157 @@
+// pyo3 0.18 expands the __or__ / __and__ slots of the #[pymethods] block below into nested impls that newer
+// compilers flag. The generated wrappers sit outside the impl block, so the allow has to cover this module.
+#![allow(non_local_definitions)]
+
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyBytes;
 use pyo3::PyObject;
 use crate::other::rs_bit_array::BitArray;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_format::{read_file, write_file, ByteReader, ByteWriter};
+use crate::probability::rs_filter_hash::FilterHasher;
+
+// Double hashing -> The i-th probe lands on (h1 + i) * h2, where h2 is h1 rotated by 17 bits.
+pub(crate) fn probe_indices(py_hash: isize, hash_count: usize, size: usize) -> impl Iterator<Item = usize> {
+    let h1 = py_hash as usize;
+    let h2 = (h1 >> 17) | (h1 << 47);
+
+    (0..hash_count).map(move |i| h1.wrapping_add(i).wrapping_mul(h2) % size)
+}
 
 #[allow(dead_code)]
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct BloomFilter {
+    capacity: usize,
     probability: f64,
     size: usize,
     hash_count: usize,
+    hasher: FilterHasher,
     array: BitArray,
 }
 
 impl BloomFilter {
-    fn get_size(x_value: usize, y_value: f64) -> usize {
+    pub(crate) fn get_size(x_value: usize, y_value: f64) -> usize {
         assert!(y_value > 0.0 && y_value < 1.0, "probability value must be 0 - 1");
         let n = x_value as f64;
         let m = -(n * y_value.ln()) / (2f64.ln().powi(2));
         m.ceil() as usize
     }
 
-    fn get_hash_count(m: usize, n: usize) -> usize {
+    pub(crate) fn get_hash_count(m: usize, n: usize) -> usize {
         assert!(n > 0, "n value must be more than 0");
         let m = m as f64;
         let n = n as f64;
         let k = (m / n) * 2f64.ln();
         k.ceil() as usize
     }
+
+    fn check_compatible(&self, other: &BloomFilter) -> PyResult<()> {
+        if self.size != other.size || self.hash_count != other.hash_count {
+            return Err(PyValueError::new_err(format!(
+                "BloomFilters must share size and hash_count to be combined ({}/{} vs {}/{})",
+                self.size, self.hash_count, other.size, other.hash_count
+            )));
+        }
+        if self.hasher != other.hasher {
+            return Err(PyValueError::new_err("BloomFilters must share the same hash scheme and seed to be combined"));
+        }
+        Ok(())
+    }
+
+    fn combine(&self, other: &BloomFilter, union: bool) -> PyResult<BloomFilter> {
+        self.check_compatible(other)?;
+
+        let mut array = self.array.clone();
+        if union {
+            array.union_with(&other.array);
+        } else {
+            array.intersect_with(&other.array);
+        }
+
+        Ok(BloomFilter {
+            capacity: self.capacity,
+            probability: self.probability,
+            size: self.size,
+            hash_count: self.hash_count,
+            hasher: self.hasher,
+            array,
+        })
+    }
+
+    const MAGIC: &'static [u8; 4] = b"SNBF";
+    const FORMAT_VERSION: u16 = 1;
+
+    fn encode(&self) -> Vec<u8> {
+        // Header -> capacity, probability, bit count, hash count & hash scheme, followed by the raw bit words.
+        let mut writer = ByteWriter::new(Self::MAGIC, Self::FORMAT_VERSION);
+        writer.put_usize(self.capacity);
+        writer.put_f64(self.probability);
+        writer.put_usize(self.size);
+        writer.put_usize(self.hash_count);
+        writer.put_hasher(self.hasher);
+        writer.put_bytes(&self.array.to_bytes());
+        writer.finish()
+    }
+
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        let mut reader = ByteReader::new(data, Self::MAGIC, Self::FORMAT_VERSION, "BloomFilter")?;
+        let capacity = reader.get_usize()?;
+        let probability = reader.get_f64()?;
+        let size = reader.get_usize()?;
+        let hash_count = reader.get_usize()?;
+        let hasher = reader.get_hasher()?;
+
+        if capacity == 0 || size == 0 || hash_count == 0 || !(probability > 0.0 && probability < 1.0) {
+            return Err(reader.invalid("header values out of range"));
+        }
+
+        let words = size / 64 + usize::from(size % 64 != 0);
+        let array = match BitArray::from_bytes(size, reader.take(words * 8)?) {
+            Some(array) => array,
+            None => return Err(reader.invalid("bits set beyond the filter size")),
+        };
+        reader.finish()?;
+
+        Ok(Self {
+            capacity,
+            probability,
+            size,
+            hash_count,
+            hasher,
+            array,
+        })
+    }
 }
 
 #[pymethods]
 impl BloomFilter {
     #[new]
-    pub fn new(size: usize, probability: f64) -> Self {
+    pub fn new(size: usize, probability: f64, seed: Option<u64>) -> Self {
         let final_size = Self::get_size(size, probability);
         Self {
+            capacity: size,
             probability: probability,
             size: final_size,
             hash_count: Self::get_hash_count(final_size, size),
+            hasher: FilterHasher::from_seed(seed),
             array: BitArray::new(final_size),
         }
     }
 
     pub fn add(&mut self, py: Python<'_>, item: PyObject) -> PyResult<()> {
-        let py_hash = item.as_ref(py).hash()?;
+        let py_hash = self.hasher.hash(item.as_ref(py))?;
 
-        let h1 = py_hash as usize;
-        let h2 = (h1 >> 17) | (h1 << 47);
-
-        for i in 0..self.hash_count {
-            let combined_hash = h1.wrapping_add(i).wrapping_mul(h2);
-            let index = combined_hash % self.size;
+        for index in probe_indices(py_hash, self.hash_count, self.size) {
             self.array.set(index);
         }
         Ok(())
     }
 
     pub fn contains(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
-        let py_hash = item.as_ref(py).hash()?;
-
-        let h1 = py_hash as usize;
-        let h2 = (h1 >> 17) | (h1 << 47);
+        let py_hash = self.hasher.hash(item.as_ref(py))?;
 
-        for i in 0..self.hash_count {
-            let combined_hash = h1.wrapping_add(i).wrapping_mul(h2);
-            let index = combined_hash % self.size;
+        for index in probe_indices(py_hash, self.hash_count, self.size) {
             if !self.array.get(index) {
                 return Ok(false);
             }
@@ -74,4 +162,127 @@ impl BloomFilter {
     pub fn clear(&mut self) {
         self.array.clear_all();
     }
+
+    pub fn hash_scheme(&self) -> &'static str {
+        self.hasher.scheme()
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.hasher.seed()
+    }
+
+    pub fn union(&self, other: PyRef<BloomFilter>) -> PyResult<BloomFilter> {
+        self.combine(&other, true)
+    }
+
+    pub fn intersection(&self, other: PyRef<BloomFilter>) -> PyResult<BloomFilter> {
+        self.combine(&other, false)
+    }
+
+    pub fn approximate_len(&self) -> f64 {
+        // Swamidass-Baldi estimate -> n = -(m / k) * ln(1 - X / m), with X set bits out of m.
+        let m = self.size as f64;
+        let k = self.hash_count as f64;
+        let set_bits = self.array.count_ones() as f64;
+        if set_bits >= m {
+            return f64::INFINITY;
+        }
+        -(m / k) * (1.0 - set_bits / m).ln()
+    }
+
+    pub fn current_false_positive_rate(&self) -> f64 {
+        // Probability that all k probed bits are set, given the current fill ratio.
+        let fill_ratio = self.array.count_ones() as f64 / self.size as f64;
+        fill_ratio.powi(self.hash_count as i32)
+    }
+
+    pub fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Self::decode(data)
+    }
+
+    pub fn save(&self, path: &str) -> PyResult<()> {
+        write_file(path, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn load(path: &str) -> PyResult<Self> {
+        Self::decode(&read_file(path)?)
+    }
+
+    pub fn __or__(&self, other: PyRef<BloomFilter>) -> PyResult<BloomFilter> {
+        self.combine(&other, true)
+    }
+
+    pub fn __and__(&self, other: PyRef<BloomFilter>) -> PyResult<BloomFilter> {
+        self.combine(&other, false)
+    }
+
+    pub fn __ior__(&mut self, other: PyRef<BloomFilter>) -> PyResult<()> {
+        self.check_compatible(&other)?;
+        self.array.union_with(&other.array);
+        Ok(())
+    }
+
+    pub fn __iand__(&mut self, other: PyRef<BloomFilter>) -> PyResult<()> {
+        self.check_compatible(&other)?;
+        self.array.intersect_with(&other.array);
+        Ok(())
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let bits = PyBytes::new(py, &self.array.to_bytes());
+        Ok((STATE_VERSION, self.capacity, self.probability, self.size, self.hash_count, bits).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "BloomFilter", 5)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let probability: f64 = state.get_item(2)?.extract()?;
+        let size: usize = state.get_item(3)?.extract()?;
+        let hash_count: usize = state.get_item(4)?.extract()?;
+        let bits: &[u8] = state.get_item(5)?.extract()?;
+
+        if size == 0 || hash_count == 0 {
+            return Err(invalid_state("BloomFilter", "size and hash_count must be positive"));
+        }
+        let array = match BitArray::from_bytes(size, bits) {
+            Some(array) => array,
+            None => return Err(invalid_state("BloomFilter", "bit array does not match size")),
+        };
+
+        self.capacity = capacity;
+        self.probability = probability;
+        self.size = size;
+        self.hash_count = hash_count;
+        self.array = array;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        // The hash scheme travels through the constructor arguments, the state only carries the bits.
+        let args = (self.capacity, self.probability, self.hasher.seed());
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = BloomFilter::new(self.capacity, self.probability, self.hasher.seed());
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let (capacity, probability, seed) = {
+            let inner = slf.borrow();
+            (inner.capacity, inner.probability, inner.hasher.seed())
+        };
+        let copied = Py::new(py, BloomFilter::new(capacity, probability, seed))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,365 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_hash::{probe_sequence, FilterHasher};
+
+type HitterState = (PyObject, u64);
+
+// Upper bound on width * depth -> 2^28 u64 counters already take 2 GiB.
+const MAX_COUNTERS: usize = 1 << 28;
+
+struct Hitter {
+    item: PyObject,
+    estimate: u64,
+}
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct CountMinSketch {
+    width: usize,
+    depth: usize,
+    conservative: bool,
+    top_k: usize,
+    total: u64,
+    hasher: FilterHasher,
+    counters: Vec<u64>,
+    // Heavy hitters sit in an indexed min-heap keyed by estimate, slots maps item -> hitter index.
+    slots: Py<PyDict>,
+    hitters: Vec<Hitter>,
+    heap: Vec<usize>,
+    position: Vec<usize>,
+}
+
+impl CountMinSketch {
+    fn cells(&self, py: Python, item: &PyObject) -> PyResult<Vec<usize>> {
+        // Row r owns counters[r * width .. (r + 1) * width], each row probes one column.
+        let hash = self.hasher.hash(item.as_ref(py))?;
+        Ok(probe_sequence(hash, self.depth, self.width)
+            .enumerate()
+            .map(|(row, column)| row * self.width + column)
+            .collect())
+    }
+
+    fn min_count(&self, cells: &[usize]) -> u64 {
+        cells.iter().map(|&cell| self.counters[cell]).min().unwrap_or(0)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.position[self.heap[a]] = a;
+        self.position[self.heap[b]] = b;
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+            if left < self.heap.len() && self.hitters[self.heap[left]].estimate < self.hitters[self.heap[smallest]].estimate {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.hitters[self.heap[right]].estimate < self.hitters[self.heap[smallest]].estimate {
+                smallest = right;
+            }
+            if smallest == index {
+                return;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.hitters[self.heap[parent]].estimate <= self.hitters[self.heap[index]].estimate {
+                return;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn push(&mut self, py: Python, item: PyObject, estimate: u64) -> PyResult<()> {
+        let slot = self.hitters.len();
+        self.slots.as_ref(py).set_item(item.as_ref(py), slot)?;
+        self.hitters.push(Hitter { item, estimate });
+        self.heap.push(slot);
+        self.position.push(self.heap.len() - 1);
+        self.sift_up(self.heap.len() - 1);
+        Ok(())
+    }
+
+    fn rebuild(&mut self, py: Python, entries: Vec<HitterState>) -> PyResult<()> {
+        self.slots.as_ref(py).clear();
+        self.hitters.clear();
+        self.heap.clear();
+        self.position.clear();
+        for (item, estimate) in entries {
+            self.push(py, item, estimate)?;
+        }
+        Ok(())
+    }
+
+    fn ranked(&self, py: Python) -> Vec<HitterState> {
+        let mut entries: Vec<HitterState> = self.hitters
+            .iter()
+            .map(|hitter| (hitter.item.clone_ref(py), hitter.estimate))
+            .collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+        entries
+    }
+
+    fn track(&mut self, py: Python, item: &PyObject, estimate: u64) -> PyResult<()> {
+        if self.top_k == 0 {
+            return Ok(());
+        }
+
+        let slots = self.slots.as_ref(py);
+        if let Some(slot) = slots.get_item_with_error(item.as_ref(py))? {
+            let slot: usize = slot.extract()?;
+            self.hitters[slot].estimate = estimate;
+            self.sift_up(self.position[slot]);
+            self.sift_down(self.position[slot]);
+            return Ok(());
+        }
+        if self.hitters.len() < self.top_k {
+            return self.push(py, item.clone_ref(py), estimate);
+        }
+
+        // Full tracker -> the newcomer replaces the lightest hitter only if it outweighs it.
+        let slot = self.heap[0];
+        if estimate > self.hitters[slot].estimate {
+            let evicted = std::mem::replace(&mut self.hitters[slot], Hitter { item: item.clone_ref(py), estimate });
+            slots.del_item(evicted.item.as_ref(py))?;
+            slots.set_item(item.as_ref(py), slot)?;
+            self.sift_down(0);
+        }
+        Ok(())
+    }
+
+    fn absorb(&mut self, py: Python, other: &CountMinSketch) -> PyResult<()> {
+        if self.width != other.width || self.depth != other.depth || self.hasher != other.hasher {
+            return Err(PyValueError::new_err(format!(
+                "CountMinSketches must share width, depth and hash scheme to be merged ({}x{} vs {}x{})",
+                self.width, self.depth, other.width, other.depth
+            )));
+        }
+
+        for (counter, other_counter) in self.counters.iter_mut().zip(other.counters.iter()) {
+            *counter = counter.saturating_add(*other_counter);
+        }
+        self.total = self.total.saturating_add(other.total);
+
+        // Both trackers are re-scored against the merged counters.
+        let candidates: Vec<PyObject> = self.hitters
+            .iter()
+            .chain(other.hitters.iter())
+            .map(|hitter| hitter.item.clone_ref(py))
+            .collect();
+        for item in candidates {
+            let estimate = self.min_count(&self.cells(py, &item)?);
+            self.track(py, &item, estimate)?;
+        }
+        Ok(())
+    }
+
+    fn duplicate(&self, py: Python) -> PyResult<CountMinSketch> {
+        let mut copied = CountMinSketch::new(py, Some(self.width), Some(self.depth), None, None, Some(self.conservative), Some(self.top_k), self.hasher.seed())?;
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(copied)
+    }
+
+    fn table_size(width: usize, depth: usize) -> Option<usize> {
+        width.checked_mul(depth).filter(|&cells| cells <= MAX_COUNTERS)
+    }
+
+    fn dimensions(width: Option<usize>, depth: Option<usize>, epsilon: Option<f64>, delta: Option<f64>) -> PyResult<(usize, usize)> {
+        if epsilon.is_none() && delta.is_none() {
+            let width = width.unwrap_or(2048);
+            let depth = depth.unwrap_or(5);
+            if width == 0 || depth == 0 {
+                return Err(PyValueError::new_err("Width and depth must be positive integers"));
+            }
+            return Self::check_size(width, depth);
+        }
+
+        if width.is_some() || depth.is_some() {
+            return Err(PyValueError::new_err("Specify either width and depth or epsilon and delta, not both"));
+        }
+        let (epsilon, delta) = match (epsilon, delta) {
+            (Some(epsilon), Some(delta)) => (epsilon, delta),
+            _ => return Err(PyValueError::new_err("Epsilon and delta must be given together")),
+        };
+        if epsilon <= 0.0 || epsilon >= 1.0 || delta <= 0.0 || delta >= 1.0 {
+            return Err(PyValueError::new_err("Epsilon and delta must be between 0.0 and 1.0"));
+        }
+
+        // Estimates overshoot by at most epsilon * total with probability 1 - delta.
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        Self::check_size(width, depth)
+    }
+
+    fn check_size(width: usize, depth: usize) -> PyResult<(usize, usize)> {
+        match Self::table_size(width, depth) {
+            Some(_) => Ok((width, depth)),
+            None => Err(PyValueError::new_err(format!(
+                "CountMinSketch of {}x{} counters exceeds the maximum of {} counters", width, depth, MAX_COUNTERS
+            ))),
+        }
+    }
+}
+
+#[pymethods]
+impl CountMinSketch {
+    #[new]
+    pub fn new(
+        py: Python,
+        width: Option<usize>,
+        depth: Option<usize>,
+        epsilon: Option<f64>,
+        delta: Option<f64>,
+        conservative: Option<bool>,
+        top_k: Option<usize>,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let (width, depth) = Self::dimensions(width, depth, epsilon, delta)?;
+        Ok(Self {
+            width,
+            depth,
+            conservative: conservative.unwrap_or(false),
+            top_k: top_k.unwrap_or(0),
+            total: 0,
+            hasher: FilterHasher::from_seed(seed),
+            counters: vec![0; width * depth],
+            slots: PyDict::new(py).into(),
+            hitters: Vec::new(),
+            heap: Vec::new(),
+            position: Vec::new(),
+        })
+    }
+
+    pub fn add(&mut self, py: Python, item: PyObject, count: Option<u64>) -> PyResult<u64> {
+        let count = count.unwrap_or(1);
+        let cells = self.cells(py, &item)?;
+
+        let estimate = if self.conservative {
+            // Conservative update -> only raise counters that fall below the new minimum estimate.
+            let target = self.min_count(&cells).saturating_add(count);
+            for &cell in cells.iter() {
+                self.counters[cell] = self.counters[cell].max(target);
+            }
+            target
+        } else {
+            for &cell in cells.iter() {
+                self.counters[cell] = self.counters[cell].saturating_add(count);
+            }
+            self.min_count(&cells)
+        };
+
+        self.total = self.total.saturating_add(count);
+        self.track(py, &item, estimate)?;
+        Ok(estimate)
+    }
+
+    pub fn estimate(&self, py: Python, item: PyObject) -> PyResult<u64> {
+        Ok(self.min_count(&self.cells(py, &item)?))
+    }
+
+    pub fn merge(slf: &PyCell<Self>, py: Python, other: &PyCell<CountMinSketch>) -> PyResult<()> {
+        // x.merge(x) -> merge from a snapshot of the sketch, doubling every counter and the total.
+        if slf.is(other) {
+            let snapshot = slf.borrow().duplicate(py)?;
+            return slf.borrow_mut().absorb(py, &snapshot);
+        }
+        slf.borrow_mut().absorb(py, &other.borrow())
+    }
+
+    pub fn heavy_hitters(&self, py: Python) -> PyResult<PyObject> {
+        let elements: Vec<PyObject> = self.ranked(py).into_iter().map(|entry| entry.to_object(py)).collect();
+        Ok(PyList::new(py, elements).into())
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn is_conservative(&self) -> bool {
+        self.conservative
+    }
+
+    pub fn hash_scheme(&self) -> &'static str {
+        self.hasher.scheme()
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.hasher.seed()
+    }
+
+    pub fn clear(&mut self, py: Python) -> PyResult<()> {
+        self.counters.iter_mut().for_each(|counter| *counter = 0);
+        self.total = 0;
+        self.rebuild(py, Vec::new())
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((STATE_VERSION, self.width, self.depth, self.conservative, self.top_k, self.total, self.counters.clone(), self.ranked(py)).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "CountMinSketch", 7)?;
+        let width: usize = state.get_item(1)?.extract()?;
+        let depth: usize = state.get_item(2)?.extract()?;
+        let conservative: bool = state.get_item(3)?.extract()?;
+        let top_k: usize = state.get_item(4)?.extract()?;
+        let total: u64 = state.get_item(5)?.extract()?;
+        let counters: Vec<u64> = state.get_item(6)?.extract()?;
+        let heavy_hitters: Vec<HitterState> = state.get_item(7)?.extract()?;
+
+        if width == 0 || depth == 0 || Self::table_size(width, depth) != Some(counters.len()) {
+            return Err(invalid_state("CountMinSketch", "counter table does not match width and depth"));
+        }
+        if heavy_hitters.len() > top_k {
+            return Err(invalid_state("CountMinSketch", "more heavy hitters than top_k"));
+        }
+
+        self.width = width;
+        self.depth = depth;
+        self.conservative = conservative;
+        self.top_k = top_k;
+        self.total = total;
+        self.counters = counters;
+        self.rebuild(py, heavy_hitters)?;
+        if self.hitters.len() != self.slots.as_ref(py).len() {
+            return Err(invalid_state("CountMinSketch", "duplicate heavy hitters"));
+        }
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.width, self.depth, py.None(), py.None(), self.conservative, self.top_k, self.hasher.seed());
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        Ok(Py::new(py, self.duplicate(py)?)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let seed = slf.borrow().hasher.seed();
+        let copied = Py::new(py, CountMinSketch::new(py, Some(1), Some(1), None, None, None, None, seed)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
+}
@@ -0,0 +1,234 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_bloom_filter_native::{probe_indices, BloomFilter};
+use crate::probability::rs_filter_hash::FilterHasher;
+
+// Packed saturating counters -> 4-bit counters share a byte (low nibble first), 8-bit counters use one byte each.
+#[derive(Debug, Clone)]
+struct CounterArray {
+    bits: u8,
+    len: usize,
+    data: Vec<u8>,
+}
+
+impl CounterArray {
+    fn new(len: usize, bits: u8) -> Self {
+        Self {
+            bits,
+            len,
+            data: vec![0; Self::byte_len(len, bits)],
+        }
+    }
+
+    fn from_bytes(len: usize, bits: u8, data: &[u8]) -> Option<Self> {
+        if data.len() != Self::byte_len(len, bits) {
+            return None;
+        }
+        // An odd number of 4-bit counters leaves an unused high nibble, which must stay empty.
+        if bits == 4 && len % 2 == 1 && data[data.len() - 1] >> 4 != 0 {
+            return None;
+        }
+        Some(Self { bits, len, data: data.to_vec() })
+    }
+
+    fn byte_len(len: usize, bits: u8) -> usize {
+        if bits == 4 { len / 2 + len % 2 } else { len }
+    }
+
+    fn max(&self) -> u8 {
+        if self.bits == 4 { 0x0F } else { 0xFF }
+    }
+
+    fn get(&self, index: usize) -> u8 {
+        if self.bits == 8 {
+            return self.data[index];
+        }
+        let shift = (index % 2) * 4;
+        (self.data[index / 2] >> shift) & 0x0F
+    }
+
+    fn set(&mut self, index: usize, value: u8) {
+        if self.bits == 8 {
+            self.data[index] = value;
+            return;
+        }
+        let shift = (index % 2) * 4;
+        let byte = &mut self.data[index / 2];
+        *byte = (*byte & !(0x0F << shift)) | (value << shift);
+    }
+
+    fn saturated(&self) -> usize {
+        (0..self.len).filter(|&index| self.get(index) == self.max()).count()
+    }
+
+    fn clear(&mut self) {
+        self.data.iter_mut().for_each(|byte| *byte = 0);
+    }
+}
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct CountingBloomFilter {
+    capacity: usize,
+    probability: f64,
+    size: usize,
+    hash_count: usize,
+    overflows: usize,
+    hasher: FilterHasher,
+    counters: CounterArray,
+}
+
+impl CountingBloomFilter {
+    fn indices(&self, py: Python, item: &PyObject) -> PyResult<Vec<usize>> {
+        let py_hash = self.hasher.hash(item.as_ref(py))?;
+        Ok(probe_indices(py_hash, self.hash_count, self.size).collect())
+    }
+}
+
+#[pymethods]
+impl CountingBloomFilter {
+    #[new]
+    pub fn new(size: usize, probability: f64, counter_bits: Option<u8>, seed: Option<u64>) -> PyResult<Self> {
+        let bits = counter_bits.unwrap_or(4);
+        if bits != 4 && bits != 8 {
+            return Err(PyValueError::new_err(format!("Counter bits must be 4 or 8, not {}", bits)));
+        }
+
+        let final_size = BloomFilter::get_size(size, probability);
+        Ok(Self {
+            capacity: size,
+            probability,
+            size: final_size,
+            hash_count: BloomFilter::get_hash_count(final_size, size),
+            overflows: 0,
+            hasher: FilterHasher::from_seed(seed),
+            counters: CounterArray::new(final_size, bits),
+        })
+    }
+
+    pub fn add(&mut self, py: Python<'_>, item: PyObject) -> PyResult<()> {
+        let max = self.counters.max();
+
+        for index in self.indices(py, &item)? {
+            let count = self.counters.get(index);
+            if count == max {
+                // Saturated counters stick at their maximum, the lost increment is recorded instead.
+                self.overflows += 1;
+            } else {
+                self.counters.set(index, count + 1);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        let indices = self.indices(py, &item)?;
+        if indices.iter().any(|&index| self.counters.get(index) == 0) {
+            return Ok(false);
+        }
+
+        let max = self.counters.max();
+        for index in indices {
+            // A saturated counter no longer knows its true count, so it is never decremented.
+            let count = self.counters.get(index);
+            if count != max {
+                self.counters.set(index, count - 1);
+            }
+        }
+        Ok(true)
+    }
+
+    pub fn contains(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        Ok(self.indices(py, &item)?.iter().all(|&index| self.counters.get(index) > 0))
+    }
+
+    pub fn count_estimate(&self, py: Python<'_>, item: PyObject) -> PyResult<u8> {
+        // Every probed counter is at least the item's count -> the smallest one is the tightest bound.
+        Ok(self.indices(py, &item)?.iter().map(|&index| self.counters.get(index)).min().unwrap_or(0))
+    }
+
+    pub fn overflow_count(&self) -> usize {
+        self.overflows
+    }
+
+    pub fn saturated_counters(&self) -> usize {
+        self.counters.saturated()
+    }
+
+    pub fn counter_bits(&self) -> u8 {
+        self.counters.bits
+    }
+
+    pub fn hash_scheme(&self) -> &'static str {
+        self.hasher.scheme()
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.hasher.seed()
+    }
+
+    pub fn clear(&mut self) {
+        self.counters.clear();
+        self.overflows = 0;
+    }
+
+    pub fn __contains__(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        self.contains(py, item)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let counters = PyBytes::new(py, &self.counters.data);
+        Ok((STATE_VERSION, self.capacity, self.probability, self.counters.bits, self.size, self.hash_count, self.overflows, counters).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "CountingBloomFilter", 7)?;
+        let capacity: usize = state.get_item(1)?.extract()?;
+        let probability: f64 = state.get_item(2)?.extract()?;
+        let bits: u8 = state.get_item(3)?.extract()?;
+        let size: usize = state.get_item(4)?.extract()?;
+        let hash_count: usize = state.get_item(5)?.extract()?;
+        let overflows: usize = state.get_item(6)?.extract()?;
+        let data: &[u8] = state.get_item(7)?.extract()?;
+
+        if size == 0 || hash_count == 0 || (bits != 4 && bits != 8) {
+            return Err(invalid_state("CountingBloomFilter", "size, hash_count or counter bits out of range"));
+        }
+        let counters = match CounterArray::from_bytes(size, bits, data) {
+            Some(counters) => counters,
+            None => return Err(invalid_state("CountingBloomFilter", "counter array does not match size")),
+        };
+
+        self.capacity = capacity;
+        self.probability = probability;
+        self.size = size;
+        self.hash_count = hash_count;
+        self.overflows = overflows;
+        self.counters = counters;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.capacity, self.probability, self.counters.bits, self.hasher.seed());
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = CountingBloomFilter::new(self.capacity, self.probability, Some(self.counters.bits), self.hasher.seed())?;
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let (capacity, probability, bits, seed) = {
+            let inner = slf.borrow();
+            (inner.capacity, inner.probability, inner.counters.bits, inner.hasher.seed())
+        };
+        let copied = Py::new(py, CountingBloomFilter::new(capacity, probability, Some(bits), seed)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+}
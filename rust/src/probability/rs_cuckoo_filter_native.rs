@@ -1,9 +1,17 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyBytes;
 use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_format::{read_file, write_file, ByteReader, ByteWriter};
+use crate::probability::rs_filter_hash::{mix, FilterHasher};
+use rand::Rng;
+
+type TableState = (usize, Vec<Vec<u32>>, Vec<(u32, usize)>);
 
 #[derive(Debug, Clone)]
 struct CuckooBucket {
-    entries: Vec<Option<u16>>,
+    entries: Vec<Option<u32>>,
 }
 
 #[allow(dead_code)]
@@ -14,7 +22,7 @@ impl CuckooBucket {
         }
     }
 
-    fn insert(&mut self, fingerprint: u16) -> bool {
+    fn insert(&mut self, fingerprint: u32) -> bool {
         for slot in self.entries.iter_mut() {
             if slot.is_none() {
                 *slot = Some(fingerprint);
@@ -24,7 +32,7 @@ impl CuckooBucket {
         false
     }
 
-    fn delete(&mut self, fingerprint: u16) -> bool {
+    fn delete(&mut self, fingerprint: u32) -> bool {
         for slot in self.entries.iter_mut() {
             if let Some(value) = slot {
                 if *value == fingerprint {
@@ -36,20 +44,17 @@ impl CuckooBucket {
         false
     }
 
-    fn contains(&self, fingerprint: u16) -> bool {
+    fn contains(&self, fingerprint: u32) -> bool {
         let result = self.entries.contains(&Some(fingerprint));
         result
     }
 
-    fn swap(&mut self, fingerprint: u16) -> Option<u16> {
-        for slot in self.entries.iter_mut() {
-            if let Some(existing) = slot {
-                let evicted_value = *existing;
-                *slot = Some(fingerprint);
-                return Some(evicted_value);
-            }
-        }
-        None
+    fn swap(&mut self, fingerprint: u32) -> (usize, Option<u32>) {
+        // Evicting a random slot keeps repeated kicks from cycling through the same entry.
+        let slot = rand::thread_rng().gen_range(0..self.entries.len());
+        let evicted_value = self.entries[slot];
+        self.entries[slot] = Some(fingerprint);
+        (slot, evicted_value)
     }
 
     fn is_full(&self) -> bool {
@@ -61,9 +66,158 @@ impl CuckooBucket {
     }
 }
 
+// One bucket array plus its victim stash. A stash entry remembers the bucket it was kicked out of,
+// which is one of its two candidate buckets, so lookups can still match it exactly.
+// Tables index by a mixed hash, so the bucket index never shares its low bits with the fingerprint.
+#[derive(Debug, Clone)]
+struct CuckooTable {
+    buckets: Vec<CuckooBucket>,
+    stash: Vec<(u32, usize)>,
+    size: usize,
+}
+
+impl CuckooTable {
+    fn new(bucket_count: usize, bucket_size: usize) -> Self {
+        Self {
+            buckets: vec![CuckooBucket::new(bucket_size); bucket_count],
+            stash: Vec::new(),
+            size: 0,
+        }
+    }
+
+    fn first_index(&self, hash: u64) -> usize {
+        (mix(hash) as usize) % self.buckets.len()
+    }
+
+    fn second_index(&self, index: usize, fingerprint: u32) -> usize {
+        // XOR is its own inverse once the bucket count is a power of two, so either bucket leads back to the other.
+        let fingerprint_hash = hash_fingerprint(fingerprint);
+        (index ^ fingerprint_hash) % self.buckets.len()
+    }
+
+    fn candidates(&self, hash: u64, fingerprint: u32) -> (usize, usize) {
+        let index_1 = self.first_index(hash);
+        (index_1, self.second_index(index_1, fingerprint))
+    }
+
+    fn contains(&self, hash: u64, fingerprint: u32) -> bool {
+        let (index_1, index_2) = self.candidates(hash, fingerprint);
+        self.buckets[index_1].contains(fingerprint)
+            || self.buckets[index_2].contains(fingerprint)
+            || self.stash.iter().any(|&(fp, index)| fp == fingerprint && (index == index_1 || index == index_2))
+    }
+
+    fn insert(&mut self, hash: u64, fingerprint: u32, retries: usize) -> bool {
+        let (index_1, index_2) = self.candidates(hash, fingerprint);
+        if self.buckets[index_1].insert(fingerprint) || self.buckets[index_2].insert(fingerprint) {
+            self.size += 1;
+            return true;
+        }
+
+        // Cuckoo functionality -> every swap is recorded so a chain that strands its last victim can be undone.
+        let mut cuckoo_idx = if rand::random() { index_1 } else { index_2 };
+        let mut fp = fingerprint;
+        let mut kicks: Vec<(usize, usize, u32)> = Vec::new();
+
+        for _ in 0..retries {
+            match self.buckets[cuckoo_idx].swap(fp) {
+                (slot, Some(evicted_value)) => {
+                    kicks.push((cuckoo_idx, slot, evicted_value));
+                    fp = evicted_value;
+                    cuckoo_idx = self.second_index(cuckoo_idx, fp);
+                    if self.buckets[cuckoo_idx].insert(fp) {
+                        self.size += 1;
+                        return true;
+                    }
+                }
+                (_, None) => {
+                    self.size += 1;
+                    return true;
+                }
+            }
+        }
+
+        if self.stash.len() >= STASH_CAPACITY {
+            // No stash room for the homeless victim -> restore every displaced entry and report the table full.
+            for (index, slot, evicted_value) in kicks.into_iter().rev() {
+                self.buckets[index].entries[slot] = Some(evicted_value);
+            }
+            return false;
+        }
+
+        self.stash.push((fp, cuckoo_idx));
+        self.size += 1;
+        true
+    }
+
+    fn delete(&mut self, hash: u64, fingerprint: u32) -> bool {
+        let (index_1, index_2) = self.candidates(hash, fingerprint);
+        if self.buckets[index_1].delete(fingerprint) || self.buckets[index_2].delete(fingerprint) {
+            self.size -= 1;
+            self.drain_stash();
+            return true;
+        }
+
+        let position = self.stash
+            .iter()
+            .position(|&(fp, index)| fp == fingerprint && (index == index_1 || index == index_2));
+        match position {
+            Some(position) => {
+                self.stash.remove(position);
+                self.size -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn drain_stash(&mut self) {
+        // Freed slots are handed back to stashed victims, keeping the stash free for future kicks.
+        let stash = std::mem::take(&mut self.stash);
+        for (fp, index) in stash {
+            let alternate = self.second_index(index, fp);
+            if !self.buckets[index].insert(fp) && !self.buckets[alternate].insert(fp) {
+                self.stash.push((fp, index));
+            }
+        }
+    }
+
+    fn occupied(&self) -> usize {
+        self.buckets.iter().flat_map(|bucket| bucket.entries.iter()).filter(|slot| slot.is_some()).count()
+    }
+
+    fn slot_count(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.entries.len()).sum()
+    }
+
+    fn clear(&mut self) {
+        for bucket in self.buckets.iter_mut() {
+            for slot in bucket.entries.iter_mut() {
+                *slot = None;
+            }
+        }
+        self.stash.clear();
+        self.size = 0;
+    }
+}
+
+const STASH_CAPACITY: usize = 4;
+const FINGERPRINT_WIDTHS: [u8; 4] = [8, 12, 16, 32];
+
+fn hash_fingerprint(fingerprint: u32) -> usize {
+    let mut hashed = fingerprint as u64;
+    hashed ^= hashed >> 33;
+    hashed = hashed.wrapping_mul(0xff51afd7ed558ccd);
+    hashed ^= hashed >> 33;
+    hashed = hashed.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hashed ^= hashed >> 33;
+    hashed as usize
+}
+
 impl CuckooFilter {
-    fn produce_fingerprint(&self, hash: u64) -> u16 {
-        let fingerprint = (hash & 0xFFFF) as u16;
+    fn produce_fingerprint(&self, hash: u64) -> u32 {
+        let mask = if self.fingerprint_bits == 32 { u32::MAX as u64 } else { (1u64 << self.fingerprint_bits) - 1 };
+        let fingerprint = (hash & mask) as u32;
         if fingerprint == 0 {
             1
         } else {
@@ -71,122 +225,365 @@ impl CuckooFilter {
         }
     }
 
-    fn first_index(&self, hash: u64) -> usize {
-        (hash as usize) % self.buckets.len()
+    fn hash_item(&self, py: Python, item: &PyObject) -> PyResult<(u64, u32)> {
+        let py_hash = self.hasher.hash(item.as_ref(py))? as u64;
+        Ok((py_hash, self.produce_fingerprint(py_hash)))
     }
 
-    fn second_index(&mut self, index: usize, fingerprint: u16) -> usize {
-        let fingerprint_hash = self.hash_fingerprint(fingerprint);
-        (index ^ fingerprint_hash) % self.buckets.len()
+    fn check_fingerprint_bits(bits: u8) -> PyResult<()> {
+        if !FINGERPRINT_WIDTHS.contains(&bits) {
+            return Err(PyValueError::new_err(format!("Fingerprint bits must be 8, 12, 16 or 32, not {}", bits)));
+        }
+        Ok(())
+    }
+
+    fn grow(&mut self) {
+        let bucket_count = self.tables.last().map_or(1, |table| table.buckets.len()) * 2;
+        self.tables.push(CuckooTable::new(bucket_count, self.bucket_size));
+    }
+
+    const MAGIC: &'static [u8; 4] = b"SNCF";
+    const FORMAT_VERSION: u16 = 1;
+
+    fn put_fingerprint(&self, writer: &mut ByteWriter, fingerprint: u32) {
+        match self.fingerprint_bits {
+            8 => writer.put_u8(fingerprint as u8),
+            12 | 16 => writer.put_u16(fingerprint as u16),
+            _ => writer.put_u32(fingerprint),
+        }
+    }
+
+    fn get_fingerprint(reader: &mut ByteReader, bits: u8) -> PyResult<u32> {
+        match bits {
+            8 => Ok(reader.get_u8()? as u32),
+            12 | 16 => Ok(reader.get_u16()? as u32),
+            _ => reader.get_u32(),
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        // Header -> bucket size, retries, fingerprint width, resize flag & hash scheme, followed by every table:
+        // bucket count, entry count, stash (fingerprint, bucket) pairs and every slot (0 = empty).
+        let mut writer = ByteWriter::new(Self::MAGIC, Self::FORMAT_VERSION);
+        writer.put_usize(self.bucket_size);
+        writer.put_usize(self.retries);
+        writer.put_u8(self.fingerprint_bits);
+        writer.put_u8(u8::from(self.auto_resize));
+        writer.put_hasher(self.hasher);
+        writer.put_usize(self.tables.len());
+        for table in self.tables.iter() {
+            writer.put_usize(table.buckets.len());
+            writer.put_usize(table.size);
+            writer.put_usize(table.stash.len());
+            for &(fingerprint, index) in table.stash.iter() {
+                self.put_fingerprint(&mut writer, fingerprint);
+                writer.put_usize(index);
+            }
+            for bucket in table.buckets.iter() {
+                for slot in bucket.entries.iter() {
+                    self.put_fingerprint(&mut writer, slot.unwrap_or(0));
+                }
+            }
+        }
+        writer.finish()
     }
 
-    fn hash_fingerprint(&mut self, fingerprint: u16) -> usize {
-        let mut hashed = fingerprint as u64;
-        hashed ^= hashed >> 33;
-        hashed = hashed.wrapping_mul(0xff51afd7ed558ccd);
-        hashed ^= hashed >> 33;
-        hashed = hashed.wrapping_mul(0xc4ceb9fe1a85ec53);
-        hashed ^= hashed >> 33;
-        hashed as usize
+    fn decode_table(reader: &mut ByteReader, max_slots: usize, bucket_count: usize, bucket_size: usize, bits: u8) -> PyResult<CuckooTable> {
+        if bucket_count == 0 {
+            return Err(reader.invalid("bucket layout must be positive"));
+        }
+        if !matches!(bucket_count.checked_mul(bucket_size), Some(count) if count <= max_slots) {
+            return Err(reader.invalid("bucket layout larger than the data"));
+        }
+
+        let mut table = CuckooTable::new(bucket_count, bucket_size);
+        for bucket in table.buckets.iter_mut() {
+            for slot in bucket.entries.iter_mut() {
+                let fingerprint = Self::get_fingerprint(reader, bits)?;
+                if fingerprint != 0 {
+                    *slot = Some(fingerprint);
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        let mut reader = ByteReader::new(data, Self::MAGIC, Self::FORMAT_VERSION, "CuckooFilter")?;
+        let bucket_size = reader.get_usize()?;
+        let retries = reader.get_usize()?;
+        let fingerprint_bits = reader.get_u8()?;
+        let auto_resize = reader.get_u8()? != 0;
+        let hasher = reader.get_hasher()?;
+        let table_count = reader.get_usize()?;
+
+        if bucket_size == 0 || table_count == 0 || table_count > data.len() {
+            return Err(reader.invalid("bucket layout must be positive"));
+        }
+        if !FINGERPRINT_WIDTHS.contains(&fingerprint_bits) {
+            return Err(reader.invalid("unsupported fingerprint width"));
+        }
+
+        let mut tables = Vec::with_capacity(table_count);
+        for _ in 0..table_count {
+            let bucket_count = reader.get_usize()?;
+            let size = reader.get_usize()?;
+            let stash_len = reader.get_usize()?;
+            if stash_len > STASH_CAPACITY {
+                return Err(reader.invalid("stash larger than its capacity"));
+            }
+            let mut stash = Vec::with_capacity(stash_len);
+            for _ in 0..stash_len {
+                let fingerprint = Self::get_fingerprint(&mut reader, fingerprint_bits)?;
+                let index = reader.get_usize()?;
+                if fingerprint == 0 || index >= bucket_count {
+                    return Err(reader.invalid("stash entry out of range"));
+                }
+                stash.push((fingerprint, index));
+            }
+
+            let mut table = Self::decode_table(&mut reader, data.len(), bucket_count, bucket_size, fingerprint_bits)?;
+            if table.occupied() + stash.len() != size {
+                return Err(reader.invalid("entry count does not match stored fingerprints"));
+            }
+            table.stash = stash;
+            table.size = size;
+            tables.push(table);
+        }
+        reader.finish()?;
+
+        Ok(Self {
+            tables,
+            bucket_size,
+            retries,
+            fingerprint_bits,
+            auto_resize,
+            hasher,
+        })
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct CuckooFilter {
-    buckets: Vec<CuckooBucket>,
-    size: usize,
+    tables: Vec<CuckooTable>,
     bucket_size: usize,
-    retries: usize
+    retries: usize,
+    fingerprint_bits: u8,
+    auto_resize: bool,
+    hasher: FilterHasher,
 }
 
 #[pymethods]
 impl CuckooFilter {
     #[new]
-    pub fn new(size: Option<usize>, bucket_size: Option<usize>, retries: Option<usize>) -> Self {
-        let capacity = size.unwrap_or(100);
+    pub fn new(
+        size: Option<usize>,
+        bucket_size: Option<usize>,
+        retries: Option<usize>,
+        seed: Option<u64>,
+        fingerprint_bits: Option<u8>,
+        auto_resize: Option<bool>,
+    ) -> PyResult<Self> {
+        let capacity = size.unwrap_or(100).max(1).next_power_of_two();
         let bucket_size = bucket_size.unwrap_or(4);
-        Self {
-            buckets: vec![CuckooBucket::new(bucket_size); capacity],
-            size: 0,
+        let fingerprint_bits = fingerprint_bits.unwrap_or(16);
+        Self::check_fingerprint_bits(fingerprint_bits)?;
+        Ok(Self {
+            tables: vec![CuckooTable::new(capacity, bucket_size)],
             bucket_size: bucket_size,
-            retries: retries.unwrap_or(4),
-        }
+            retries: retries.unwrap_or(500),
+            fingerprint_bits,
+            auto_resize: auto_resize.unwrap_or(false),
+            hasher: FilterHasher::from_seed(seed),
+        })
     }
 
     pub fn insert(&mut self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
-        let py_hash = item.as_ref(py).hash()? as u64;
+        let (py_hash, fingerprint) = self.hash_item(py, &item)?;
 
-        let fingerprint = self.produce_fingerprint(py_hash);
-        let index_1 = self.first_index(py_hash);
-        let index_2 = self.second_index(index_1, fingerprint);
-
-        if self.buckets[index_1].insert(fingerprint) || self.buckets[index_2].insert(fingerprint) {
-            self.size += 1;
-            return Ok(true);
+        let retries = self.retries;
+        if let Some(table) = self.tables.last_mut() {
+            if table.insert(py_hash, fingerprint, retries) {
+                return Ok(true);
+            }
+        }
+        if !self.auto_resize {
+            return Ok(false); // Table and stash are full, the filter is left untouched
         }
 
-        // Cuckoo functionality
-        let mut cuckoo_idx = if rand::random() { index_1 } else { index_2 };
-        let mut fp = fingerprint;
-
-        for _ in 0..self.retries {
-            if let Some(evicted_value) = self.buckets[cuckoo_idx].swap(fp) {
-                fp = evicted_value;
-                cuckoo_idx = self.second_index(cuckoo_idx, fp);
-                if self.buckets[cuckoo_idx].insert(fp) {
-                    self.size += 1;
-                    return Ok(true);
-                }
-            } else {
-                break;
-            }
+        // Fingerprints cannot be re-homed without their items, so growth appends a table twice as large.
+        self.grow();
+        match self.tables.last_mut() {
+            Some(table) => Ok(table.insert(py_hash, fingerprint, retries)),
+            None => Ok(false),
         }
-        Ok(false) // Fingerprint entry failed to insert after all retry attempts
     }
 
-    pub fn contains(&mut self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
-        let py_hash = item.as_ref(py).hash()? as u64;
-        let fingerprint = self.produce_fingerprint(py_hash);
-        let index_1 = self.first_index(py_hash);
-        let index_2 = self.second_index(index_1, fingerprint);
-
-        Ok(
-            self.buckets[index_1].contains(fingerprint) || self.buckets[index_2].contains(fingerprint)
-        )
+    pub fn contains(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        let (py_hash, fingerprint) = self.hash_item(py, &item)?;
+        Ok(self.tables.iter().any(|table| table.contains(py_hash, fingerprint)))
     }
 
     pub fn delete(&mut self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
-        let py_hash = item.as_ref(py).hash()? as u64;
-        let fingerprint = self.produce_fingerprint(py_hash);
-        let index_1 = self.first_index(py_hash);
-        let index_2 = self.second_index(index_1, fingerprint);
-
-        if self.buckets[index_1].delete(fingerprint) || self.buckets[index_2].delete(fingerprint) {
-            self.size -= 1;
-            return Ok(true);
+        let (py_hash, fingerprint) = self.hash_item(py, &item)?;
+        for table in self.tables.iter_mut().rev() {
+            if table.delete(py_hash, fingerprint) {
+                return Ok(true);
+            }
         }
         Ok(false)
     }
 
     pub fn len(&self) -> usize {
-        self.size
+        self.tables.iter().map(|table| table.size).sum()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.size == 0
+        self.len() == 0
     }
 
     pub fn load_factor(&self) -> f64 {
-        let total = self.buckets.len() * self.bucket_size;
-        self.size as f64 / total as f64
+        let total: usize = self.tables.iter().map(CuckooTable::slot_count).sum();
+        self.len() as f64 / total as f64
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.tables.iter().map(CuckooTable::slot_count).sum()
+    }
+
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn stash_len(&self) -> usize {
+        self.tables.iter().map(|table| table.stash.len()).sum()
+    }
+
+    pub fn fingerprint_bits(&self) -> u8 {
+        self.fingerprint_bits
+    }
+
+    pub fn false_positive_rate(&self) -> f64 {
+        // A lookup compares against 2 * bucket_size * load slots per table; each matches with probability 2^-f.
+        let miss = 1.0 - 2f64.powi(-(self.fingerprint_bits as i32));
+        let slots_per_lookup = (2 * self.bucket_size) as f64;
+        let all_miss: f64 = self.tables
+            .iter()
+            .map(|table| miss.powf(slots_per_lookup * table.size as f64 / table.slot_count() as f64))
+            .product();
+        1.0 - all_miss
+    }
+
+    pub fn hash_scheme(&self) -> &'static str {
+        self.hasher.scheme()
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.hasher.seed()
     }
 
     pub fn clear(&mut self) {
-        for bucket in self.buckets.iter_mut() {
-            for slot in bucket.entries.iter_mut() {
-                *slot = None;
-            }
+        self.tables.truncate(1);
+        for table in self.tables.iter_mut() {
+            table.clear();
         }
-        self.size = 0;
     }
-}
\ No newline at end of file
+
+    pub fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Self::decode(data)
+    }
+
+    pub fn save(&self, path: &str) -> PyResult<()> {
+        write_file(path, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn load(path: &str) -> PyResult<Self> {
+        Self::decode(&read_file(path)?)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Fingerprints are never 0, so 0 marks an empty slot in the serialised buckets.
+        let tables: Vec<PyObject> = self.tables
+            .iter()
+            .map(|table| {
+                let buckets: Vec<Vec<u32>> = table.buckets
+                    .iter()
+                    .map(|bucket| bucket.entries.iter().map(|slot| slot.unwrap_or(0)).collect())
+                    .collect();
+                (table.size, buckets, table.stash.clone()).to_object(py)
+            })
+            .collect();
+        Ok((STATE_VERSION, self.bucket_size, self.retries, self.fingerprint_bits, self.auto_resize, tables).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "CuckooFilter", 5)?;
+        let bucket_size: usize = state.get_item(1)?.extract()?;
+        let retries: usize = state.get_item(2)?.extract()?;
+        let fingerprint_bits: u8 = state.get_item(3)?.extract()?;
+        let auto_resize: bool = state.get_item(4)?.extract()?;
+        let stored: Vec<TableState> = state.get_item(5)?.extract()?;
+
+        if !FINGERPRINT_WIDTHS.contains(&fingerprint_bits) {
+            return Err(invalid_state("CuckooFilter", "unsupported fingerprint width"));
+        }
+        if stored.is_empty() {
+            return Err(invalid_state("CuckooFilter", "at least one table is required"));
+        }
+        let mut tables = Vec::with_capacity(stored.len());
+        for (size, buckets, stash) in stored {
+            tables.push(Self::restore_table(size, buckets, stash, bucket_size)?);
+        }
+
+        self.tables = tables;
+        self.bucket_size = bucket_size;
+        self.retries = retries;
+        self.fingerprint_bits = fingerprint_bits;
+        self.auto_resize = auto_resize;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.tables[0].buckets.len(), self.bucket_size, self.retries, self.hasher.seed(), self.fingerprint_bits, self.auto_resize);
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = CuckooFilter::new(Some(1), Some(self.bucket_size), Some(self.retries), self.hasher.seed(), Some(self.fingerprint_bits), Some(self.auto_resize))?;
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let seed = slf.borrow().hasher.seed();
+        let copied = Py::new(py, CuckooFilter::new(Some(1), None, None, seed, None, None)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+}
+
+impl CuckooFilter {
+    fn restore_table(size: usize, stored: Vec<Vec<u32>>, stash: Vec<(u32, usize)>, bucket_size: usize) -> PyResult<CuckooTable> {
+        if stored.is_empty() || stored.iter().any(|entries| entries.len() != bucket_size) {
+            return Err(invalid_state("CuckooFilter", "inconsistent bucket layout"));
+        }
+        if stash.len() > STASH_CAPACITY || stash.iter().any(|&(fp, index)| fp == 0 || index >= stored.len()) {
+            return Err(invalid_state("CuckooFilter", "invalid stash"));
+        }
+
+        let buckets = stored
+            .into_iter()
+            .map(|entries| CuckooBucket {
+                entries: entries.into_iter().map(|fp| if fp == 0 { None } else { Some(fp) }).collect(),
+            })
+            .collect();
+        Ok(CuckooTable { buckets, stash, size })
+    }
+}
@@ -0,0 +1,158 @@
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use crate::probability::rs_filter_hash::FilterHasher;
+
+// ---------------------------------------------------------------------------------
+// Binary layout shared by the probabilistic filters.
+//
+// Every blob starts with a 4-byte magic tag naming the filter type, followed by a
+// little-endian u16 format version. All remaining integers are little-endian u64
+// and floats are IEEE-754 f64, so a blob written on one machine loads on any other.
+// Each filter versions its own layout next to its magic tag, and a reader only accepts
+// that exact version.
+// ---------------------------------------------------------------------------------
+
+pub struct ByteWriter {
+    buffer: Vec<u8>,
+}
+
+impl ByteWriter {
+    pub fn new(magic: &[u8; 4], version: u16) -> Self {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(magic);
+        buffer.extend_from_slice(&version.to_le_bytes());
+        Self { buffer }
+    }
+
+    pub fn put_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    pub fn put_u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn put_usize(&mut self, value: usize) {
+        self.buffer.extend_from_slice(&(value as u64).to_le_bytes());
+    }
+
+    pub fn put_u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn put_u64(&mut self, value: u64) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn put_f64(&mut self, value: f64) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn put_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    pub fn put_hasher(&mut self, hasher: FilterHasher) {
+        self.put_u8(hasher.tag());
+        self.put_u64(hasher.seed().unwrap_or(0));
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    name: &'static str,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(data: &'a [u8], magic: &[u8; 4], expected_version: u16, name: &'static str) -> PyResult<Self> {
+        let mut reader = Self { data, position: 0, name };
+
+        if reader.take(4)? != magic {
+            return Err(PyValueError::new_err(format!("Data is not a serialised {}", name)));
+        }
+
+        let version = reader.get_u16()?;
+        if version != expected_version {
+            return Err(PyValueError::new_err(format!(
+                "Unsupported {} format version {} (expected {})", name, version, expected_version
+            )));
+        }
+        Ok(reader)
+    }
+
+    pub fn take(&mut self, count: usize) -> PyResult<&'a [u8]> {
+        let end = match self.position.checked_add(count) {
+            Some(end) if end <= self.data.len() => end,
+            _ => return Err(PyValueError::new_err(format!("Serialised {} is truncated", self.name))),
+        };
+        let slice = &self.data[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    pub fn get_u8(&mut self) -> PyResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn get_u16(&mut self) -> PyResult<u16> {
+        let mut buffer = [0u8; 2];
+        buffer.copy_from_slice(self.take(2)?);
+        Ok(u16::from_le_bytes(buffer))
+    }
+
+    pub fn get_u32(&mut self) -> PyResult<u32> {
+        let mut buffer = [0u8; 4];
+        buffer.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buffer))
+    }
+
+    pub fn get_u64(&mut self) -> PyResult<u64> {
+        let mut buffer = [0u8; 8];
+        buffer.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buffer))
+    }
+
+    pub fn get_usize(&mut self) -> PyResult<usize> {
+        usize::try_from(self.get_u64()?)
+            .map_err(|_| PyValueError::new_err(format!("Serialised {} holds an out of range value", self.name)))
+    }
+
+    pub fn get_f64(&mut self) -> PyResult<f64> {
+        let mut buffer = [0u8; 8];
+        buffer.copy_from_slice(self.take(8)?);
+        Ok(f64::from_le_bytes(buffer))
+    }
+
+    pub fn finish(self) -> PyResult<()> {
+        if self.position != self.data.len() {
+            return Err(PyValueError::new_err(format!("Serialised {} has trailing bytes", self.name)));
+        }
+        Ok(())
+    }
+
+    pub fn get_hasher(&mut self) -> PyResult<FilterHasher> {
+        let tag = self.get_u8()?;
+        let seed = self.get_u64()?;
+        match FilterHasher::from_tag(tag, seed) {
+            Some(hasher) => Ok(hasher),
+            None => Err(self.invalid("unknown hash scheme")),
+        }
+    }
+
+    pub fn invalid(&self, reason: &str) -> PyErr {
+        PyValueError::new_err(format!("Invalid serialised {}: {}", self.name, reason))
+    }
+}
+
+pub fn write_file(path: &str, bytes: &[u8]) -> PyResult<()> {
+    std::fs::write(path, bytes).map_err(|err| PyIOError::new_err(format!("Unable to write '{}': {}", path, err)))
+}
+
+pub fn read_file(path: &str) -> PyResult<Vec<u8>> {
+    std::fs::read(path).map_err(|err| PyIOError::new_err(format!("Unable to read '{}': {}", path, err)))
+}
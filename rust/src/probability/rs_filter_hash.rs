@@ -0,0 +1,116 @@
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyByteArray, PyBytes, PyLong, PyString};
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+// ---------------------------------------------------------------------------------
+// Item hashing shared by the probabilistic filters.
+//
+// The default scheme reuses Python's hash(), which is salted per process for str
+// and bytes. The seeded scheme runs xxh3 over a canonical, type-tagged byte
+// encoding instead, so a filter answers identically in every process and machine
+// that uses the same seed.
+// ---------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterHasher {
+    Python,
+    Xxh3(u64),
+}
+
+impl FilterHasher {
+    pub fn from_seed(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => FilterHasher::Xxh3(seed),
+            None => FilterHasher::Python,
+        }
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        match self {
+            FilterHasher::Python => None,
+            FilterHasher::Xxh3(seed) => Some(*seed),
+        }
+    }
+
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            FilterHasher::Python => "python",
+            FilterHasher::Xxh3(_) => "xxh3",
+        }
+    }
+
+    pub fn hash(&self, item: &PyAny) -> PyResult<isize> {
+        match self {
+            FilterHasher::Python => item.hash(),
+            FilterHasher::Xxh3(seed) => Ok(xxh3_64_with_seed(&canonical_bytes(item)?, *seed) as isize),
+        }
+    }
+
+    // Binary tag -> 0 = Python hash(), 1 = xxh3 followed by its seed.
+    pub fn tag(&self) -> u8 {
+        match self {
+            FilterHasher::Python => 0,
+            FilterHasher::Xxh3(_) => 1,
+        }
+    }
+
+    pub fn from_tag(tag: u8, seed: u64) -> Option<Self> {
+        match tag {
+            0 => Some(FilterHasher::Python),
+            1 => Some(FilterHasher::Xxh3(seed)),
+            _ => None,
+        }
+    }
+}
+
+// Kirsch-Mitzenmacher double hashing over two splitmix64-derived hashes -> `count` well spread indices below `size`.
+// BloomFilter keeps its original rotated-hash probing for compatibility, but those probes correlate too strongly
+// for structures that need tight error bounds.
+pub fn probe_sequence(hash: isize, count: usize, size: usize) -> impl Iterator<Item = usize> {
+    let h1 = mix(hash as u64);
+    let h2 = mix(h1) | 1;
+
+    (0..count as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % size as u64) as usize)
+}
+
+pub fn mix(value: u64) -> u64 {
+    let mut hashed = value;
+    hashed = (hashed ^ (hashed >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hashed = (hashed ^ (hashed >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hashed ^ (hashed >> 31)
+}
+
+fn canonical_bytes(item: &PyAny) -> PyResult<Vec<u8>> {
+    // Every encoding starts with a type tag, so "1", b"1" and 1 never collide by construction.
+    let mut bytes = Vec::new();
+    if let Ok(text) = item.downcast::<PyString>() {
+        bytes.push(b's');
+        bytes.extend_from_slice(text.to_str()?.as_bytes());
+    } else if let Ok(data) = item.downcast::<PyBytes>() {
+        bytes.push(b'b');
+        bytes.extend_from_slice(data.as_bytes());
+    } else if let Ok(data) = item.downcast::<PyByteArray>() {
+        bytes.push(b'b');
+        bytes.extend_from_slice(&data.to_vec());
+    } else if item.is_instance_of::<PyLong>()? {
+        // Integers (and bools, which compare equal to 0 and 1) that fit an i64 use a fixed 8-byte little-endian
+        // form. Larger ones use their minimal little-endian two's complement form, which is always 9+ bytes long.
+        bytes.push(b'i');
+        match item.extract::<i64>() {
+            Ok(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+            Err(_) => {
+                let bit_length: usize = item.call_method0("bit_length")?.extract()?;
+                let length = bit_length / 8 + 1;
+                let kwargs = [("signed", true)].into_py_dict(item.py());
+                let encoded: &PyBytes = item.call_method("to_bytes", (length, "little"), Some(kwargs))?.downcast()?;
+                bytes.extend_from_slice(encoded.as_bytes());
+            }
+        }
+    } else {
+        return Err(PyTypeError::new_err(format!(
+            "Seeded hashing supports str, bytes and int items, not '{}'", item.get_type().name()?
+        )));
+    }
+    Ok(bytes)
+}
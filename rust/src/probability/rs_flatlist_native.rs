@@ -1,32 +1,55 @@
-use pyo3::basic::CompareOp;
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyIndexError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 use rand::Rng;
-use rustc_hash::FxHashMap;
-use std::cmp::Ordering;
 
-#[derive(Debug, Clone)]
+// ---------------------------------------------------------------------------------
+// Indexable skip list.
+//
+// Nodes live in an arena and link to their successors through per-level forward
+// pointers. Every forward pointer also records its span - the number of base
+// level steps it skips - so a descent from the head can count positions while
+// it searches. Search, insert, delete, rank and positional access are O(log n).
+// ---------------------------------------------------------------------------------
+
+const NIL: usize = usize::MAX;
+const HEAD: usize = 0;
+
+type SearchPath = (Vec<usize>, Vec<usize>);
+
+#[derive(Debug)]
 struct FlatNode {
-    id: usize,
-    payload: PyObject,
+    payload: Option<PyObject>,
+    forward: Vec<usize>,
+    span: Vec<usize>,
 }
 
 impl FlatNode {
-    fn new(id: usize, payload: PyObject) -> Self {
+    fn new(payload: Option<PyObject>, height: usize) -> Self {
         Self {
-            id: id,
-            payload: payload,
+            payload,
+            forward: vec![NIL; height],
+            span: vec![0; height],
         }
     }
 }
 
+#[pyclass(module = "_rust_snaplit")]
+pub struct Flatlist {
+    size: usize,
+    probability: f64,
+    height: usize,
+    length: usize,
+    nodes: Vec<FlatNode>,
+    free: Vec<usize>,
+}
+
 impl Flatlist {
     fn coin_toss(&self) -> bool {
         let mut rng = rand::thread_rng();
-        let n = rng.gen_bool(self.probability);
-        return n;
+        rng.gen_bool(self.probability)
     }
 
     fn get_top_level(&self) -> usize {
@@ -38,133 +61,216 @@ impl Flatlist {
         level
     }
 
-    fn py_compare(py: Python, x_value: &PyObject, y_value: &PyObject) -> Ordering {
-        let x = x_value.as_ref(py);
-        let y = y_value.as_ref(py);
+    fn payload(&self, node: usize) -> &PyObject {
+        self.nodes[node].payload.as_ref().expect("linked skip list node without payload")
+    }
 
-        // Try X < Y
-        if let Ok(obj) = x.rich_compare(y, CompareOp::Lt) {
-            if obj.is_true().unwrap_or(false) {
-                return Ordering::Less
+    fn search_value(&self, py: Python, value: &PyObject, after_equal: bool) -> PyResult<SearchPath> {
+        // Descend from the top level, stopping each level before the first element not less than
+        // `value` (or, with `after_equal`, the first element greater than it).
+        let value = value.as_ref(py);
+        let mut update = vec![HEAD; self.size];
+        let mut rank = vec![0; self.size];
+        let mut node = HEAD;
+        let mut traversed = 0;
+
+        for level in (0..self.height).rev() {
+            loop {
+                let next = self.nodes[node].forward[level];
+                if next == NIL {
+                    break;
+                }
+                let candidate = self.payload(next).as_ref(py);
+                let advance = if after_equal { !value.lt(candidate)? } else { candidate.lt(value)? };
+                if !advance {
+                    break;
+                }
+                traversed += self.nodes[node].span[level];
+                node = next;
             }
+            update[level] = node;
+            rank[level] = traversed;
         }
+        Ok((update, rank))
+    }
 
-        // Try X == Y
-        if let Ok(obj) = x.rich_compare(y, CompareOp::Eq) {
-            if obj.is_true().unwrap_or(false) {
-                return Ordering::Equal
+    fn search_rank(&self, index: usize) -> SearchPath {
+        // Same descent as `search_value`, but stops before the element at position `index`.
+        let mut update = vec![HEAD; self.size];
+        let mut rank = vec![0; self.size];
+        let mut node = HEAD;
+        let mut traversed = 0;
+
+        for level in (0..self.height).rev() {
+            while self.nodes[node].forward[level] != NIL && traversed + self.nodes[node].span[level] <= index {
+                traversed += self.nodes[node].span[level];
+                node = self.nodes[node].forward[level];
             }
+            update[level] = node;
+            rank[level] = traversed;
         }
+        (update, rank)
+    }
 
-        // Try X > Y
-        if let Ok(obj) = x.rich_compare(y, CompareOp::Gt) {
-            if obj.is_true().unwrap_or(false) {
-                return Ordering::Greater
+    fn link(&mut self, (update, rank): SearchPath, payload: PyObject) {
+        let top_lvl = self.get_top_level();
+        let mut update = update;
+        let mut rank = rank;
+
+        if top_lvl >= self.height {
+            for level in self.height..=top_lvl {
+                update[level] = HEAD;
+                rank[level] = 0;
+                self.nodes[HEAD].span[level] = self.length;
             }
+            self.height = top_lvl + 1;
         }
-        return Ordering::Equal
-    }
 
-}
+        let node = FlatNode::new(Some(payload), top_lvl + 1);
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = node;
+                id
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        for level in 0..=top_lvl {
+            let previous = update[level];
+            let skipped = rank[0] - rank[level];
+            self.nodes[id].forward[level] = self.nodes[previous].forward[level];
+            self.nodes[id].span[level] = self.nodes[previous].span[level] - skipped;
+            self.nodes[previous].forward[level] = id;
+            self.nodes[previous].span[level] = skipped + 1;
+        }
+        for (level, &previous) in update.iter().enumerate().take(self.height).skip(top_lvl + 1) {
+            self.nodes[previous].span[level] += 1;
+        }
+        self.length += 1;
+    }
 
-#[pyclass]
-pub struct Flatlist {
-    size: usize,
-    probability: f64,
-    nex_id: usize,
-    id_map: FxHashMap<usize, usize>,
-    list: Vec<Vec<FlatNode>>,
-}
+    fn unlink(&mut self, (update, _): SearchPath) -> PyObject {
+        let target = self.nodes[update[0]].forward[0];
 
-#[pymethods]
-impl Flatlist {
-    #[new]
-    pub fn new(num_list: Option<usize>, probability: Option<f64>) -> Self {
-        let rs_num = num_list.unwrap_or(4);
-        let rs_prob = probability.unwrap_or(0.5);
-        Self {
-            size: rs_num,
-            probability: rs_prob,
-            nex_id: 1,
-            id_map: FxHashMap::default(),
-            list: vec![Vec::new(); rs_num],
+        for (level, &previous) in update.iter().enumerate().take(self.height) {
+            if self.nodes[previous].forward[level] == target {
+                self.nodes[previous].span[level] += self.nodes[target].span[level];
+                self.nodes[previous].span[level] -= 1;
+                self.nodes[previous].forward[level] = self.nodes[target].forward[level];
+            } else {
+                self.nodes[previous].span[level] -= 1;
+            }
         }
+        while self.height > 1 && self.nodes[HEAD].forward[self.height - 1] == NIL {
+            self.height -= 1;
+        }
+
+        self.length -= 1;
+        self.free.push(target);
+        let removed = std::mem::replace(&mut self.nodes[target], FlatNode::new(None, 0));
+        removed.payload.expect("linked skip list node without payload")
     }
 
-    pub fn insert(&mut self, py: Python, payload: PyObject) -> PyResult<bool> {
-        let id = self.nex_id;
-        let new_node = FlatNode::new(id, payload);
-        let top_lvl = self.get_top_level();
+    fn node_at(&self, index: usize) -> usize {
+        let (update, _) = self.search_rank(index);
+        self.nodes[update[0]].forward[0]
+    }
 
-        for lvl in 0..=top_lvl {
-            let level = &mut self.list[lvl];
-            let index = level.binary_search_by(|node| Flatlist::py_compare(py, &node.payload, &new_node.payload)).unwrap_or_else(|i| i);
+    fn find_equal(&self, py: Python, key: &PyObject) -> PyResult<Option<(usize, usize)>> {
+        // Elements ordered alongside `key` sit in one run starting at bisect_left -> return the first equal one.
+        let (update, rank) = self.search_value(py, key, false)?;
+        let mut node = self.nodes[update[0]].forward[0];
+        let mut index = rank[0];
+        while node != NIL {
+            let candidate = self.payload(node).as_ref(py);
+            if key.as_ref(py).lt(candidate)? {
+                break;
+            }
+            if candidate.eq(key.as_ref(py))? {
+                return Ok(Some((index, node)));
+            }
+            node = self.nodes[node].forward[0];
+            index += 1;
+        }
+        Ok(None)
+    }
 
-            level.insert(index, new_node.clone());
+    fn normalise_index(&self, index: isize) -> PyResult<usize> {
+        let resolved = if index < 0 { index + self.length as isize } else { index };
+        if resolved < 0 || resolved as usize >= self.length {
+            return Err(PyIndexError::new_err(format!("Index {} out of range for Flatlist of length {}", index, self.length)));
         }
+        Ok(resolved as usize)
+    }
 
-        self.id_map.insert(id, top_lvl);
-        self.nex_id += 1;
-        Ok(true)
+    fn collect(&self, py: Python, start: usize, count: usize) -> Vec<PyObject> {
+        let mut elements = Vec::with_capacity(count);
+        let mut node = if start < self.length { self.node_at(start) } else { NIL };
+        while node != NIL && elements.len() < count {
+            elements.push(self.payload(node).clone_ref(py));
+            node = self.nodes[node].forward[0];
+        }
+        elements
     }
 
-    pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<PyObject> {
-        let mut removed_node = None;
-        let mut removed_id = 0;
-
-        for level in self.list.iter_mut().rev() {
-            level.retain(|node| {
-                if node.payload.as_ref(py).eq(key.as_ref(py)).unwrap_or(false) {
-                    removed_node = Some(node.payload.clone());
-                    removed_id = node.id.clone();
-                    false
-                } else {
-                    true
-                }
-            });
+    fn empty(size: usize, probability: f64) -> Self {
+        Self {
+            size,
+            probability,
+            height: 1,
+            length: 0,
+            nodes: vec![FlatNode::new(None, size)],
+            free: Vec::new(),
         }
-        
-        match removed_node {
-            Some(value) => {
-                self.id_map.remove(&removed_id);
-                return Ok(value)
-            },
-            None => return Err(PyValueError::new_err(format!("No value with key {} found in list!", key)))
+    }
+
+    fn check_parameters(size: usize, probability: f64) -> PyResult<()> {
+        if size == 0 {
+            return Err(PyValueError::new_err("Levels must be a positive integer"));
+        }
+        if !(probability > 0.0 && probability <= 1.0) {
+            return Err(PyValueError::new_err("Probability must be between 0.0 and 1.0"));
         }
+        Ok(())
     }
+}
 
-    pub fn contains(&self, py:Python, key: PyObject) -> PyResult<bool> {
-        for level in self.list.iter().rev() {
-            
-            let mut index = 0;
-            while index < level.len() {
-                let comparison = Flatlist::py_compare(py, &level[index].payload, &key);
+#[pymethods]
+impl Flatlist {
+    #[new]
+    pub fn new(num_list: Option<usize>, probability: Option<f64>) -> PyResult<Self> {
+        let rs_num = num_list.unwrap_or(32);
+        let rs_prob = probability.unwrap_or(0.5);
+        Self::check_parameters(rs_num, rs_prob)?;
+        Ok(Self::empty(rs_num, rs_prob))
+    }
 
-                match comparison {
-                    Ordering::Less => index += 1,
-                    Ordering::Equal => return Ok(true),
-                    Ordering::Greater => break,
-                }
+    pub fn insert(&mut self, py: Python, payload: PyObject) -> PyResult<bool> {
+        // Equal elements keep their insertion order -> new ones go after the existing run.
+        let path = self.search_value(py, &payload, true)?;
+        self.link(path, payload);
+        Ok(true)
+    }
+
+    pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<PyObject> {
+        match self.find_equal(py, &key)? {
+            Some((index, _)) => {
+                let path = self.search_rank(index);
+                Ok(self.unlink(path))
             }
+            None => Err(PyValueError::new_err(format!("No value with key {} found in list!", key))),
         }
-        Ok(false)
     }
 
-    pub fn get(&self, py:Python, key: PyObject) -> PyResult<Option<PyObject>> {
-        for level in self.list.iter().rev() {
-            
-            let mut index = 0;
-            while index < level.len() {
-                let comparison = Flatlist::py_compare(py, &level[index].payload, &key);
+    pub fn contains(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        Ok(self.find_equal(py, &key)?.is_some())
+    }
 
-                match comparison {
-                    Ordering::Less => index += 1,
-                    Ordering::Equal => return Ok(Some(level[index].payload.clone())),
-                    Ordering::Greater => break,
-                }
-            }
-        }
-        Ok(None)
+    pub fn get(&self, py: Python, key: PyObject) -> PyResult<Option<PyObject>> {
+        Ok(self.find_equal(py, &key)?.map(|(_, node)| self.payload(node).clone_ref(py)))
     }
 
     pub fn update(&mut self, py: Python, key: PyObject, new_value: PyObject) -> PyResult<bool> {
@@ -174,100 +280,162 @@ impl Flatlist {
     }
 
     pub fn extend(&mut self, py: Python, items: Vec<PyObject>) -> PyResult<bool> {
-        for item in items.iter() {
-            self.insert(py, item.clone())?;
+        for item in items.into_iter() {
+            self.insert(py, item)?;
         }
 
         Ok(true)
     }
 
     pub fn index_of(&self, py: Python, key: PyObject) -> PyResult<usize> {
-        for (idx, node) in self.list[0].iter().enumerate() {
-            if node.payload.as_ref(py).eq(key.as_ref(py)).unwrap_or(false) {
-                return Ok(idx);
-            }
+        match self.find_equal(py, &key)? {
+            Some((index, _)) => Ok(index),
+            None => Err(PyValueError::new_err(format!("No node with value {} found in Flatlist!", key))),
         }
-        return Err(PyValueError::new_err(format!("No node with value {} found in Flatlist!", key)));
+    }
+
+    pub fn rank(&self, py: Python, value: PyObject) -> PyResult<usize> {
+        self.bisect_left(py, value)
+    }
+
+    pub fn bisect_left(&self, py: Python, value: PyObject) -> PyResult<usize> {
+        Ok(self.search_value(py, &value, false)?.1[0])
+    }
+
+    pub fn bisect_right(&self, py: Python, value: PyObject) -> PyResult<usize> {
+        Ok(self.search_value(py, &value, true)?.1[0])
+    }
+
+    pub fn at(&self, py: Python, index: isize) -> PyResult<PyObject> {
+        let index = self.normalise_index(index)?;
+        Ok(self.payload(self.node_at(index)).clone_ref(py))
+    }
+
+    pub fn range(&self, py: Python, lo: Option<PyObject>, hi: Option<PyObject>) -> PyResult<PyObject> {
+        // Half-open [lo, hi) like bisect -> missing bounds extend to the ends of the list.
+        let start = match lo {
+            Some(lo) => self.bisect_left(py, lo)?,
+            None => 0,
+        };
+        let end = match hi {
+            Some(hi) => self.bisect_left(py, hi)?,
+            None => self.length,
+        };
+        let elements = self.collect(py, start, end.saturating_sub(start));
+        Ok(PyList::new(py, elements).into())
     }
 
     pub fn to_list<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
-        let elements: Vec<PyObject> = self.list[0].iter().map(|node| node.payload.clone_ref(py)).collect();
+        let elements = self.collect(py, 0, self.length);
         Ok(PyList::new(py, elements).into())
     }
 
     pub fn peek_first(&self, py: Python) -> PyResult<PyObject> {
-        let value = self.list[0].first().map(|node| node.payload.clone_ref(py));
-
-        match value {
-            Some(val) => return Ok(val),
-            None => return Err(PyValueError::new_err("No first value found in Flatlist!")),
+        match self.nodes[HEAD].forward[0] {
+            NIL => Err(PyValueError::new_err("No first value found in Flatlist!")),
+            node => Ok(self.payload(node).clone_ref(py)),
         }
     }
 
     pub fn peek_last(&self, py: Python) -> PyResult<PyObject> {
-        let value = self.list[0].last().map(|node| node.payload.clone_ref(py));
-
-        match value {
-            Some(val) => return Ok(val),
-            None => return Err(PyValueError::new_err("No last value found in Flatlist!")),
+        if self.length == 0 {
+            return Err(PyValueError::new_err("No last value found in Flatlist!"));
         }
+        Ok(self.payload(self.node_at(self.length - 1)).clone_ref(py))
     }
 
     pub fn pop_first(&mut self) -> PyResult<PyObject> {
-        if self.list[0].is_empty() {
+        if self.length == 0 {
             return Err(PyValueError::new_err("No nodes currently present in Flatlist!"));
         }
-
-        let node = self.list[0].remove(0);
-        let top_lvl = *self.id_map.get(&node.id).unwrap_or(&0);
-
-        self.id_map.remove(&node.id);
-
-        for level in 1..=top_lvl {
-            self.list[level].retain(|n| n.id != node.id);
-        }
-
-        Ok(node.payload)
+        let path = self.search_rank(0);
+        Ok(self.unlink(path))
     }
 
     pub fn pop_last(&mut self) -> PyResult<PyObject> {
-        if self.list[0].is_empty() {
+        if self.length == 0 {
             return Err(PyValueError::new_err("No nodes currently present in Flatlist!"));
         }
-
-        let node = self.list[0].pop().unwrap();
-        let top_lvl = *self.id_map.get(&node.id).unwrap_or(&0);
-
-        self.id_map.remove(&node.id);
-
-        for level in 1..=top_lvl {
-            self.list[level].retain(|n| n.id != node.id);
-        }
-
-        Ok(node.payload)
+        let path = self.search_rank(self.length - 1);
+        Ok(self.unlink(path))
     }
 
     pub fn merge(&mut self, py: Python, other: &Flatlist) -> PyResult<bool> {
-        for node in &other.list[0] {
-            self.insert(py, node.payload.clone_ref(py))?;
+        for payload in other.collect(py, 0, other.length) {
+            self.insert(py, payload)?;
         }
         Ok(true)
     }
 
     pub fn size(&self) -> PyResult<usize> {
-        Ok(self.list[0].len())
+        Ok(self.length)
     }
 
     pub fn is_empty(&self) -> PyResult<bool> {
-        Ok(self.list[0].is_empty())
+        Ok(self.length == 0)
     }
 
     pub fn clear(&mut self) -> PyResult<()> {
-        for level in self.list.iter_mut() {
-            level.clear();
+        *self = Self::empty(self.size, self.probability);
+        Ok(())
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.length
+    }
+
+    pub fn __getitem__(&self, py: Python, index: isize) -> PyResult<PyObject> {
+        self.at(py, index)
+    }
+
+    pub fn __contains__(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        self.contains(py, key)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Only the ordered payloads are stored -> levels are redrawn on restore.
+        let payloads = self.collect(py, 0, self.length);
+        Ok((STATE_VERSION, self.size, self.probability, payloads).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "Flatlist", 3)?;
+        let size: usize = state.get_item(1)?.extract()?;
+        let probability: f64 = state.get_item(2)?.extract()?;
+        let payloads: Vec<PyObject> = state.get_item(3)?.extract()?;
+
+        if size == 0 || !(probability > 0.0 && probability <= 1.0) {
+            return Err(invalid_state("Flatlist", "levels or probability out of range"));
+        }
+        for pair in payloads.windows(2) {
+            if pair[1].as_ref(py).lt(pair[0].as_ref(py))? {
+                return Err(invalid_state("Flatlist", "payloads are not sorted"));
+            }
+        }
+
+        let mut restored = Self::empty(size, probability);
+        for payload in payloads {
+            let path = restored.search_rank(restored.length);
+            restored.link(path, payload);
         }
-        self.id_map.clear();
-        self.nex_id = 1;
+        *self = restored;
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.size, self.probability), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = Flatlist::new(Some(self.size), Some(self.probability))?;
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, Flatlist::new(None, None)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
+}
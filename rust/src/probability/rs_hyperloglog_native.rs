@@ -0,0 +1,335 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_format::{read_file, write_file, ByteReader, ByteWriter};
+use crate::probability::rs_filter_hash::FilterHasher;
+use std::collections::BTreeMap;
+
+const MIN_PRECISION: u8 = 4;
+const MAX_PRECISION: u8 = 18;
+
+// Sparse sketches keep only the non-zero registers, keyed by register index.
+// Dense sketches hold one byte per register.
+#[derive(Debug, Clone)]
+enum Registers {
+    Sparse(BTreeMap<u32, u8>),
+    Dense(Vec<u8>),
+}
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct HyperLogLog {
+    precision: u8,
+    seed: u64,
+    registers: Registers,
+}
+
+impl HyperLogLog {
+    const MAGIC: &'static [u8; 4] = b"SNHL";
+    const FORMAT_VERSION: u16 = 1;
+
+    fn register_count(&self) -> usize {
+        1 << self.precision
+    }
+
+    fn check_precision(precision: u8) -> PyResult<()> {
+        if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+            return Err(PyValueError::new_err(format!(
+                "Precision must be between {} and {}, not {}", MIN_PRECISION, MAX_PRECISION, precision
+            )));
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, index: u32, rank: u8) {
+        match &mut self.registers {
+            Registers::Sparse(entries) => {
+                let entry = entries.entry(index).or_insert(0);
+                *entry = (*entry).max(rank);
+                // Past a quarter of the registers the map outweighs the flat array, so switch over.
+                if entries.len() > self.register_count() / 4 {
+                    self.densify();
+                }
+            }
+            Registers::Dense(values) => {
+                let value = &mut values[index as usize];
+                *value = (*value).max(rank);
+            }
+        }
+    }
+
+    fn densify(&mut self) {
+        if let Registers::Sparse(entries) = &self.registers {
+            let mut values = vec![0u8; self.register_count()];
+            for (&index, &rank) in entries.iter() {
+                values[index as usize] = rank;
+            }
+            self.registers = Registers::Dense(values);
+        }
+    }
+
+    fn non_zero(&self) -> Vec<(u32, u8)> {
+        match &self.registers {
+            Registers::Sparse(entries) => entries.iter().map(|(&index, &rank)| (index, rank)).collect(),
+            Registers::Dense(values) => values
+                .iter()
+                .enumerate()
+                .filter(|(_, &rank)| rank > 0)
+                .map(|(index, &rank)| (index as u32, rank))
+                .collect(),
+        }
+    }
+
+    fn estimate(&self) -> f64 {
+        let m = self.register_count() as f64;
+        let (harmonic_sum, zeros) = match &self.registers {
+            Registers::Sparse(entries) => {
+                let zeros = self.register_count() - entries.len();
+                let sum: f64 = entries.values().map(|&rank| 2f64.powi(-(rank as i32))).sum();
+                (sum + zeros as f64, zeros)
+            }
+            Registers::Dense(values) => {
+                let zeros = values.iter().filter(|&&rank| rank == 0).count();
+                (values.iter().map(|&rank| 2f64.powi(-(rank as i32))).sum(), zeros)
+            }
+        };
+
+        let alpha = match self.register_count() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let raw = alpha * m * m / harmonic_sum;
+
+        // Small range correction -> linear counting while empty registers remain.
+        if raw <= 2.5 * m && zeros > 0 {
+            return m * (m / zeros as f64).ln();
+        }
+        raw
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        // Header -> precision & hash scheme, then a representation tag followed by (index, rank) pairs or every register.
+        let mut writer = ByteWriter::new(Self::MAGIC, Self::FORMAT_VERSION);
+        writer.put_u8(self.precision);
+        writer.put_hasher(FilterHasher::Xxh3(self.seed));
+        match &self.registers {
+            Registers::Sparse(entries) => {
+                writer.put_u8(0);
+                writer.put_usize(entries.len());
+                for (&index, &rank) in entries.iter() {
+                    writer.put_u32(index);
+                    writer.put_u8(rank);
+                }
+            }
+            Registers::Dense(values) => {
+                writer.put_u8(1);
+                writer.put_bytes(values);
+            }
+        }
+        writer.finish()
+    }
+
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        let mut reader = ByteReader::new(data, Self::MAGIC, Self::FORMAT_VERSION, "HyperLogLog")?;
+        let precision = reader.get_u8()?;
+        let seed = match reader.get_hasher()? {
+            FilterHasher::Xxh3(seed) => seed,
+            FilterHasher::Python => return Err(reader.invalid("sketches must use the seeded hash scheme")),
+        };
+        if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+            return Err(reader.invalid("precision out of range"));
+        }
+
+        let register_count = 1usize << precision;
+        let max_rank = 64 - precision + 1;
+        let registers = match reader.get_u8()? {
+            0 => {
+                let count = reader.get_usize()?;
+                if count > register_count {
+                    return Err(reader.invalid("more sparse entries than registers"));
+                }
+                let mut entries = BTreeMap::new();
+                for _ in 0..count {
+                    let index = reader.get_u32()?;
+                    let rank = reader.get_u8()?;
+                    if index as usize >= register_count || rank == 0 || rank > max_rank {
+                        return Err(reader.invalid("sparse entry out of range"));
+                    }
+                    entries.insert(index, rank);
+                }
+                Registers::Sparse(entries)
+            }
+            1 => {
+                let values = reader.take(register_count)?.to_vec();
+                if values.iter().any(|&rank| rank > max_rank) {
+                    return Err(reader.invalid("register rank out of range"));
+                }
+                Registers::Dense(values)
+            }
+            _ => return Err(reader.invalid("unknown register representation")),
+        };
+        reader.finish()?;
+
+        Ok(Self {
+            precision,
+            seed,
+            registers,
+        })
+    }
+}
+
+#[pymethods]
+impl HyperLogLog {
+    #[new]
+    pub fn new(precision: Option<u8>, seed: Option<u64>) -> PyResult<Self> {
+        let precision = precision.unwrap_or(14);
+        Self::check_precision(precision)?;
+        Ok(Self {
+            precision,
+            seed: seed.unwrap_or(0),
+            registers: Registers::Sparse(BTreeMap::new()),
+        })
+    }
+
+    pub fn add(&mut self, py: Python<'_>, item: PyObject) -> PyResult<()> {
+        let hash = FilterHasher::Xxh3(self.seed).hash(item.as_ref(py))? as u64;
+
+        // Leading `precision` bits pick the register, the rank is the position of the first 1 in the rest.
+        let index = (hash >> (64 - self.precision)) as u32;
+        let remainder = hash << self.precision;
+        let rank = (remainder.leading_zeros() as u8).min(64 - self.precision) + 1;
+        self.update(index, rank);
+        Ok(())
+    }
+
+    pub fn count(&self) -> f64 {
+        self.estimate()
+    }
+
+    pub fn merge(slf: &PyCell<Self>, other: &PyCell<HyperLogLog>) -> PyResult<()> {
+        // x.merge(x) -> every register already holds its own maximum, so there is nothing to fold in.
+        if slf.is(other) {
+            return Ok(());
+        }
+        let mut this = slf.borrow_mut();
+        let other = other.borrow();
+        if this.precision != other.precision || this.seed != other.seed {
+            return Err(PyValueError::new_err(format!(
+                "HyperLogLogs must share precision and seed to be merged ({}/{} vs {}/{})",
+                this.precision, this.seed, other.precision, other.seed
+            )));
+        }
+        for (index, rank) in other.non_zero() {
+            this.update(index, rank);
+        }
+        Ok(())
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.registers, Registers::Sparse(_))
+    }
+
+    pub fn relative_error(&self) -> f64 {
+        1.04 / (self.register_count() as f64).sqrt()
+    }
+
+    pub fn clear(&mut self) {
+        self.registers = Registers::Sparse(BTreeMap::new());
+    }
+
+    pub fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Self::decode(data)
+    }
+
+    pub fn save(&self, path: &str) -> PyResult<()> {
+        write_file(path, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn load(path: &str) -> PyResult<Self> {
+        Self::decode(&read_file(path)?)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.estimate().round() as usize
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        // Sparse sketches store their (index, rank) pairs, dense sketches every register as bytes.
+        let registers = match &self.registers {
+            Registers::Sparse(_) => self.non_zero().to_object(py),
+            Registers::Dense(values) => PyBytes::new(py, values).to_object(py),
+        };
+        Ok((STATE_VERSION, self.precision, self.seed, self.is_sparse(), registers).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "HyperLogLog", 4)?;
+        let precision: u8 = state.get_item(1)?.extract()?;
+        let seed: u64 = state.get_item(2)?.extract()?;
+        let sparse: bool = state.get_item(3)?.extract()?;
+
+        if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+            return Err(invalid_state("HyperLogLog", "precision out of range"));
+        }
+        let register_count = 1usize << precision;
+        let max_rank = 64 - precision + 1;
+        let registers = if sparse {
+            let entries: Vec<(u32, u8)> = state.get_item(4)?.extract()?;
+            if entries.iter().any(|&(index, rank)| index as usize >= register_count || rank == 0 || rank > max_rank) {
+                return Err(invalid_state("HyperLogLog", "sparse entry out of range"));
+            }
+            Registers::Sparse(entries.into_iter().collect())
+        } else {
+            let values: &[u8] = state.get_item(4)?.extract()?;
+            if values.len() != register_count {
+                return Err(invalid_state("HyperLogLog", "register count does not match precision"));
+            }
+            if values.iter().any(|&rank| rank > max_rank) {
+                return Err(invalid_state("HyperLogLog", "register rank out of range"));
+            }
+            Registers::Dense(values.to_vec())
+        };
+
+        self.precision = precision;
+        self.seed = seed;
+        self.registers = registers;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.precision, self.seed), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let copied = HyperLogLog {
+            precision: self.precision,
+            seed: self.seed,
+            registers: self.registers.clone(),
+        };
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, HyperLogLog::new(None, None)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+}
@@ -0,0 +1,293 @@
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_hash::mix;
+use crate::probability::rs_minhash_native::{MinHash, MERSENNE_PRIME};
+use std::collections::HashMap;
+
+const INTEGRATION_STEPS: usize = 100;
+
+fn integrate(function: impl Fn(f64) -> f64, start: f64, end: f64) -> f64 {
+    // Composite trapezoid rule -> plenty for the smooth S-curves of the banding scheme.
+    let step = (end - start) / INTEGRATION_STEPS as f64;
+    let inner: f64 = (1..INTEGRATION_STEPS).map(|index| function(start + index as f64 * step)).sum();
+    step * (inner + (function(start) + function(end)) / 2.0)
+}
+
+fn optimal_bands(threshold: f64, num_perm: usize) -> (usize, usize) {
+    // A pair with similarity s collides in some band with probability 1 - (1 - s^rows)^bands.
+    // Pick the split minimising the false-positive area below the threshold plus the false-negative area above it.
+    let mut best = (1, num_perm);
+    let mut min_error = f64::INFINITY;
+    for bands in 1..=num_perm {
+        for rows in 1..=num_perm / bands {
+            let collide = |s: f64| 1.0 - (1.0 - s.powi(rows as i32)).powi(bands as i32);
+            let false_positive = integrate(collide, 0.0, threshold);
+            let false_negative = integrate(|s| 1.0 - collide(s), threshold, 1.0);
+            let error = 0.5 * false_positive + 0.5 * false_negative;
+            if error < min_error {
+                min_error = error;
+                best = (bands, rows);
+            }
+        }
+    }
+    best
+}
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct LSHIndex {
+    threshold: f64,
+    num_perm: usize,
+    bands: usize,
+    rows: usize,
+    next_id: usize,
+    ids: Py<PyDict>,
+    entries: HashMap<usize, (PyObject, Vec<u64>)>,
+    buckets: Vec<HashMap<u64, Vec<usize>>>,
+}
+
+impl LSHIndex {
+    fn extract_signature(&self, signature: &PyAny) -> PyResult<Vec<u64>> {
+        let values = match signature.extract::<PyRef<MinHash>>() {
+            Ok(minhash) => minhash.values.clone(),
+            Err(_) => signature.extract::<Vec<u64>>()?,
+        };
+        if values.len() != self.num_perm {
+            return Err(PyValueError::new_err(format!(
+                "Signature has {} permutations, but the LSHIndex expects {}", values.len(), self.num_perm
+            )));
+        }
+        if values.iter().any(|&value| value > MERSENNE_PRIME) {
+            return Err(PyValueError::new_err("Signature value out of range"));
+        }
+        Ok(values)
+    }
+
+    fn band_keys(&self, signature: &[u64]) -> Vec<u64> {
+        signature
+            .chunks(self.rows)
+            .take(self.bands)
+            .map(|band| band.iter().fold(0u64, |acc, &value| mix(acc ^ value)))
+            .collect()
+    }
+
+    fn index(&mut self, id: usize, signature: &[u64]) {
+        for (band, key) in self.band_keys(signature).into_iter().enumerate() {
+            self.buckets[band].entry(key).or_default().push(id);
+        }
+    }
+
+    fn unindex(&mut self, id: usize, signature: &[u64]) {
+        for (band, key) in self.band_keys(signature).into_iter().enumerate() {
+            if let Some(bucket) = self.buckets[band].get_mut(&key) {
+                bucket.retain(|&other| other != id);
+                if bucket.is_empty() {
+                    self.buckets[band].remove(&key);
+                }
+            }
+        }
+    }
+
+    fn check_threshold(threshold: f64) -> PyResult<()> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(PyValueError::new_err("Threshold must be between 0.0 and 1.0"));
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl LSHIndex {
+    #[new]
+    pub fn new(py: Python, threshold: Option<f64>, num_perm: Option<usize>, bands: Option<usize>, rows: Option<usize>) -> PyResult<Self> {
+        let threshold = threshold.unwrap_or(0.5);
+        let num_perm = num_perm.unwrap_or(128);
+        Self::check_threshold(threshold)?;
+        if num_perm == 0 {
+            return Err(PyValueError::new_err("Number of permutations must be a positive integer"));
+        }
+
+        let (bands, rows) = match (bands, rows) {
+            (None, None) => optimal_bands(threshold, num_perm),
+            (Some(bands), Some(rows)) => {
+                if bands == 0 || rows == 0 || bands * rows > num_perm {
+                    return Err(PyValueError::new_err(format!(
+                        "Bands and rows must be positive and bands * rows may not exceed {}", num_perm
+                    )));
+                }
+                (bands, rows)
+            }
+            _ => return Err(PyValueError::new_err("Bands and rows must be given together")),
+        };
+
+        Ok(Self {
+            threshold,
+            num_perm,
+            bands,
+            rows,
+            next_id: 0,
+            ids: PyDict::new(py).into(),
+            entries: HashMap::new(),
+            buckets: vec![HashMap::new(); bands],
+        })
+    }
+
+    pub fn insert(&mut self, py: Python, key: PyObject, signature: &PyAny) -> PyResult<()> {
+        let signature = self.extract_signature(signature)?;
+        let ids = self.ids.as_ref(py);
+        if ids.contains(key.as_ref(py))? {
+            return Err(PyValueError::new_err("Key already exists in the LSHIndex"));
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        ids.set_item(key.as_ref(py), id)?;
+        self.index(id, &signature);
+        self.entries.insert(id, (key, signature));
+        Ok(())
+    }
+
+    pub fn remove(&mut self, py: Python, key: PyObject) -> PyResult<()> {
+        let ids = self.ids.as_ref(py);
+        let id: usize = match ids.get_item(key.as_ref(py)) {
+            Some(id) => id.extract()?,
+            None => return Err(PyKeyError::new_err(key)),
+        };
+        ids.del_item(key.as_ref(py))?;
+        if let Some((_, signature)) = self.entries.remove(&id) {
+            self.unindex(id, &signature);
+        }
+        Ok(())
+    }
+
+    pub fn query(&self, py: Python, signature: &PyAny, threshold: Option<f64>) -> PyResult<PyObject> {
+        let threshold = threshold.unwrap_or(self.threshold);
+        Self::check_threshold(threshold)?;
+        let signature = self.extract_signature(signature)?;
+
+        // Candidates share at least one band, then get filtered on their estimated Jaccard similarity.
+        let mut candidates: Vec<usize> = self.band_keys(&signature)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(band, key)| self.buckets[band].get(&key))
+            .flatten()
+            .copied()
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut matches: Vec<(f64, usize)> = candidates
+            .into_iter()
+            .map(|id| (MinHash::similarity(&signature, &self.entries[&id].1), id))
+            .filter(|&(similarity, _)| similarity >= threshold)
+            .collect();
+        matches.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let keys: Vec<PyObject> = matches.into_iter().map(|(_, id)| self.entries[&id].0.clone_ref(py)).collect();
+        Ok(PyList::new(py, keys).into())
+    }
+
+    pub fn contains(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        self.ids.as_ref(py).contains(key)
+    }
+
+    pub fn keys(&self, py: Python) -> PyObject {
+        let mut entries: Vec<(&usize, &(PyObject, Vec<u64>))> = self.entries.iter().collect();
+        entries.sort_unstable_by_key(|(id, _)| **id);
+        let keys: Vec<PyObject> = entries.into_iter().map(|(_, (key, _))| key.clone_ref(py)).collect();
+        PyList::new(py, keys).into()
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    pub fn num_perm(&self) -> usize {
+        self.num_perm
+    }
+
+    pub fn bands(&self) -> usize {
+        self.bands
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self, py: Python) {
+        self.ids.as_ref(py).clear();
+        self.entries.clear();
+        self.buckets.iter_mut().for_each(HashMap::clear);
+        self.next_id = 0;
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn __contains__(&self, py: Python, key: PyObject) -> PyResult<bool> {
+        self.contains(py, key)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut entries: Vec<(&usize, &(PyObject, Vec<u64>))> = self.entries.iter().collect();
+        entries.sort_unstable_by_key(|(id, _)| **id);
+        let stored: Vec<PyObject> = entries
+            .into_iter()
+            .map(|(_, (key, signature))| (key.clone_ref(py), signature.clone()).to_object(py))
+            .collect();
+        Ok((STATE_VERSION, self.threshold, self.num_perm, self.bands, self.rows, stored).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "LSHIndex", 5)?;
+        let threshold: f64 = state.get_item(1)?.extract()?;
+        let num_perm: usize = state.get_item(2)?.extract()?;
+        let bands: usize = state.get_item(3)?.extract()?;
+        let rows: usize = state.get_item(4)?.extract()?;
+        let stored: Vec<(PyObject, Vec<u64>)> = state.get_item(5)?.extract()?;
+
+        if !(0.0..=1.0).contains(&threshold) || bands == 0 || rows == 0 || bands * rows > num_perm {
+            return Err(invalid_state("LSHIndex", "threshold or banding out of range"));
+        }
+        if stored.iter().any(|(_, signature)| signature.len() != num_perm) {
+            return Err(invalid_state("LSHIndex", "signature length does not match num_perm"));
+        }
+
+        let mut restored = LSHIndex::new(py, Some(threshold), Some(num_perm), Some(bands), Some(rows))?;
+        for (key, signature) in stored {
+            let signature = PyList::new(py, signature);
+            restored.insert(py, key, signature)?;
+        }
+        *self = restored;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.threshold, self.num_perm, self.bands, self.rows);
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = LSHIndex::new(py, Some(self.threshold), Some(self.num_perm), Some(self.bands), Some(self.rows))?;
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, LSHIndex::new(py, Some(0.5), Some(1), Some(1), Some(1))?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
+}
@@ -0,0 +1,181 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_hash::{mix, FilterHasher};
+
+// Permutations are universal hashes (a * h + b) mod p over the Mersenne prime p = 2^61 - 1.
+// Every permuted value is below p, so p doubles as the "no item seen yet" marker.
+pub(crate) const MERSENNE_PRIME: u64 = (1 << 61) - 1;
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct MinHash {
+    seed: u64,
+    permutations: Vec<(u64, u64)>,
+    pub(crate) values: Vec<u64>,
+}
+
+impl MinHash {
+    fn permutations(num_perm: usize, seed: u64) -> Vec<(u64, u64)> {
+        // Coefficients come from a splitmix64 stream over the seed -> identical seeds give identical permutations.
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            mix(state)
+        };
+        (0..num_perm)
+            .map(|_| (next() % (MERSENNE_PRIME - 1) + 1, next() % MERSENNE_PRIME))
+            .collect()
+    }
+
+    fn with_values(seed: u64, values: Vec<u64>) -> Self {
+        Self {
+            seed,
+            permutations: Self::permutations(values.len(), seed),
+            values,
+        }
+    }
+
+    fn update_hash(&mut self, hash: u64) {
+        for (value, &(a, b)) in self.values.iter_mut().zip(self.permutations.iter()) {
+            let permuted = ((a as u128 * hash as u128 + b as u128) % MERSENNE_PRIME as u128) as u64;
+            *value = (*value).min(permuted);
+        }
+    }
+
+    fn check_compatible(&self, other: &MinHash) -> PyResult<()> {
+        if self.values.len() != other.values.len() || self.seed != other.seed {
+            return Err(PyValueError::new_err(format!(
+                "MinHashes must share num_perm and seed to be compared ({}/{} vs {}/{})",
+                self.values.len(), self.seed, other.values.len(), other.seed
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn similarity(left: &[u64], right: &[u64]) -> f64 {
+        let matches = left.iter().zip(right.iter()).filter(|(a, b)| a == b).count();
+        matches as f64 / left.len() as f64
+    }
+}
+
+#[pymethods]
+impl MinHash {
+    #[new]
+    pub fn new(py: Python, num_perm: Option<usize>, seed: Option<u64>, items: Option<PyObject>) -> PyResult<Self> {
+        let num_perm = num_perm.unwrap_or(128);
+        if num_perm == 0 {
+            return Err(PyValueError::new_err("Number of permutations must be a positive integer"));
+        }
+
+        let mut minhash = Self::with_values(seed.unwrap_or(0), vec![MERSENNE_PRIME; num_perm]);
+        if let Some(items) = items {
+            minhash.update_batch(py, items)?;
+        }
+        Ok(minhash)
+    }
+
+    pub fn update(&mut self, py: Python<'_>, item: PyObject) -> PyResult<()> {
+        let hash = FilterHasher::Xxh3(self.seed).hash(item.as_ref(py))? as u64;
+        self.update_hash(hash);
+        Ok(())
+    }
+
+    pub fn update_batch(&mut self, py: Python<'_>, items: PyObject) -> PyResult<()> {
+        let hasher = FilterHasher::Xxh3(self.seed);
+        for item in items.as_ref(py).iter()? {
+            let hash = hasher.hash(item?)? as u64;
+            self.update_hash(hash);
+        }
+        Ok(())
+    }
+
+    pub fn jaccard(&self, other: PyRef<MinHash>) -> PyResult<f64> {
+        self.check_compatible(&other)?;
+        Ok(Self::similarity(&self.values, &other.values))
+    }
+
+    pub fn merge(slf: &PyCell<Self>, other: &PyCell<MinHash>) -> PyResult<()> {
+        // x.merge(x) -> every slot already holds its own minimum, so there is nothing to fold in.
+        if slf.is(other) {
+            return Ok(());
+        }
+        let mut this = slf.borrow_mut();
+        let other = other.borrow();
+        this.check_compatible(&other)?;
+        for (value, &other_value) in this.values.iter_mut().zip(other.values.iter()) {
+            *value = (*value).min(other_value);
+        }
+        Ok(())
+    }
+
+    pub fn count(&self) -> f64 {
+        // Each minimum is ~ p / (n + 1) -> invert the mean of the normalised minima.
+        let normalised: f64 = self.values.iter().map(|&value| value as f64 / MERSENNE_PRIME as f64).sum();
+        self.values.len() as f64 / normalised - 1.0
+    }
+
+    pub fn signature(&self, py: Python) -> PyObject {
+        PyList::new(py, &self.values).into()
+    }
+
+    pub fn num_perm(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(|&value| value == MERSENNE_PRIME)
+    }
+
+    pub fn clear(&mut self) {
+        self.values.iter_mut().for_each(|value| *value = MERSENNE_PRIME);
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((STATE_VERSION, self.seed, self.values.clone()).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "MinHash", 2)?;
+        let seed: u64 = state.get_item(1)?.extract()?;
+        let values: Vec<u64> = state.get_item(2)?.extract()?;
+
+        if values.is_empty() {
+            return Err(invalid_state("MinHash", "signature must not be empty"));
+        }
+        if values.iter().any(|&value| value > MERSENNE_PRIME) {
+            return Err(invalid_state("MinHash", "signature value out of range"));
+        }
+        *self = Self::with_values(seed, values);
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.values.len(), self.seed), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let copied = MinHash {
+            seed: self.seed,
+            permutations: self.permutations.clone(),
+            values: self.values.clone(),
+        };
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, MinHash::with_values(0, vec![MERSENNE_PRIME]))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+}
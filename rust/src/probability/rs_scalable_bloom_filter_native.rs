@@ -0,0 +1,245 @@
+use pyo3::exceptions::{PyOverflowError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::PyObject;
+use crate::other::rs_bit_array::BitArray;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_bloom_filter_native::BloomFilter;
+use crate::probability::rs_filter_hash::{probe_sequence, FilterHasher};
+
+type SliceState = (usize, f64, usize, usize, usize, Vec<u8>);
+
+#[derive(Debug, Clone)]
+struct FilterSlice {
+    capacity: usize,
+    probability: f64,
+    size: usize,
+    hash_count: usize,
+    count: usize,
+    array: BitArray,
+}
+
+impl FilterSlice {
+    fn new(capacity: usize, probability: f64) -> Self {
+        let size = BloomFilter::get_size(capacity, probability);
+        Self {
+            capacity,
+            probability,
+            size,
+            hash_count: BloomFilter::get_hash_count(size, capacity),
+            count: 0,
+            array: BitArray::new(size),
+        }
+    }
+
+    fn add(&mut self, py_hash: isize) {
+        for index in probe_sequence(py_hash, self.hash_count, self.size) {
+            self.array.set(index);
+        }
+        self.count += 1;
+    }
+
+    fn contains(&self, py_hash: isize) -> bool {
+        probe_sequence(py_hash, self.hash_count, self.size).all(|index| self.array.get(index))
+    }
+
+    fn is_full(&self) -> bool {
+        self.count >= self.capacity
+    }
+
+    fn false_positive_rate(&self) -> f64 {
+        let fill_ratio = self.array.count_ones() as f64 / self.size as f64;
+        fill_ratio.powi(self.hash_count as i32)
+    }
+}
+
+// Scalable Bloom filter (Almeida et al.) -> Slice i holds initial_capacity * growth^i items at an error
+// rate of probability * (1 - ratio) * ratio^i, so the compounded rate stays below probability.
+#[pyclass(module = "_rust_snaplit")]
+pub struct ScalableBloomFilter {
+    initial_capacity: usize,
+    probability: f64,
+    growth: usize,
+    ratio: f64,
+    hasher: FilterHasher,
+    slices: Vec<FilterSlice>,
+}
+
+impl ScalableBloomFilter {
+    fn next_slice(&self) -> PyResult<FilterSlice> {
+        let level = self.slices.len() as i32;
+        let capacity = self.growth
+            .checked_pow(level as u32)
+            .and_then(|factor| self.initial_capacity.checked_mul(factor));
+        let probability = self.probability * (1.0 - self.ratio) * self.ratio.powi(level);
+
+        // Past a few hundred slices the tightened error rate underflows, or the slice outgrows memory -> stop growing.
+        let capacity = match capacity {
+            Some(capacity) if probability >= f64::MIN_POSITIVE => capacity,
+            _ => return Err(PyOverflowError::new_err(format!("ScalableBloomFilter cannot grow further ({} slices)", level))),
+        };
+        let bits = -(capacity as f64) * probability.ln() / 2f64.ln().powi(2);
+        if bits > isize::MAX as f64 {
+            return Err(PyOverflowError::new_err(format!("ScalableBloomFilter cannot grow further ({} slices)", level)));
+        }
+        Ok(FilterSlice::new(capacity, probability))
+    }
+}
+
+#[pymethods]
+impl ScalableBloomFilter {
+    #[new]
+    pub fn new(initial_capacity: usize, probability: f64, growth: Option<usize>, ratio: Option<f64>, seed: Option<u64>) -> PyResult<Self> {
+        let growth = growth.unwrap_or(2);
+        let ratio = ratio.unwrap_or(0.9);
+        if initial_capacity == 0 {
+            return Err(PyValueError::new_err("Initial capacity must be a positive integer"));
+        }
+        if !(probability > 0.0 && probability < 1.0) {
+            return Err(PyValueError::new_err("Probability must be between 0.0 and 1.0"));
+        }
+        if growth == 0 {
+            return Err(PyValueError::new_err("Growth factor must be a positive integer"));
+        }
+        if !(ratio > 0.0 && ratio < 1.0) {
+            return Err(PyValueError::new_err("Tightening ratio must be between 0.0 and 1.0"));
+        }
+
+        let mut filter = Self {
+            initial_capacity,
+            probability,
+            growth,
+            ratio,
+            hasher: FilterHasher::from_seed(seed),
+            slices: Vec::new(),
+        };
+        filter.slices.push(filter.next_slice()?);
+        Ok(filter)
+    }
+
+    pub fn add(&mut self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        let py_hash = self.hasher.hash(item.as_ref(py))?;
+
+        // Items already reported as present are skipped, so every slice's count tracks distinct items.
+        if self.slices.iter().any(|slice| slice.contains(py_hash)) {
+            return Ok(false);
+        }
+        if self.slices.last().map_or(true, FilterSlice::is_full) {
+            let slice = self.next_slice()?;
+            self.slices.push(slice);
+        }
+        if let Some(slice) = self.slices.last_mut() {
+            slice.add(py_hash);
+        }
+        Ok(true)
+    }
+
+    pub fn contains(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        let py_hash = self.hasher.hash(item.as_ref(py))?;
+        Ok(self.slices.iter().any(|slice| slice.contains(py_hash)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.slices.iter().map(|slice| slice.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slices.iter().map(|slice| slice.capacity).sum()
+    }
+
+    pub fn slice_count(&self) -> usize {
+        self.slices.len()
+    }
+
+    pub fn false_positive_rate(&self) -> f64 {
+        // A lookup is a false positive if any slice reports one -> 1 - product of the per-slice misses.
+        1.0 - self.slices.iter().map(|slice| 1.0 - slice.false_positive_rate()).product::<f64>()
+    }
+
+    pub fn hash_scheme(&self) -> &'static str {
+        self.hasher.scheme()
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.hasher.seed()
+    }
+
+    pub fn clear(&mut self) -> PyResult<()> {
+        self.slices.clear();
+        self.slices.push(self.next_slice()?);
+        Ok(())
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.len()
+    }
+
+    pub fn __contains__(&self, py: Python<'_>, item: PyObject) -> PyResult<bool> {
+        self.contains(py, item)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let slices: Vec<PyObject> = self.slices
+            .iter()
+            .map(|slice| {
+                let bits = PyBytes::new(py, &slice.array.to_bytes());
+                (slice.capacity, slice.probability, slice.size, slice.hash_count, slice.count, bits).to_object(py)
+            })
+            .collect();
+        Ok((STATE_VERSION, self.initial_capacity, self.probability, self.growth, self.ratio, slices).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "ScalableBloomFilter", 5)?;
+        let initial_capacity: usize = state.get_item(1)?.extract()?;
+        let probability: f64 = state.get_item(2)?.extract()?;
+        let growth: usize = state.get_item(3)?.extract()?;
+        let ratio: f64 = state.get_item(4)?.extract()?;
+        let stored: Vec<SliceState> = state.get_item(5)?.extract()?;
+
+        if stored.is_empty() {
+            return Err(invalid_state("ScalableBloomFilter", "at least one slice is required"));
+        }
+        let mut slices = Vec::with_capacity(stored.len());
+        for (capacity, slice_probability, size, hash_count, count, bits) in stored {
+            if size == 0 || hash_count == 0 {
+                return Err(invalid_state("ScalableBloomFilter", "slice size and hash_count must be positive"));
+            }
+            let array = match BitArray::from_bytes(size, &bits) {
+                Some(array) => array,
+                None => return Err(invalid_state("ScalableBloomFilter", "slice bit array does not match size")),
+            };
+            slices.push(FilterSlice { capacity, probability: slice_probability, size, hash_count, count, array });
+        }
+
+        self.initial_capacity = initial_capacity;
+        self.probability = probability;
+        self.growth = growth;
+        self.ratio = ratio;
+        self.slices = slices;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (self.initial_capacity, self.probability, self.growth, self.ratio, self.hasher.seed());
+        Ok((py.get_type::<Self>(), args, self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut copied = ScalableBloomFilter::new(self.initial_capacity, self.probability, Some(self.growth), Some(self.ratio), self.hasher.seed())?;
+        copied.__setstate__(self.__getstate__(py)?.as_ref(py))?;
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let seed = slf.borrow().hasher.seed();
+        let copied = Py::new(py, ScalableBloomFilter::new(1, 0.5, None, None, seed)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+}
@@ -0,0 +1,453 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+use crate::probability::rs_filter_format::{read_file, write_file, ByteReader, ByteWriter};
+use std::f64::consts::PI;
+
+// ---------------------------------------------------------------------------------
+// Merging t-digest (Dunning & Ertl, 2019).
+//
+// Values are buffered and periodically merged into a sorted list of weighted
+// centroids. Every centroid may span at most one unit on two scale functions:
+//   k1(q) = compression / 2π * asin(2q - 1)      -> fine-grained body (p50, p90)
+//   k2(q) = compression / Z * ln(q / (1 - q))    -> single values in the tails (p99, p999)
+// with Z = 4 * ln(n / compression) + 24, so the digest stays at ~compression centroids.
+// ---------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+fn weighted_average(x1: f64, w1: f64, x2: f64, w2: f64) -> f64 {
+    let (low, high) = if x1 <= x2 { (x1, x2) } else { (x2, x1) };
+    ((x1 * w1 + x2 * w2) / (w1 + w2)).clamp(low, high)
+}
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct TDigest {
+    compression: f64,
+    min: f64,
+    max: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<Centroid>,
+}
+
+impl TDigest {
+    const MAGIC: &'static [u8; 4] = b"SNTD";
+    const FORMAT_VERSION: u16 = 1;
+
+    fn q_limit(&self, q: f64, total: f64) -> f64 {
+        // Largest quantile a centroid starting at q may reach -> one unit further on k1, capped by one unit on k2.
+        let k1 = self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin() + 1.0;
+        let k1_limit = if k1 >= self.compression / 4.0 { 1.0 } else { ((k1 * 2.0 * PI / self.compression).sin() + 1.0) / 2.0 };
+
+        let normalizer = self.compression / (4.0 * (total / self.compression).max(1.0).ln() + 24.0);
+        let q = q.clamp(1e-15, 1.0 - 1e-15);
+        let k2 = normalizer * (q / (1.0 - q)).ln() + 1.0;
+        let k2_limit = 1.0 / (1.0 + (-k2 / normalizer).exp());
+
+        k1_limit.min(k2_limit)
+    }
+
+    fn merged(&self) -> Vec<Centroid> {
+        if self.buffer.is_empty() {
+            return self.centroids.clone();
+        }
+        let mut pending: Vec<Centroid> = self.centroids.iter().chain(self.buffer.iter()).copied().collect();
+        pending.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total: f64 = pending.iter().map(|centroid| centroid.weight).sum();
+        let mut result: Vec<Centroid> = Vec::with_capacity(self.compression as usize * 2);
+        let mut current = pending[0];
+        let mut weight_so_far = 0.0;
+        let mut q_limit = self.q_limit(0.0, total);
+
+        for &next in pending.iter().skip(1) {
+            if (weight_so_far + current.weight + next.weight) / total <= q_limit {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_so_far += current.weight;
+                result.push(current);
+                q_limit = self.q_limit(weight_so_far / total, total);
+                current = next;
+            }
+        }
+        result.push(current);
+        result
+    }
+
+    fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            self.centroids = self.merged();
+            self.buffer.clear();
+        }
+    }
+
+    fn push(&mut self, mean: f64, weight: f64) {
+        self.min = self.min.min(mean);
+        self.max = self.max.max(mean);
+        self.buffer.push(Centroid { mean, weight });
+        if self.buffer.len() >= self.compression as usize * 5 {
+            self.flush();
+        }
+    }
+
+    fn check_not_empty(&self) -> PyResult<()> {
+        if self.centroids.is_empty() && self.buffer.is_empty() {
+            return Err(PyValueError::new_err("TDigest is empty"));
+        }
+        Ok(())
+    }
+
+    fn restore(compression: f64, min: f64, max: f64, centroids: Vec<Centroid>) -> Result<Self, &'static str> {
+        if !(compression.is_finite() && compression >= 10.0) {
+            return Err("compression out of range");
+        }
+        if centroids.iter().any(|centroid| !centroid.mean.is_finite() || !centroid.weight.is_finite() || centroid.weight <= 0.0) {
+            return Err("centroid mean or weight out of range");
+        }
+        if centroids.windows(2).any(|pair| pair[0].mean > pair[1].mean) {
+            return Err("centroids are not sorted");
+        }
+        if let (Some(first), Some(last)) = (centroids.first(), centroids.last()) {
+            if !(min <= first.mean && last.mean <= max) {
+                return Err("min or max does not enclose the centroids");
+            }
+        }
+
+        let (min, max) = if centroids.is_empty() { (f64::INFINITY, f64::NEG_INFINITY) } else { (min, max) };
+        Ok(Self { compression, min, max, centroids, buffer: Vec::new() })
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        // Header -> compression, min & max, then every (mean, weight) centroid.
+        let centroids = self.merged();
+        let mut writer = ByteWriter::new(Self::MAGIC, Self::FORMAT_VERSION);
+        writer.put_f64(self.compression);
+        writer.put_f64(self.min);
+        writer.put_f64(self.max);
+        writer.put_usize(centroids.len());
+        for centroid in centroids.iter() {
+            writer.put_f64(centroid.mean);
+            writer.put_f64(centroid.weight);
+        }
+        writer.finish()
+    }
+
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        let mut reader = ByteReader::new(data, Self::MAGIC, Self::FORMAT_VERSION, "TDigest")?;
+        let compression = reader.get_f64()?;
+        let min = reader.get_f64()?;
+        let max = reader.get_f64()?;
+        let count = reader.get_usize()?;
+        if count > data.len() / 16 {
+            return Err(reader.invalid("centroid count exceeds the data"));
+        }
+
+        let mut centroids = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mean = reader.get_f64()?;
+            let weight = reader.get_f64()?;
+            centroids.push(Centroid { mean, weight });
+        }
+        let digest = Self::restore(compression, min, max, centroids).map_err(|reason| reader.invalid(reason))?;
+        reader.finish()?;
+        Ok(digest)
+    }
+}
+
+#[pymethods]
+impl TDigest {
+    #[new]
+    pub fn new(compression: Option<f64>) -> PyResult<Self> {
+        let compression = compression.unwrap_or(100.0);
+        if !(compression.is_finite() && compression >= 10.0) {
+            return Err(PyValueError::new_err("Compression must be a number of at least 10"));
+        }
+        Ok(Self {
+            compression,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+        })
+    }
+
+    pub fn add(&mut self, value: f64, weight: Option<f64>) -> PyResult<()> {
+        let weight = weight.unwrap_or(1.0);
+        if !value.is_finite() {
+            return Err(PyValueError::new_err("Value must be a finite number"));
+        }
+        if !(weight > 0.0 && weight.is_finite()) {
+            return Err(PyValueError::new_err("Weight must be a positive number"));
+        }
+        self.push(value, weight);
+        Ok(())
+    }
+
+    pub fn add_batch(&mut self, values: Vec<f64>) -> PyResult<()> {
+        if values.iter().any(|value| !value.is_finite()) {
+            return Err(PyValueError::new_err("Value must be a finite number"));
+        }
+        for value in values {
+            self.push(value, 1.0);
+        }
+        Ok(())
+    }
+
+    pub fn merge(slf: &PyCell<Self>, other: &PyCell<TDigest>) {
+        // Snapshot the other digest before borrowing this one -> x.merge(x) doubles every centroid's weight.
+        let (centroids, min, max) = {
+            let other = other.borrow();
+            let centroids: Vec<Centroid> = other.centroids.iter().chain(other.buffer.iter()).copied().collect();
+            (centroids, other.min, other.max)
+        };
+        let mut this = slf.borrow_mut();
+        this.buffer.extend(centroids);
+        this.min = this.min.min(min);
+        this.max = this.max.max(max);
+        this.flush();
+    }
+
+    pub fn quantile(&mut self, q: f64) -> PyResult<f64> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(PyValueError::new_err("Quantile must be between 0.0 and 1.0"));
+        }
+        self.check_not_empty()?;
+        self.flush();
+
+        let centroids = &self.centroids;
+        let count = centroids.len();
+        if count == 1 {
+            return Ok(centroids[0].mean);
+        }
+
+        let total = self.total_weight();
+        let index = q * total;
+        let (first, last) = (centroids[0], centroids[count - 1]);
+
+        // Tails -> interpolate between the extreme values and the outermost centroid centres.
+        if index < 1.0 {
+            return Ok(self.min);
+        }
+        if first.weight > 1.0 && index < first.weight / 2.0 {
+            return Ok(self.min + (index - 1.0) / (first.weight / 2.0 - 1.0) * (first.mean - self.min));
+        }
+        if index > total - 1.0 {
+            return Ok(self.max);
+        }
+        if last.weight > 1.0 && total - index <= last.weight / 2.0 {
+            return Ok(self.max - (total - index - 1.0) / (last.weight / 2.0 - 1.0) * (self.max - last.mean));
+        }
+
+        // Body -> interpolate between neighbouring centroid centres, treating singletons as exact points.
+        let mut weight_so_far = first.weight / 2.0;
+        for pair in centroids.windows(2) {
+            let (left, right) = (pair[0], pair[1]);
+            let gap = (left.weight + right.weight) / 2.0;
+            if weight_so_far + gap > index {
+                let mut left_unit = 0.0;
+                if left.weight == 1.0 {
+                    if index - weight_so_far < 0.5 {
+                        return Ok(left.mean);
+                    }
+                    left_unit = 0.5;
+                }
+                let mut right_unit = 0.0;
+                if right.weight == 1.0 {
+                    if weight_so_far + gap - index <= 0.5 {
+                        return Ok(right.mean);
+                    }
+                    right_unit = 0.5;
+                }
+                let z1 = index - weight_so_far - left_unit;
+                let z2 = weight_so_far + gap - index - right_unit;
+                return Ok(weighted_average(left.mean, z2, right.mean, z1));
+            }
+            weight_so_far += gap;
+        }
+
+        let z1 = index - total - last.weight / 2.0;
+        let z2 = last.weight / 2.0 - z1;
+        Ok(weighted_average(last.mean, z1, self.max, z2))
+    }
+
+    pub fn quantiles(&mut self, qs: Vec<f64>) -> PyResult<Vec<f64>> {
+        qs.into_iter().map(|q| self.quantile(q)).collect()
+    }
+
+    pub fn cdf(&mut self, x: f64) -> PyResult<f64> {
+        if x.is_nan() {
+            return Err(PyValueError::new_err("Value must be a number"));
+        }
+        self.check_not_empty()?;
+        self.flush();
+
+        if x < self.min {
+            return Ok(0.0);
+        }
+        if x > self.max {
+            return Ok(1.0);
+        }
+
+        let centroids = &self.centroids;
+        let count = centroids.len();
+        let total = self.total_weight();
+        if count == 1 {
+            if self.max - self.min <= f64::EPSILON {
+                return Ok(0.5);
+            }
+            return Ok((x - self.min) / (self.max - self.min));
+        }
+
+        let (first, last) = (centroids[0], centroids[count - 1]);
+        if x < first.mean {
+            if x == self.min {
+                return Ok(0.5 / total);
+            }
+            return Ok((1.0 + (x - self.min) / (first.mean - self.min) * (first.weight / 2.0 - 1.0)) / total);
+        }
+        if x > last.mean {
+            if x == self.max {
+                return Ok(1.0 - 0.5 / total);
+            }
+            return Ok(1.0 - (1.0 + (self.max - x) / (self.max - last.mean) * (last.weight / 2.0 - 1.0)) / total);
+        }
+
+        let mut weight_so_far = 0.0;
+        let mut index = 0;
+        while index < count {
+            let centroid = centroids[index];
+            if centroid.mean == x {
+                // Equal centroids count half of their combined weight.
+                let mut equal_weight = 0.0;
+                while index < count && centroids[index].mean == x {
+                    equal_weight += centroids[index].weight;
+                    index += 1;
+                }
+                return Ok((weight_so_far + equal_weight / 2.0) / total);
+            }
+            let next = centroids[index + 1];
+            if x < next.mean {
+                let mut left_excluded = 0.0;
+                let mut right_excluded = 0.0;
+                if centroid.weight == 1.0 {
+                    if next.weight == 1.0 {
+                        return Ok((weight_so_far + 1.0) / total);
+                    }
+                    left_excluded = 0.5;
+                } else if next.weight == 1.0 {
+                    right_excluded = 0.5;
+                }
+                let gap = (centroid.weight + next.weight) / 2.0;
+                let base = weight_so_far + centroid.weight / 2.0 + left_excluded;
+                let fraction = (x - centroid.mean) / (next.mean - centroid.mean);
+                return Ok((base + (gap - left_excluded - right_excluded) * fraction) / total);
+            }
+            weight_so_far += centroid.weight;
+            index += 1;
+        }
+        Ok(1.0 - 0.5 / total)
+    }
+
+    pub fn total_weight(&self) -> f64 {
+        self.centroids.iter().chain(self.buffer.iter()).map(|centroid| centroid.weight).sum()
+    }
+
+    pub fn min(&self) -> PyResult<f64> {
+        self.check_not_empty()?;
+        Ok(self.min)
+    }
+
+    pub fn max(&self) -> PyResult<f64> {
+        self.check_not_empty()?;
+        Ok(self.max)
+    }
+
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    pub fn centroid_count(&mut self) -> usize {
+        self.flush();
+        self.centroids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty() && self.buffer.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.centroids.clear();
+        self.buffer.clear();
+        self.min = f64::INFINITY;
+        self.max = f64::NEG_INFINITY;
+    }
+
+    pub fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Self::decode(data)
+    }
+
+    pub fn save(&self, path: &str) -> PyResult<()> {
+        write_file(path, &self.encode())
+    }
+
+    #[staticmethod]
+    pub fn load(path: &str) -> PyResult<Self> {
+        Self::decode(&read_file(path)?)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.total_weight().round() as usize
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let centroids: Vec<(f64, f64)> = self.merged().iter().map(|centroid| (centroid.mean, centroid.weight)).collect();
+        Ok((STATE_VERSION, self.compression, self.min, self.max, centroids).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "TDigest", 4)?;
+        let compression: f64 = state.get_item(1)?.extract()?;
+        let min: f64 = state.get_item(2)?.extract()?;
+        let max: f64 = state.get_item(3)?.extract()?;
+        let centroids: Vec<(f64, f64)> = state.get_item(4)?.extract()?;
+
+        let centroids = centroids.into_iter().map(|(mean, weight)| Centroid { mean, weight }).collect();
+        *self = Self::restore(compression, min, max, centroids).map_err(|reason| invalid_state("TDigest", reason))?;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.compression,), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let copied = TDigest {
+            compression: self.compression,
+            min: self.min,
+            max: self.max,
+            centroids: self.centroids.clone(),
+            buffer: self.buffer.clone(),
+        };
+        Ok(Py::new(py, copied)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, TDigest::new(None)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(state.as_ref(py))?;
+        Ok(copied)
+    }
+}
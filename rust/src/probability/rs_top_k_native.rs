@@ -0,0 +1,305 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
+
+// ---------------------------------------------------------------------------------
+// Space-Saving heavy hitters (Metwally et al., 2005).
+//
+// At most k counters are kept. An untracked item takes over the counter with the
+// smallest count, inheriting that count as its error. Every tracked count
+// overestimates the true frequency by at most its error, and the error never
+// exceeds total / k. Counters sit in an indexed min-heap, so replacing the
+// minimum and bumping a counter are both O(log k).
+// ---------------------------------------------------------------------------------
+
+type CounterState = (PyObject, u64, u64);
+
+struct Counter {
+    item: PyObject,
+    count: u64,
+    error: u64,
+}
+
+#[pyclass(module = "_rust_snaplit")]
+pub struct TopK {
+    k: usize,
+    total: u64,
+    slots: Py<PyDict>,
+    counters: Vec<Counter>,
+    heap: Vec<usize>,
+    position: Vec<usize>,
+}
+
+impl TopK {
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.position[self.heap[a]] = a;
+        self.position[self.heap[b]] = b;
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+            if left < self.heap.len() && self.counters[self.heap[left]].count < self.counters[self.heap[smallest]].count {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.counters[self.heap[right]].count < self.counters[self.heap[smallest]].count {
+                smallest = right;
+            }
+            if smallest == index {
+                return;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.counters[self.heap[parent]].count <= self.counters[self.heap[index]].count {
+                return;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn min_count(&self) -> u64 {
+        match self.heap.first() {
+            Some(&slot) if self.counters.len() == self.k => self.counters[slot].count,
+            _ => 0,
+        }
+    }
+
+    fn push(&mut self, py: Python, item: PyObject, count: u64, error: u64) -> PyResult<()> {
+        let slot = self.counters.len();
+        self.slots.as_ref(py).set_item(item.as_ref(py), slot)?;
+        self.counters.push(Counter { item, count, error });
+        self.heap.push(slot);
+        self.position.push(self.heap.len() - 1);
+        self.sift_up(self.heap.len() - 1);
+        Ok(())
+    }
+
+    fn rebuild(&mut self, py: Python, entries: Vec<CounterState>) -> PyResult<()> {
+        self.slots.as_ref(py).clear();
+        self.counters.clear();
+        self.heap.clear();
+        self.position.clear();
+        for (item, count, error) in entries {
+            self.push(py, item, count, error)?;
+        }
+        Ok(())
+    }
+
+    fn absorb(&mut self, py: Python, other: &TopK) -> PyResult<()> {
+        // Mergeable summaries (Agarwal et al.) -> an item missing from one side may have occurred up to
+        // that side's minimum count times, so it is charged that minimum as count and error.
+        let self_min = self.min_count();
+        let other_min = other.min_count();
+        let merged = PyDict::new(py);
+        let mut entries: Vec<CounterState> = Vec::with_capacity(self.counters.len() + other.counters.len());
+
+        for counter in self.counters.iter() {
+            merged.set_item(counter.item.as_ref(py), entries.len())?;
+            entries.push((counter.item.clone_ref(py), counter.count + other_min, counter.error + other_min));
+        }
+        for counter in other.counters.iter() {
+            match merged.get_item(counter.item.as_ref(py)) {
+                Some(index) => {
+                    let entry = &mut entries[index.extract::<usize>()?];
+                    entry.1 = entry.1 - other_min + counter.count;
+                    entry.2 = entry.2 - other_min + counter.error;
+                }
+                None => entries.push((counter.item.clone_ref(py), counter.count + self_min, counter.error + self_min)),
+            }
+        }
+
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+        entries.truncate(self.k);
+        self.total = self.total.saturating_add(other.total);
+        self.rebuild(py, entries)
+    }
+
+    fn duplicate(&self, py: Python) -> PyResult<TopK> {
+        let mut copied = TopK::new(py, self.k)?;
+        copied.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Ok(copied)
+    }
+
+    fn ranked(&self, py: Python) -> Vec<CounterState> {
+        let mut entries: Vec<CounterState> = self.counters
+            .iter()
+            .map(|counter| (counter.item.clone_ref(py), counter.count, counter.error))
+            .collect();
+        // Ties favour the smaller error -> the count the summary is more certain about ranks first.
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+        entries
+    }
+}
+
+#[pymethods]
+impl TopK {
+    #[new]
+    pub fn new(py: Python, k: usize) -> PyResult<Self> {
+        if k == 0 {
+            return Err(PyValueError::new_err("K must be a positive integer"));
+        }
+        Ok(Self {
+            k,
+            total: 0,
+            slots: PyDict::new(py).into(),
+            counters: Vec::with_capacity(k),
+            heap: Vec::with_capacity(k),
+            position: Vec::with_capacity(k),
+        })
+    }
+
+    pub fn add(&mut self, py: Python, item: PyObject, count: Option<u64>) -> PyResult<Option<PyObject>> {
+        let count = count.unwrap_or(1);
+        if count == 0 {
+            return Err(PyValueError::new_err("Count must be a positive integer"));
+        }
+
+        // The lookup hashes the item -> an unhashable item raises here, before total or any counter moves.
+        let slots = self.slots.as_ref(py);
+        if let Some(slot) = slots.get_item_with_error(item.as_ref(py))? {
+            let slot: usize = slot.extract()?;
+            self.counters[slot].count = self.counters[slot].count.saturating_add(count);
+            self.sift_down(self.position[slot]);
+            self.total = self.total.saturating_add(count);
+            return Ok(None);
+        }
+        if self.counters.len() < self.k {
+            self.push(py, item, count, 0)?;
+            self.total = self.total.saturating_add(count);
+            return Ok(None);
+        }
+
+        // Full summary -> the new item evicts the smallest counter and inherits its count as error.
+        let slot = self.heap[0];
+        let minimum = self.counters[slot].count;
+        let evicted = std::mem::replace(
+            &mut self.counters[slot],
+            Counter { item: item.clone_ref(py), count: minimum.saturating_add(count), error: minimum },
+        );
+        slots.del_item(evicted.item.as_ref(py))?;
+        slots.set_item(item.as_ref(py), slot)?;
+        self.sift_down(0);
+        self.total = self.total.saturating_add(count);
+        Ok(Some(evicted.item))
+    }
+
+    pub fn top(&self, py: Python, n: Option<usize>) -> PyResult<PyObject> {
+        let n = n.unwrap_or(self.k);
+        let entries: Vec<PyObject> = self.ranked(py).into_iter().take(n).map(|entry| entry.to_object(py)).collect();
+        Ok(PyList::new(py, entries).into())
+    }
+
+    pub fn estimate(&self, py: Python, item: PyObject) -> PyResult<u64> {
+        match self.slots.as_ref(py).get_item_with_error(item.as_ref(py))? {
+            Some(slot) => Ok(self.counters[slot.extract::<usize>()?].count),
+            None => Ok(self.min_count()),
+        }
+    }
+
+    pub fn error(&self, py: Python, item: PyObject) -> PyResult<u64> {
+        match self.slots.as_ref(py).get_item_with_error(item.as_ref(py))? {
+            Some(slot) => Ok(self.counters[slot.extract::<usize>()?].error),
+            None => Ok(self.min_count()),
+        }
+    }
+
+    pub fn merge(slf: &PyCell<Self>, py: Python, other: &PyCell<TopK>) -> PyResult<()> {
+        // x.merge(x) -> merge from a snapshot of the summary, doubling every count, error and the total.
+        if slf.is(other) {
+            let snapshot = slf.borrow().duplicate(py)?;
+            return slf.borrow_mut().absorb(py, &snapshot);
+        }
+        slf.borrow_mut().absorb(py, &other.borrow())
+    }
+
+    pub fn contains(&self, py: Python, item: PyObject) -> PyResult<bool> {
+        self.slots.as_ref(py).contains(item)
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn max_error(&self) -> u64 {
+        self.min_count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.counters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty()
+    }
+
+    pub fn clear(&mut self, py: Python) -> PyResult<()> {
+        self.total = 0;
+        self.rebuild(py, Vec::new())
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.counters.len()
+    }
+
+    pub fn __contains__(&self, py: Python, item: PyObject) -> PyResult<bool> {
+        self.contains(py, item)
+    }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((STATE_VERSION, self.k, self.total, self.ranked(py)).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "TopK", 3)?;
+        let k: usize = state.get_item(1)?.extract()?;
+        let total: u64 = state.get_item(2)?.extract()?;
+        let entries: Vec<CounterState> = state.get_item(3)?.extract()?;
+
+        if k == 0 || entries.len() > k {
+            return Err(invalid_state("TopK", "more counters than k"));
+        }
+        if entries.iter().any(|&(_, count, error)| count == 0 || error > count) {
+            return Err(invalid_state("TopK", "counter error exceeds its count"));
+        }
+
+        let mut restored = TopK::new(py, k)?;
+        restored.total = total;
+        restored.rebuild(py, entries)?;
+        if restored.counters.len() != restored.slots.as_ref(py).len() {
+            return Err(invalid_state("TopK", "duplicate items"));
+        }
+        *self = restored;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.k,), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        Ok(Py::new(py, self.duplicate(py)?)?.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, TopK::new(py, 1)?)?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
+}
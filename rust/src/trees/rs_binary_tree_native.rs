@@ -2,6 +2,7 @@ use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 struct LeafNode {
@@ -22,7 +23,7 @@ impl LeafNode {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct BinarySearchTree {
     root: Option<Box<LeafNode>>,
     size: usize,
@@ -167,6 +168,34 @@ impl BinarySearchTree {
         }
         Ok((current_node.value.clone_ref(py), current_node.count))
     }
+
+    fn encode_nodes(py: Python, node: &Option<Box<LeafNode>>, acc: &mut Vec<PyObject>) {
+        if let Some(ref boxed_node) = node {
+            let shape = (boxed_node.value.clone_ref(py), boxed_node.count, boxed_node.left.is_some(), boxed_node.right.is_some());
+            acc.push(shape.to_object(py));
+
+            Self::encode_nodes(py, &boxed_node.left, acc);
+            Self::encode_nodes(py, &boxed_node.right, acc);
+        }
+    }
+
+    fn decode_nodes(py: Python, nodes: &mut std::slice::Iter<PyObject>) -> PyResult<Box<LeafNode>> {
+        let entry = match nodes.next() {
+            Some(entry) => entry,
+            None => return Err(invalid_state("BinarySearchTree", "truncated node list")),
+        };
+        let (value, count, has_left, has_right): (PyObject, usize, bool, bool) = entry.extract(py)?;
+
+        let mut node = Box::new(LeafNode::new(value));
+        node.count = count;
+        if has_left {
+            node.left = Some(Self::decode_nodes(py, nodes)?);
+        }
+        if has_right {
+            node.right = Some(Self::decode_nodes(py, nodes)?);
+        }
+        Ok(node)
+    }
 }
 
 #[pymethods]
@@ -394,4 +423,45 @@ impl BinarySearchTree {
         self.root = None;
         self.size = 0;
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut nodes = Vec::with_capacity(self.size);
+        Self::encode_nodes(py, &self.root, &mut nodes);
+        Ok((STATE_VERSION, self.allow_duplicates, self.size, nodes).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "BinarySearchTree", 3)?;
+        let allow_duplicates: bool = state.get_item(1)?.extract()?;
+        let size: usize = state.get_item(2)?.extract()?;
+        let nodes: Vec<PyObject> = state.get_item(3)?.extract()?;
+
+        let mut iter = nodes.iter();
+        let root = if nodes.is_empty() { None } else { Some(Self::decode_nodes(py, &mut iter)?) };
+        if iter.next().is_some() {
+            return Err(invalid_state("BinarySearchTree", "unused trailing nodes"));
+        }
+
+        self.allow_duplicates = allow_duplicates;
+        self.size = size;
+        self.root = root;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), (self.allow_duplicates,), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut new_tree = BinarySearchTree::new(self.allow_duplicates);
+        new_tree.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Py::new(py, new_tree).map(|py_obj| py_obj.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, BinarySearchTree::new(false))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
@@ -2,8 +2,9 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use pyo3::exceptions::PyValueError;
 use pyo3::{prelude::*, PyTypeInfo};
-use pyo3::types::{PyList, PyString};
+use pyo3::types::{PyList, PyString, PyTuple};
 use pyo3::PyObject;
+use crate::other::rs_pickle::{deep_copy_state, invalid_state, unpack_state, STATE_VERSION};
 
 #[derive(Clone)]
 struct TrieNode {
@@ -22,7 +23,7 @@ impl TrieNode {
     }
 }
 
-#[pyclass]
+#[pyclass(module = "_rust_snaplit")]
 pub struct Trie {
     root: TrieNode,
     words_count: usize,
@@ -69,6 +70,20 @@ impl Trie {
             }
         }
     }
+
+    fn encode_nodes(py: Python, node: &TrieNode, path: &mut String, acc: &mut Vec<PyObject>) {
+        for (key, child_node) in &node.children {
+            path.push(*key);
+            let value = match child_node.value {
+                Some(ref entry) => entry.clone_ref(py),
+                None => py.None(),
+            };
+            acc.push((path.as_str(), child_node.terminal, value).to_object(py));
+
+            Self::encode_nodes(py, child_node, path, acc);
+            path.pop();
+        }
+    }
 }
 
 #[pymethods]
@@ -325,4 +340,60 @@ impl Trie {
         self.size = 0;
         Ok(())
     }
+
+    pub fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let mut nodes = Vec::with_capacity(self.size);
+        Self::encode_nodes(py, &self.root, &mut String::new(), &mut nodes);
+        Ok((STATE_VERSION, self.words_count, self.size, nodes).to_object(py))
+    }
+
+    pub fn __setstate__(&mut self, py: Python, state: &PyAny) -> PyResult<()> {
+        let state = unpack_state(state, "Trie", 3)?;
+        let words_count: usize = state.get_item(1)?.extract()?;
+        let size: usize = state.get_item(2)?.extract()?;
+        let nodes: Vec<(String, bool, PyObject)> = state.get_item(3)?.extract()?;
+
+        let mut root = TrieNode::new(py.None());
+        for (path, terminal, value) in nodes {
+            let mut chars: Vec<char> = path.chars().collect();
+            let last = match chars.pop() {
+                Some(last) => last,
+                None => return Err(invalid_state("Trie", "empty node path")),
+            };
+
+            let mut current_node = &mut root;
+            for item in chars {
+                current_node = match current_node.children.get_mut(&item) {
+                    Some(child_node) => child_node,
+                    None => return Err(invalid_state("Trie", "node stored before its parent")),
+                };
+            }
+
+            let mut new_node = TrieNode::new(value);
+            new_node.terminal = terminal;
+            current_node.children.insert(last, Box::new(new_node));
+        }
+
+        self.root = root;
+        self.words_count = words_count;
+        self.size = size;
+        Ok(())
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        Ok((py.get_type::<Self>(), PyTuple::empty(py), self.__getstate__(py)?).to_object(py))
+    }
+
+    pub fn __copy__(&self, py: Python) -> PyResult<PyObject> {
+        let mut new_trie = Trie::new(py);
+        new_trie.__setstate__(py, self.__getstate__(py)?.as_ref(py))?;
+        Py::new(py, new_trie).map(|py_obj| py_obj.to_object(py))
+    }
+
+    pub fn __deepcopy__(slf: &PyCell<Self>, py: Python, memo: &PyAny) -> PyResult<Py<Self>> {
+        let copied = Py::new(py, Trie::new(py))?;
+        let state = deep_copy_state(py, slf, copied.to_object(py), slf.borrow().__getstate__(py)?, memo)?;
+        copied.borrow_mut(py).__setstate__(py, state.as_ref(py))?;
+        Ok(copied)
+    }
 }
\ No newline at end of file
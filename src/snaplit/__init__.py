@@ -5,7 +5,7 @@ from .linear import (
     RingBuffer, CircularBuffer, ArrayStack
 )
 from .trees import BinarySearchTree, AVLTree, Trie
-from .probability import BloomFilter, CuckooFilter, Flatlist
+from .probability import BinaryFuseFilter, BloomFilter, CountMinSketch, CountingBloomFilter, CuckooFilter, Flatlist, HyperLogLog, LSHIndex, MinHash, ScalableBloomFilter, TDigest, TopK
 from .graph import BaseGraph, Digraph, WeightedGraph, WeightedDigraph, Hypergraph
 from .hashing import SnapMap, RhoodMap
 
@@ -24,9 +24,18 @@ __all__ = [
     "Trie",
     "SnapMap",
     "RhoodMap",
+    "BinaryFuseFilter",
     "BloomFilter",
+    "CountMinSketch",
+    "CountingBloomFilter",
     "CuckooFilter",
     "Flatlist",
+    "HyperLogLog",
+    "LSHIndex",
+    "MinHash",
+    "ScalableBloomFilter",
+    "TDigest",
+    "TopK",
     "BaseGraph",
     "Digraph",
     "WeightedGraph",
@@ -2,7 +2,7 @@
 
 from _rust_snaplit import BaseGraph as _RustBaseGraph
 
-from typing import Any, List, Tuple, Optional, Union
+from typing import Any, Callable, List, Tuple, Optional, Union
 
 #---------- Base Graph Shim ----------
 
@@ -47,6 +47,18 @@ class BaseGraph():
     has_path(x: int, y: int) -> bool:
         Returns True if a path exists between the 2 specified nodes, else False.
 
+    connected_components() -> List[List[int]]:
+        Returns the node IDs of every connected component, each sorted in ascending order.
+
+    component_of(id: int) -> List[int]:
+        Returns the node IDs of the connected component holding the specified node.
+
+    bridges() -> List[Tuple[int, int]]:
+        Returns every edge whose removal would disconnect the Graph, as (lower ID, higher ID) pairs.
+
+    articulation_points() -> List[int]:
+        Returns every node whose removal would disconnect the Graph.
+
     neighbours(index: int) -> List[int]:
         Returns the identifying node keys of all neighbours.
 
@@ -67,6 +79,31 @@ class BaseGraph():
     edge_count() -> int:
         Returns the total number of edges in internal Graph structure.
 
+    to_edge_list() -> str:
+        Returns one 'from_id to_id' line per edge, plus lone IDs for isolated nodes.
+        Payloads are not included.
+
+    from_edge_list(text: str) -> BaseGraph:
+        Builds a new BaseGraph from an edge list. Node IDs are kept and payloads are set to None.
+
+    to_dot(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph in Graphviz DOT format, using encoder(payload) (default: str) as node labels.
+
+    from_dot(text: str, decoder: Optional[Callable[[str], Any]] = None) -> BaseGraph:
+        Builds a new BaseGraph from DOT text, restoring payloads through decoder(label).
+
+    to_graphml(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a GraphML document, using encoder(payload) (default: str) for payloads.
+
+    from_graphml(text: str, decoder: Optional[Callable[[str], Any]] = None) -> BaseGraph:
+        Builds a new BaseGraph from a GraphML document, restoring payloads through decoder(text).
+
+    to_json(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a JSON adjacency document, using encoder(payload) (default: str) for payloads.
+
+    from_json(text: str, decoder: Optional[Callable[[str], Any]] = None) -> BaseGraph:
+        Builds a new BaseGraph from a JSON adjacency document, restoring payloads through decoder(text).
+
     is_empty() -> bool:
         Checks if the internal Graph holds no nodes.
 
@@ -198,6 +235,84 @@ class BaseGraph():
         
         return self._inner.has_path(x, y)
     
+    def connected_components(self) -> List[List[int]]:
+        return self._inner.connected_components()
+    
+    def component_of(self, id: int) -> List[int]:
+        if not isinstance(id, int):
+            raise TypeError("ID value must be of Type: int")
+        
+        return self._inner.component_of(id)
+    
+    def bridges(self) -> List[Tuple[int, int]]:
+        return self._inner.bridges()
+    
+    def articulation_points(self) -> List[int]:
+        return self._inner.articulation_points()
+    
+    def to_edge_list(self) -> str:
+        return self._inner.to_edge_list()
+    
+    @classmethod
+    def from_edge_list(cls, text: str) -> "BaseGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        
+        result = cls()
+        result._inner = _RustBaseGraph.from_edge_list(text)
+        return result
+    
+    def to_dot(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_dot(encoder)
+    
+    @classmethod
+    def from_dot(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "BaseGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustBaseGraph.from_dot(text, decoder)
+        return result
+    
+    def to_graphml(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_graphml(encoder)
+    
+    @classmethod
+    def from_graphml(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "BaseGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustBaseGraph.from_graphml(text, decoder)
+        return result
+    
+    def to_json(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_json(encoder)
+    
+    @classmethod
+    def from_json(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "BaseGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustBaseGraph.from_json(text, decoder)
+        return result
+    
     def is_empty(self) -> bool:
         return self._inner.is_empty()
     
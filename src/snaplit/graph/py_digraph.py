@@ -2,7 +2,7 @@
 
 from _rust_snaplit import Digraph as _RustDigraph
 
-from typing import Any, List, Tuple, Optional, Union
+from typing import Any, Callable, List, Tuple, Optional, Union
 
 #---------- Digraph Shim ----------
 
@@ -50,6 +50,17 @@ class Digraph():
     has_cycle() -> bool:
         Returns True, if the internal Digraph is cyclical.
 
+    topological_sort() -> List[int]:
+        Returns all node IDs ordered so every edge points from an earlier to a later node.
+        Raises ValueError naming an offending cycle if the Digraph is cyclical.
+
+    strongly_connected_components() -> List[List[int]]:
+        Returns the strongly connected components (Tarjan) in topological order.
+
+    condensation() -> Digraph:
+        Creates and returns a new acyclic Digraph with 1 node per strongly connected component.
+        Each node payload holds the list of member node IDs.
+
     transpose() -> Digraph:
         Creates and returns a new Digraph instance with all current nodes but inversed edges. 
 
@@ -73,6 +84,31 @@ class Digraph():
     edge_count() -> int:
         Returns the total number of edges in internal Graph structure.
 
+    to_edge_list() -> str:
+        Returns one 'from_id to_id' line per edge, plus lone IDs for isolated nodes.
+        Payloads are not included.
+
+    from_edge_list(text: str) -> Digraph:
+        Builds a new Digraph from an edge list. Node IDs are kept and payloads are set to None.
+
+    to_dot(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph in Graphviz DOT format, using encoder(payload) (default: str) as node labels.
+
+    from_dot(text: str, decoder: Optional[Callable[[str], Any]] = None) -> Digraph:
+        Builds a new Digraph from DOT text, restoring payloads through decoder(label).
+
+    to_graphml(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a GraphML document, using encoder(payload) (default: str) for payloads.
+
+    from_graphml(text: str, decoder: Optional[Callable[[str], Any]] = None) -> Digraph:
+        Builds a new Digraph from a GraphML document, restoring payloads through decoder(text).
+
+    to_json(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a JSON adjacency document, using encoder(payload) (default: str) for payloads.
+
+    from_json(text: str, decoder: Optional[Callable[[str], Any]] = None) -> Digraph:
+        Builds a new Digraph from a JSON adjacency document, restoring payloads through decoder(text).
+
     is_empty() -> bool:
         Checks if the internal Graph holds no nodes.
 
@@ -207,11 +243,85 @@ class Digraph():
     def has_cycle(self) -> bool:
         return self._inner.has_cycle()
     
+    def topological_sort(self) -> List[int]:
+        return self._inner.topological_sort()
+    
+    def strongly_connected_components(self) -> List[List[int]]:
+        return self._inner.strongly_connected_components()
+    
+    def condensation(self) -> "Digraph":
+        result = Digraph()
+        result._inner = self._inner.condensation()
+        return result
+    
     def transpose(self) -> "Digraph":
         result = Digraph()
         result._inner = self._inner.transpose()
         return result
     
+    def to_edge_list(self) -> str:
+        return self._inner.to_edge_list()
+    
+    @classmethod
+    def from_edge_list(cls, text: str) -> "Digraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        
+        result = cls()
+        result._inner = _RustDigraph.from_edge_list(text)
+        return result
+    
+    def to_dot(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_dot(encoder)
+    
+    @classmethod
+    def from_dot(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "Digraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustDigraph.from_dot(text, decoder)
+        return result
+    
+    def to_graphml(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_graphml(encoder)
+    
+    @classmethod
+    def from_graphml(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "Digraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustDigraph.from_graphml(text, decoder)
+        return result
+    
+    def to_json(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_json(encoder)
+    
+    @classmethod
+    def from_json(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "Digraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustDigraph.from_json(text, decoder)
+        return result
+    
     def is_empty(self) -> bool:
         return self._inner.is_empty()
     
@@ -2,7 +2,7 @@
 
 from _rust_snaplit import HyperGraph as _RustHypergraph
 
-from typing import Any, List, Optional
+from typing import Any, Callable, List, Optional
 
 #---------- Hypergraph Shim ----------
 
@@ -87,6 +87,31 @@ class Hypergraph():
     average_degree() -> float:
         Returns the average degree number amongst all nodes.
 
+    to_edge_list() -> str:
+        Returns one 'edge_id<TAB>node IDs' line per hyper edge, followed by a lone ID line for every node
+        outside all hyper edges. Payloads are not included.
+
+    from_edge_list(text: str) -> Hypergraph:
+        Builds a new Hypergraph from an edge list. Node IDs are kept and payloads are set to None.
+
+    to_dot(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph in Graphviz DOT format, using encoder(payload) (default: str) as node labels.
+
+    from_dot(text: str, decoder: Optional[Callable[[str], Any]] = None) -> Hypergraph:
+        Builds a new Hypergraph from DOT text, restoring payloads through decoder(label).
+
+    to_graphml(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a GraphML document, using encoder(payload) (default: str) for payloads.
+
+    from_graphml(text: str, decoder: Optional[Callable[[str], Any]] = None) -> Hypergraph:
+        Builds a new Hypergraph from a GraphML document, restoring payloads through decoder(text).
+
+    to_json(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a JSON adjacency document, using encoder(payload) (default: str) for payloads.
+
+    from_json(text: str, decoder: Optional[Callable[[str], Any]] = None) -> Hypergraph:
+        Builds a new Hypergraph from a JSON adjacency document, restoring payloads through decoder(text).
+
     is_empty() -> bool:
         Returns True if the internal Graph holds no nodes.
 
@@ -221,6 +246,69 @@ class Hypergraph():
         
         return self._inner.edge_size(edge_id)
         
+    def to_edge_list(self) -> str:
+        return self._inner.to_edge_list()
+    
+    @classmethod
+    def from_edge_list(cls, text: str) -> "Hypergraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        
+        result = cls()
+        result._inner = _RustHypergraph.from_edge_list(text)
+        return result
+    
+    def to_dot(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_dot(encoder)
+    
+    @classmethod
+    def from_dot(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "Hypergraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustHypergraph.from_dot(text, decoder)
+        return result
+    
+    def to_graphml(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_graphml(encoder)
+    
+    @classmethod
+    def from_graphml(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "Hypergraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustHypergraph.from_graphml(text, decoder)
+        return result
+    
+    def to_json(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_json(encoder)
+    
+    @classmethod
+    def from_json(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "Hypergraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustHypergraph.from_json(text, decoder)
+        return result
+    
     def is_empty(self) -> bool:
         return self._inner.is_empty()
     
@@ -2,7 +2,7 @@
 
 from _rust_snaplit import WeightedDigraph as _RustWeightedDigraph
 
-from typing import Any, List, Tuple, Optional, Union
+from typing import Any, Callable, Dict, List, Tuple, Optional, Union
 
 #---------- Weighted Digraph Shim ----------
 
@@ -59,10 +59,45 @@ class WeightedDigraph():
     has_cycle() -> bool:
         Returns True, if the internal Digraph is cyclical.
 
+    topological_sort() -> List[int]:
+        Returns all node IDs ordered so every edge points from an earlier to a later node.
+        Raises ValueError naming an offending cycle if the Digraph is cyclical.
+
+    strongly_connected_components() -> List[List[int]]:
+        Returns the strongly connected components (Tarjan) in topological order.
+
+    condensation() -> WeightedDigraph:
+        Creates and returns a new acyclic WeightedDigraph with 1 node per strongly connected component.
+        Each node payload holds the list of member node IDs. Parallel edges keep the lowest weight.
+
     transpose() -> Digraph:
         Creates and returns a new Digraph instance with all current nodes but inversed edges.
         Edges retain individual weight value.
 
+    shortest_path(start_id: int, end_id: int) -> Tuple[List[int], float]:
+        Returns the cheapest path between 2 nodes (Dijkstra) and its total weight.
+        Requires non-negative, non-NaN edge weights.
+
+    shortest_distances(start_id: int) -> Dict[int, float]:
+        Returns the cheapest total weight from start node to every reachable node (Dijkstra).
+
+    astar_path(start_id: int, end_id: int, heuristic: Callable[[int, int], float]) -> Tuple[List[int], float]:
+        Returns the cheapest path between 2 nodes using A* search and its total weight.
+        Heuristic is called as heuristic(node_id, end_id) and must be consistent: h(u) <= weight(u, v) + h(v)
+        for every edge and h(end_id) == 0. Settled nodes are never reopened, so an admissible but inconsistent
+        heuristic may return a suboptimal path.
+
+    bellman_ford(start_id: int) -> Dict[int, float]:
+        Returns the cheapest total weight from start node to every reachable node (Bellman-Ford).
+        Supports negative edge weights and raises ValueError if a negative cycle is reachable.
+
+    max_flow(source_id: int, sink_id: int) -> Tuple[float, Dict[Tuple[int, int], float]]:
+        Treats edge weights as capacities and returns the maximum flow value (Edmonds-Karp),
+        together with the flow carried by every (from_id, to_id) edge.
+
+    min_cut(source_id: int, sink_id: int) -> Tuple[List[int], List[int], List[Tuple[int, int]]]:
+        Returns the source-side nodes, sink-side nodes and the (from_id, to_id) edges of a minimum cut.
+
     neighbours(index: int) -> List[int]:
         Returns the identifying node keys of all neighbours.
 
@@ -90,6 +125,31 @@ class WeightedDigraph():
         Returns the current density of the Graph structure (e / (n * (n - 1))).
         e = Number of edges, n = Number of nodes.
 
+    to_edge_list() -> str:
+        Returns one 'from_id to_id weight' line per edge, plus lone IDs for isolated nodes.
+        Payloads are not included.
+
+    from_edge_list(text: str) -> WeightedDigraph:
+        Builds a new WeightedDigraph from an edge list. Node IDs are kept and payloads are set to None.
+
+    to_dot(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph in Graphviz DOT format, using encoder(payload) (default: str) as node labels.
+
+    from_dot(text: str, decoder: Optional[Callable[[str], Any]] = None) -> WeightedDigraph:
+        Builds a new WeightedDigraph from DOT text, restoring payloads through decoder(label).
+
+    to_graphml(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a GraphML document, using encoder(payload) (default: str) for payloads.
+
+    from_graphml(text: str, decoder: Optional[Callable[[str], Any]] = None) -> WeightedDigraph:
+        Builds a new WeightedDigraph from a GraphML document, restoring payloads through decoder(text).
+
+    to_json(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a JSON adjacency document, using encoder(payload) (default: str) for payloads.
+
+    from_json(text: str, decoder: Optional[Callable[[str], Any]] = None) -> WeightedDigraph:
+        Builds a new WeightedDigraph from a JSON adjacency document, restoring payloads through decoder(text).
+
     is_empty() -> bool:
         Checks if the internal Graph holds no nodes.
 
@@ -243,14 +303,134 @@ class WeightedDigraph():
     def has_cycle(self) -> bool:
         return self._inner.has_cycle()
     
+    def topological_sort(self) -> List[int]:
+        return self._inner.topological_sort()
+    
+    def strongly_connected_components(self) -> List[List[int]]:
+        return self._inner.strongly_connected_components()
+    
+    def condensation(self) -> "WeightedDigraph":
+        result = WeightedDigraph()
+        result._inner = self._inner.condensation()
+        return result
+    
     def transpose(self) -> "WeightedDigraph":
         result = WeightedDigraph()
         result._inner = self._inner.transpose()
         return result
     
+    def shortest_path(self, start_id: int, end_id: int) -> Tuple[List[int], float]:
+        if not isinstance(start_id, int):
+            raise TypeError("Start ID must be of Type: int")
+        if not isinstance(end_id, int):
+            raise TypeError("End ID must be of Type: int")
+        
+        return self._inner.shortest_path(start_id, end_id)
+    
+    def shortest_distances(self, start_id: int) -> Dict[int, float]:
+        if not isinstance(start_id, int):
+            raise TypeError("Start ID must be of Type: int")
+        
+        return self._inner.shortest_distances(start_id)
+    
+    def astar_path(self, start_id: int, end_id: int, heuristic: Callable[[int, int], float]) -> Tuple[List[int], float]:
+        if not isinstance(start_id, int):
+            raise TypeError("Start ID must be of Type: int")
+        if not isinstance(end_id, int):
+            raise TypeError("End ID must be of Type: int")
+        if not callable(heuristic):
+            raise TypeError("Heuristic must be a callable")
+        
+        return self._inner.astar_path(start_id, end_id, heuristic)
+    
+    def bellman_ford(self, start_id: int) -> Dict[int, float]:
+        if not isinstance(start_id, int):
+            raise TypeError("Start ID must be of Type: int")
+        
+        return self._inner.bellman_ford(start_id)
+    
+    def max_flow(self, source_id: int, sink_id: int) -> Tuple[float, Dict[Tuple[int, int], float]]:
+        if not isinstance(source_id, int):
+            raise TypeError("Source ID must be of Type: int")
+        if not isinstance(sink_id, int):
+            raise TypeError("Sink ID must be of Type: int")
+        
+        return self._inner.max_flow(source_id, sink_id)
+    
+    def min_cut(self, source_id: int, sink_id: int) -> Tuple[List[int], List[int], List[Tuple[int, int]]]:
+        if not isinstance(source_id, int):
+            raise TypeError("Source ID must be of Type: int")
+        if not isinstance(sink_id, int):
+            raise TypeError("Sink ID must be of Type: int")
+        
+        return self._inner.min_cut(source_id, sink_id)
+    
     def density(self) -> float:
         return self._inner.density()
     
+    def to_edge_list(self) -> str:
+        return self._inner.to_edge_list()
+    
+    @classmethod
+    def from_edge_list(cls, text: str) -> "WeightedDigraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        
+        result = cls()
+        result._inner = _RustWeightedDigraph.from_edge_list(text)
+        return result
+    
+    def to_dot(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_dot(encoder)
+    
+    @classmethod
+    def from_dot(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "WeightedDigraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustWeightedDigraph.from_dot(text, decoder)
+        return result
+    
+    def to_graphml(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_graphml(encoder)
+    
+    @classmethod
+    def from_graphml(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "WeightedDigraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustWeightedDigraph.from_graphml(text, decoder)
+        return result
+    
+    def to_json(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_json(encoder)
+    
+    @classmethod
+    def from_json(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "WeightedDigraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustWeightedDigraph.from_json(text, decoder)
+        return result
+    
     def is_empty(self) -> bool:
         return self._inner.is_empty()
     
@@ -2,7 +2,7 @@
 
 from _rust_snaplit import WeightedGraph as _RustWeightedGraph
 
-from typing import Any, List, Tuple, Optional, Union
+from typing import Any, Callable, Dict, List, Tuple, Optional, Union
 
 #---------- Weighted Graph Shim ----------
 
@@ -56,6 +56,23 @@ class WeightedGraph():
     has_path(x: int, y: int) -> bool:
         Returns True if a path exists between the 2 specified nodes, else False.
 
+    shortest_path(start_id: int, end_id: int) -> Tuple[List[int], float]:
+        Returns the cheapest path between 2 nodes (Dijkstra) and its total weight.
+        Requires non-negative, non-NaN edge weights.
+
+    shortest_distances(start_id: int) -> Dict[int, float]:
+        Returns the cheapest total weight from start node to every reachable node (Dijkstra).
+
+    astar_path(start_id: int, end_id: int, heuristic: Callable[[int, int], float]) -> Tuple[List[int], float]:
+        Returns the cheapest path between 2 nodes using A* search and its total weight.
+        Heuristic is called as heuristic(node_id, end_id) and must be consistent: h(u) <= weight(u, v) + h(v)
+        for every edge and h(end_id) == 0. Settled nodes are never reopened, so an admissible but inconsistent
+        heuristic may return a suboptimal path.
+
+    minimum_spanning_tree() -> WeightedGraph:
+        Creates and returns a new WeightedGraph holding the minimum spanning tree (Kruskal).
+        Nodes retain their ID and payload. Disconnected Graphs produce a minimum spanning forest.
+
     neighbours(index: int) -> List[int]:
         Returns the identifying node keys of all neighbours.
 
@@ -83,6 +100,31 @@ class WeightedGraph():
         Returns the current density of the Graph structure (2e / (n * (n - 1))).
         e = Number of edges, n = Number of nodes.
 
+    to_edge_list() -> str:
+        Returns one 'from_id to_id weight' line per edge, plus lone IDs for isolated nodes.
+        Payloads are not included.
+
+    from_edge_list(text: str) -> WeightedGraph:
+        Builds a new WeightedGraph from an edge list. Node IDs are kept and payloads are set to None.
+
+    to_dot(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph in Graphviz DOT format, using encoder(payload) (default: str) as node labels.
+
+    from_dot(text: str, decoder: Optional[Callable[[str], Any]] = None) -> WeightedGraph:
+        Builds a new WeightedGraph from DOT text, restoring payloads through decoder(label).
+
+    to_graphml(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a GraphML document, using encoder(payload) (default: str) for payloads.
+
+    from_graphml(text: str, decoder: Optional[Callable[[str], Any]] = None) -> WeightedGraph:
+        Builds a new WeightedGraph from a GraphML document, restoring payloads through decoder(text).
+
+    to_json(encoder: Optional[Callable[[Any], str]] = None) -> str:
+        Exports the Graph as a JSON adjacency document, using encoder(payload) (default: str) for payloads.
+
+    from_json(text: str, decoder: Optional[Callable[[str], Any]] = None) -> WeightedGraph:
+        Builds a new WeightedGraph from a JSON adjacency document, restoring payloads through decoder(text).
+
     is_empty() -> bool:
         Checks if the internal Graph holds no nodes.
 
@@ -233,9 +275,101 @@ class WeightedGraph():
         
         return self._inner.has_path(x, y)
     
+    def shortest_path(self, start_id: int, end_id: int) -> Tuple[List[int], float]:
+        if not isinstance(start_id, int):
+            raise TypeError("Start ID must be of Type: int")
+        if not isinstance(end_id, int):
+            raise TypeError("End ID must be of Type: int")
+        
+        return self._inner.shortest_path(start_id, end_id)
+    
+    def shortest_distances(self, start_id: int) -> Dict[int, float]:
+        if not isinstance(start_id, int):
+            raise TypeError("Start ID must be of Type: int")
+        
+        return self._inner.shortest_distances(start_id)
+    
+    def astar_path(self, start_id: int, end_id: int, heuristic: Callable[[int, int], float]) -> Tuple[List[int], float]:
+        if not isinstance(start_id, int):
+            raise TypeError("Start ID must be of Type: int")
+        if not isinstance(end_id, int):
+            raise TypeError("End ID must be of Type: int")
+        if not callable(heuristic):
+            raise TypeError("Heuristic must be a callable")
+        
+        return self._inner.astar_path(start_id, end_id, heuristic)
+    
+    def minimum_spanning_tree(self) -> "WeightedGraph":
+        result = WeightedGraph()
+        result._inner = self._inner.minimum_spanning_tree()
+        return result
+    
     def density(self) -> float:
         return self._inner.density()
     
+    def to_edge_list(self) -> str:
+        return self._inner.to_edge_list()
+    
+    @classmethod
+    def from_edge_list(cls, text: str) -> "WeightedGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        
+        result = cls()
+        result._inner = _RustWeightedGraph.from_edge_list(text)
+        return result
+    
+    def to_dot(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_dot(encoder)
+    
+    @classmethod
+    def from_dot(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "WeightedGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustWeightedGraph.from_dot(text, decoder)
+        return result
+    
+    def to_graphml(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_graphml(encoder)
+    
+    @classmethod
+    def from_graphml(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "WeightedGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustWeightedGraph.from_graphml(text, decoder)
+        return result
+    
+    def to_json(self, encoder: Optional[Callable[[Any], str]] = None) -> str:
+        if encoder is not None and not callable(encoder):
+            raise TypeError("Encoder must be a callable")
+        
+        return self._inner.to_json(encoder)
+    
+    @classmethod
+    def from_json(cls, text: str, decoder: Optional[Callable[[str], Any]] = None) -> "WeightedGraph":
+        if not isinstance(text, str):
+            raise TypeError("Text must be of Type: str")
+        if decoder is not None and not callable(decoder):
+            raise TypeError("Decoder must be a callable")
+        
+        result = cls()
+        result._inner = _RustWeightedGraph.from_json(text, decoder)
+        return result
+    
     def is_empty(self) -> bool:
         return self._inner.is_empty()
     
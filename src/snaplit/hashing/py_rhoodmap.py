@@ -1,8 +1,10 @@
 #---------- Imports ----------
 
+import copy
+from collections.abc import MutableMapping
 from _rust_snaplit import RhoodMap as _RustRhoodMap
 
-from typing import Any, Optional, List, Iterable, Iterator, Dict
+from typing import Any, Optional, List, Iterable, Iterator, Dict, Tuple, KeysView, ValuesView, ItemsView
 
 #---------- RhoodMap (Robin Hood Hashing Map) Shim ----------
 
@@ -22,20 +24,48 @@ class RhoodMap():
     capacity: Optional[int] = 1024
         The initial number of key-value slots allocated in memory. Defaults to '1024'.
         Larger capacities reduce internal entry collisions, but increase memory usage.
+        The map grows automatically when full and never shrinks below this initial capacity.
+
+    max_load: Optional[float] = 0.9
+        The load factor (entries / capacity) above which an insertion first doubles the capacity. Must be within (0.0, 1.0].
+
+    min_load: Optional[float] = 0.1
+        The load factor below which a deletion halves the capacity again. Must be below half of 'max_load' - '0.0' disables shrinking.
+
+    hasher: Optional[str] = 'siphash'
+        The hash family used to place keys. 'fx' is the fastest, 'siphash' draws a random key per map to resist
+        HashDoS attacks on untrusted keys, and 'seeded' gives the same layout in every process for a given seed.
+
+    seed: Optional[int] = None
+        The seed of the 'seeded' hasher (passing only a seed selects it). Defaults to '0'.
 
     ----- Methods -----
 
     insert(key: Any, value: Any) -> bool:
-        Inserts a key-value pair into internal hashmap. Returns 'True' is insertion is successfull.
+        Inserts a key-value pair into internal hashmap. Returns 'True' if the key was newly added.
+        An existing key has its value replaced (like 'map[key] = value') and returns 'False'.
 
     remove(key: Any) -> Optional[Any]:
         Removes and returns the key-value pair from internal hashmap. Returns 'None' is the value is not found.
 
-    get(key: Any) -> Any:
-        Retrieves the value associated with the specified key without mutating the internal hashmap.
+    get(key: Any, default: Any = None) -> Any:
+        Retrieves the value associated with the specified key, or 'default' if the key is not found.
 
     update(key: Any, new_value: Any) -> bool:
         Updates the value associated with the specified key. Returns 'True' is update is successfull.
+        Only the two-positional form is recognised - keyword arguments are always treated like 'dict.update()'.
+
+    update(other: Mapping | Iterable[Tuple[Any, Any]] = (), **kwargs) -> None:
+        Inserts or replaces every key-value pair of a mapping or iterable of pairs, like 'dict.update()'.
+
+    setdefault(key: Any, default: Any = None) -> Any:
+        Returns the value of the specified key, inserting 'default' first if the key is not found.
+
+    pop(key: Any, default: Any = <missing>) -> Any:
+        Removes and returns the value of the specified key. Returns 'default' or raises KeyError if not found.
+
+    popitem() -> Tuple[Any, Any]:
+        Removes and returns an arbitrary (key, value) pair. Raises KeyError if the map is empty.
 
     contains(key: Any) -> bool:
         Returns 'True' if the key is present in the internal hashmap.
@@ -43,21 +73,23 @@ class RhoodMap():
     from_keys(iterable: Iterable[Any]) -> List[Any]:
         Returns a list of values corresponding to the list of provided keys.
 
-    keys() -> List[Any]:
-        Returns all keys currently stored in the internal hashmap.
+    keys() -> KeysView[Any]:
+        Returns a live, set-like view of the keys stored in the internal hashmap (supports len, in, &, |, - and ^).
 
-    values() -> List[Any]:
-        Returns all values currently stored in the internal hashmap.
+    values() -> ValuesView[Any]:
+        Returns a live view of the values stored in the internal hashmap.
 
-    items() -> List[Any]:
-        Returns all items (keys + values) currently stored in the internal hashmap.
+    items() -> ItemsView[Any, Any]:
+        Returns a live, set-like view of the (key, value) pairs stored in the internal hashmap.
+        Views never copy the map - Adding or removing keys while iterating over one raises RuntimeError.
 
     copy() -> RhoodMap:
         Creates a deep copy of internal RhoodMap, preserving internal capacity and bucket_size.
         Returns a fully independent clone of original RhoodMap.
 
     info() -> Dict[str, Any]:
-        Returns internal statistics regarding the current RhoodMap.
+        Returns internal statistics regarding the current RhoodMap, including load factors, tombstones, probe lengths,
+        hash scheme and seed.
 
     capacity() -> int:
         Returns the total capacity of the internal hashmap
@@ -68,11 +100,20 @@ class RhoodMap():
     percentage() -> float:
         Returns the current load factor (the percentage of used capacity)
 
+    reserve(additional: int) -> None:
+        Pre-sizes the internal hashmap so 'additional' more entries fit without any further resizing.
+
+    shrink_to_fit() -> None:
+        Shrinks the internal hashmap to the smallest capacity holding all current entries within 'max_load'.
+
+    compact() -> int:
+        Rebuilds the internal hashmap in place. Returns the number of tombstones purged (always '0', deletions shift entries back).
+
     is_empty() -> bool:
         Returns 'True' is the internal hashmap contains no elements.
 
     clear() -> None:
-        Removes all entries from internal hashmap and resets internal metrics.
+        Removes all entries from internal hashmap, resets internal metrics and returns to the initial capacity.
 
     __len__() -> int:
         Enables the use of Python's native 'len()' to return current map size.
@@ -84,20 +125,29 @@ class RhoodMap():
         Enables the use of Python's native 'x in map' to return 'True' if the map contains specified value.
 
     __getitem__(key: Any) -> Any:
-        Enables the use of Python's native 'map[key]' to return the specified value.
+        Enables the use of Python's native 'map[key]' to return the specified value. Raises KeyError if not found.
 
     __setitem__(key: Any, value: Any) -> None:
         Enables the use of Python's native 'map[key] = value' to update the internal value associated with key.
 
     __delitem__(key: Any) -> None:
-        Enables the use of Python's native 'del map[key]' to delete internal key-value pair.
+        Enables the use of Python's native 'del map[key]' to delete internal key-value pair. Raises KeyError if not found.
 
     __iter__() -> Iterator:
         Enables the use of Python's native 'for x in map' to iterate over map's internal keys.
 
+    __eq__(other: Mapping) -> bool:
+        Enables the use of Python's native '==' to compare the map with a dict or any other mapping.
+
     __copy__() -> RhoodMap:
         Enables the use of Python's native 'copy()' to create a new instance of map object.
 
+    __deepcopy__(memo: dict) -> RhoodMap:
+        Enables the use of Python's internal 'copy.deepcopy()' functionality.
+        Returns a new instance holding recursive copies of all stored values.
+
+    RhoodMap is registered as a 'collections.abc.MutableMapping', so it can be passed wherever a dict is expected.
+
     ----- Example -----
 
     >>> map = RhoodMap(capacity=1024)
@@ -109,21 +159,22 @@ class RhoodMap():
     True
     >>> print(map.get(2))
     "Ivysaur"
-    >>> map.update(key=3, new_value="Squirtle")
+    >>> map.update(3, "Squirtle")
 
-    >>> print(map.keys())
+    >>> print(list(map.keys()))
     [1, 2, 3]
-    >>> print(map.values())
+    >>> print(list(map.values()))
     ["Bulbasaur", "Ivysaur", "Squirtle"]
     """
 
-    def __init__(self, capacity: Optional[int] = 1024):
+    def __init__(self, capacity: Optional[int] = 1024, max_load: Optional[float] = None, min_load: Optional[float] = None,
+                 hasher: Optional[str] = None, seed: Optional[int] = None):
         if not isinstance(capacity, int):
             return TypeError(f"Capacity must be of Type: int - Current type {type(capacity)}")
         if capacity <= 0:
             return ValueError("Capacity must be represented by a positive integer")
         
-        self._inner = _RustRhoodMap(capacity)
+        self._inner = _RustRhoodMap(capacity, max_load, min_load, hasher, seed)
 
     def insert(self, key: Any, value: Any) -> bool:
         return self._inner.insert(key, value)
@@ -131,11 +182,20 @@ class RhoodMap():
     def remove(self, key: Any) -> Any:
         return self._inner.remove(key)
     
-    def get(self, key: Any) -> Optional[Any]:
-        return self._inner.get(key)
+    def get(self, key: Any, default: Any = None) -> Any:
+        return self._inner.get(key, default)
     
-    def update(self, key: Any, new_value: Any) -> bool:
-        return self._inner.update(key, new_value)
+    def update(self, *args: Any, **kwargs: Any) -> Optional[bool]:
+        return self._inner.update(*args, **kwargs)
+
+    def setdefault(self, key: Any, default: Any = None) -> Any:
+        return self._inner.setdefault(key, default)
+
+    def pop(self, key: Any, *default: Any) -> Any:
+        return self._inner.pop(key, *default)
+
+    def popitem(self) -> Tuple[Any, Any]:
+        return self._inner.popitem()
     
     def contains(self, key: Any) -> bool:
         return self._inner.contains(key)
@@ -143,13 +203,13 @@ class RhoodMap():
     def from_keys(self, iterable: Iterable[Any]) -> List[Any]:
         return self._inner.from_keys(iterable)
     
-    def keys(self) -> List[Any]:
+    def keys(self) -> KeysView[Any]:
         return self._inner.keys()
     
-    def values(self) -> List[Any]:
+    def values(self) -> ValuesView[Any]:
         return self._inner.values()
     
-    def items(self) -> List[Any]:
+    def items(self) -> ItemsView[Any, Any]:
         return self._inner.items()
     
     def copy(self) -> "RhoodMap":
@@ -169,6 +229,15 @@ class RhoodMap():
     def percentage(self) -> float:
         return self._inner.precentage()
     
+    def reserve(self, additional: int) -> None:
+        self._inner.reserve(additional)
+
+    def shrink_to_fit(self) -> None:
+        self._inner.shrink_to_fit()
+
+    def compact(self) -> int:
+        return self._inner.compact()
+
     def is_empty(self) -> bool:
         return self._inner.is_empty()
     
@@ -185,26 +254,33 @@ class RhoodMap():
         return self._inner.contains(key)
     
     def __getitem__(self, key: Any) -> Any:
-        gotten_item = self._inner.get(key)
-        if gotten_item is None:
-            raise ValueError(f"Key {key} not found in RhoodMap")
-        else:
-            return gotten_item
-        
+        return self._inner[key]
+
     def __setitem__(self, key: Any, value: Any) -> None:
-        self._inner.insert(key, value)
+        self._inner[key] = value
 
     def __delitem__(self, key: Any) -> None:
-        deleted_value = self._inner.remove(key)
-        if deleted_value is None:
-            raise ValueError(f"Key {key} not found in RhoodMap")
-        else:
-            return deleted_value
-        
+        del self._inner[key]
+
     def __iter__(self) -> Iterator[Any]:
-        return iter(self._inner.keys())
-    
+        return iter(self._inner)
+
+    def __eq__(self, other: Any) -> bool:
+        if isinstance(other, RhoodMap):
+            other = other._inner
+        return self._inner == other
+
     def __copy__(self) -> "RhoodMap":
         new_map = RhoodMap(capacity=self.capacity())
         new_map._inner = self._inner.copy()
-        return new_map
\ No newline at end of file
+        return new_map
+
+    def __deepcopy__(self, memo: dict) -> "RhoodMap":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance
+
+#---------- Mapping Registration ----------
+
+MutableMapping.register(RhoodMap)
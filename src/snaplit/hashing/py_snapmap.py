@@ -1,8 +1,10 @@
 #---------- Imports ----------
 
+import copy
+from collections.abc import MutableMapping
 from _rust_snaplit import SnapMap as _RustSnapMap
 
-from typing import Any, Optional, List, Iterable, Iterator
+from typing import Any, Optional, List, Iterable, Iterator, Tuple, KeysView, ValuesView, ItemsView
 
 #---------- SnapMap (Cuckoo Hashing Map) Shim ----------
 
@@ -25,19 +27,40 @@ class SnapMap():
         The number of entries per internal hash buckets. Defaults to '4'.
         Larger buckets sizes reduce hashing frequency, but increase lookup time.
 
+    hasher: Optional[str] = 'siphash'
+        The hash family used to place keys. 'fx' is the fastest, 'siphash' draws a random key per map to resist
+        HashDoS attacks on untrusted keys, and 'seeded' gives the same layout in every process for a given seed.
+
+    seed: Optional[int] = None
+        The seed of the 'seeded' hasher (passing only a seed selects it). Defaults to '0'.
+
     ----- Methods -----
 
     insert(key: Any, value: Any) -> bool:
-        Inserts a key-value pair into internal hashmap. Returns 'True' is insertion is successfull.
+        Inserts a key-value pair into internal hashmap. Returns 'True' if the key was newly added.
+        An existing key has its value replaced (like 'map[key] = value') and returns 'False'.
 
     remove(key: Any) -> Optional[Any]:
         Removes and returns the key-value pair from internal hashmap. Returns 'None' is the value is not found.
 
-    get(key: Any) -> Any:
-        Retrieves the value associated with the specified key without mutating the internal hashmap.
+    get(key: Any, default: Any = None) -> Any:
+        Retrieves the value associated with the specified key, or 'default' if the key is not found.
 
     update(key: Any, new_value: Any) -> bool:
         Updates the value associated with the specified key. Returns 'True' is update is successfull.
+        Only the two-positional form is recognised - keyword arguments are always treated like 'dict.update()'.
+
+    update(other: Mapping | Iterable[Tuple[Any, Any]] = (), **kwargs) -> None:
+        Inserts or replaces every key-value pair of a mapping or iterable of pairs, like 'dict.update()'.
+
+    setdefault(key: Any, default: Any = None) -> Any:
+        Returns the value of the specified key, inserting 'default' first if the key is not found.
+
+    pop(key: Any, default: Any = <missing>) -> Any:
+        Removes and returns the value of the specified key. Returns 'default' or raises KeyError if not found.
+
+    popitem() -> Tuple[Any, Any]:
+        Removes and returns an arbitrary (key, value) pair. Raises KeyError if the map is empty.
 
     contains(key: Any) -> bool:
         Returns 'True' if the key is present in the internal hashmap.
@@ -45,21 +68,22 @@ class SnapMap():
     from_keys(iterable: Iterable[Any]) -> List[Any]:
         Returns a list of values corresponding to the list of provided keys.
 
-    keys() -> List[Any]:
-        Returns all keys currently stored in the internal hashmap.
+    keys() -> KeysView[Any]:
+        Returns a live, set-like view of the keys stored in the internal hashmap (supports len, in, &, |, - and ^).
 
-    values() -> List[Any]:
-        Returns all values currently stored in the internal hashmap.
+    values() -> ValuesView[Any]:
+        Returns a live view of the values stored in the internal hashmap.
 
-    items() -> List[Any]:
-        Returns all items (keys + values) currently stored in the internal hashmap.
+    items() -> ItemsView[Any, Any]:
+        Returns a live, set-like view of the (key, value) pairs stored in the internal hashmap.
+        Views never copy the map - Adding or removing keys while iterating over one raises RuntimeError.
 
     copy() -> SnapMap:
         Creates a deep copy of internal SnapMap, preserving internal capacity and bucket_size.
         Returns a fully independent clone of original SnapMap.
 
     info() -> Dict[str, Any]:
-        Returns internal statistics regarding the current SnapMap.
+        Returns internal statistics regarding the current SnapMap, including the hash scheme and seed.
 
     capacity() -> int:
         Returns the total capacity of the internal hashmap
@@ -86,20 +110,29 @@ class SnapMap():
         Enables the use of Python's native 'x in map' to return 'True' if the map contains specified value.
 
     __getitem__(key: Any) -> Any:
-        Enables the use of Python's native 'map[key]' to return the specified value.
+        Enables the use of Python's native 'map[key]' to return the specified value. Raises KeyError if not found.
 
     __setitem__(key: Any, value: Any) -> None:
         Enables the use of Python's native 'map[key] = value' to update the internal value associated with key.
 
     __delitem__(key: Any) -> None:
-        Enables the use of Python's native 'del map[key]' to delete internal key-value pair.
+        Enables the use of Python's native 'del map[key]' to delete internal key-value pair. Raises KeyError if not found.
 
     __iter__() -> Iterator:
         Enables the use of Python's native 'for x in map' to iterate over map's internal keys.
 
+    __eq__(other: Mapping) -> bool:
+        Enables the use of Python's native '==' to compare the map with a dict or any other mapping.
+
     __copy__() -> SnapMap:
         Enables the use of Python's native 'copy()' to create a new instance of map object.
 
+    __deepcopy__(memo: dict) -> SnapMap:
+        Enables the use of Python's internal 'copy.deepcopy()' functionality.
+        Returns a new instance holding recursive copies of all stored values.
+
+    SnapMap is registered as a 'collections.abc.MutableMapping', so it can be passed wherever a dict is expected.
+
     ----- Example -----
 
     >>> map = SnapMap(capacity=1024, bucket_size=4)
@@ -111,20 +144,22 @@ class SnapMap():
     True
     >>> print(map.get(2))
     "Ivysaur"
-    >>> map.update(key=3, new_value="Squirtle")
+    >>> map.update(3, "Squirtle")
 
-    >>> print(map.keys())
+    >>> print(list(map.keys()))
     [1, 2, 3]
-    >>> print(map.values())
+    >>> print(list(map.values()))
     ["Bulbasaur", "Ivysaur", "Squirtle"]
     """
 
-    def __init__(self, capacity: Optional[int] = 1024, bucket_size: Optional[int] = 4):
+    def __init__(self, capacity: Optional[int] = 1024, bucket_size: Optional[int] = 4, hasher: Optional[str] = None, seed: Optional[int] = None):
         if not isinstance(capacity, int):
             raise TypeError(f"Capacity must be of Type: int - current type {type(capacity)}")
         if not isinstance(bucket_size, int):
             raise TypeError(f"Bucket size must be of Type: int - current type {type(bucket_size)}")
-        self._inner = _RustSnapMap(capacity, bucket_size)
+        if hasher is not None and not isinstance(hasher, str):
+            raise TypeError(f"Hasher must be of Type: str - current type {type(hasher)}")
+        self._inner = _RustSnapMap(capacity, bucket_size, hasher, seed)
 
     def insert(self, key: Any, value: Any) -> bool:
         return self._inner.insert(key, value)
@@ -132,11 +167,20 @@ class SnapMap():
     def remove(self, key: Any) -> Optional[Any]:
         return self._inner.remove(key)
     
-    def get(self, key: Any) -> Any:
-        return self._inner.get(key)
+    def get(self, key: Any, default: Any = None) -> Any:
+        return self._inner.get(key, default)
     
-    def update(self, key: Any, new_value: Any) -> bool:
-        return self._inner.update(key, new_value)
+    def update(self, *args: Any, **kwargs: Any) -> Optional[bool]:
+        return self._inner.update(*args, **kwargs)
+
+    def setdefault(self, key: Any, default: Any = None) -> Any:
+        return self._inner.setdefault(key, default)
+
+    def pop(self, key: Any, *default: Any) -> Any:
+        return self._inner.pop(key, *default)
+
+    def popitem(self) -> Tuple[Any, Any]:
+        return self._inner.popitem()
     
     def contains(self, key: Any) -> bool:
         return self._inner.contains(key)
@@ -144,13 +188,13 @@ class SnapMap():
     def from_keys(self, iterable: Iterable[Any]) -> List[Any]:
         return self._inner.from_keys(iterable)
     
-    def keys(self) -> List[Any]:
+    def keys(self) -> KeysView[Any]:
         return self._inner.keys()
     
-    def values(self) -> List[Any]:
+    def values(self) -> ValuesView[Any]:
         return self._inner.values()
     
-    def items(self) -> List[Any]:
+    def items(self) -> ItemsView[Any, Any]:
         return self._inner.items()
     
     def copy(self) -> "SnapMap":
@@ -186,26 +230,33 @@ class SnapMap():
         return self._inner.contains(key)
     
     def __getitem__(self, key: Any) -> Any:
-        gotten_value = self._inner.get(key)
-        if gotten_value is None:
-            raise ValueError(f"Key {key} not found in SnapMap")
-        else:
-            return gotten_value
-        
+        return self._inner[key]
+
     def __setitem__(self, key: Any, value: Any) -> None:
-        self._inner.insert(key, value)
+        self._inner[key] = value
 
     def __delitem__(self, key: Any) -> None:
-        deleted_value = self._inner.remove(key)
-        if deleted_value is None:
-            raise ValueError(f"Key {key} not found in SnapMap")
-        else:
-            return deleted_value
-        
-    def __iter__(self) -> Iterator:
-        return iter(self._inner.keys())
-    
+        del self._inner[key]
+
+    def __iter__(self) -> Iterator[Any]:
+        return iter(self._inner)
+
+    def __eq__(self, other: Any) -> bool:
+        if isinstance(other, SnapMap):
+            other = other._inner
+        return self._inner == other
+
     def __copy__(self) -> "SnapMap":
         new_map = SnapMap(self.capacity(), self.bucket_size())
         new_map._inner = self._inner.copy()
         return new_map
+
+    def __deepcopy__(self, memo: dict) -> "SnapMap":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance
+
+#---------- Mapping Registration ----------
+
+MutableMapping.register(SnapMap)
@@ -1,5 +1,6 @@
 #---------- Imports ----------
 
+import copy
 from _rust_snaplit import ArrayStack as _RustArrayStack
 
 from typing import Any, List, Iterable, Iterator
@@ -88,6 +89,10 @@ class ArrayStack():
         Enables the use of Python's internal 'copy()' functionality.
         Returns a new instance of the current stack.
 
+    __deepcopy__(memo: dict) -> ArrayStack:
+        Enables the use of Python's internal 'copy.deepcopy()' functionality.
+        Returns a new instance holding recursive copies of all stored values.
+
     ----- Example -----
 
     >>> stack = ArrayStack()
@@ -171,6 +176,9 @@ class ArrayStack():
     
     def __copy__(self) -> "ArrayStack":
         return self._inner.copy()
-    
 
-    
+    def __deepcopy__(self, memo: dict) -> "ArrayStack":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance
@@ -1,5 +1,6 @@
 #---------- Imports ----------
 
+import copy
 from _rust_snaplit import CircularBuffer as _RustCircularBuffer
 
 from typing import Any, Optional, List, Iterable
@@ -168,4 +169,10 @@ class CircularBuffer():
         return self._inner.__contains__(value)
 
     def __copy__(self) -> "CircularBuffer":
-        return self._inner.copy()
\ No newline at end of file
+        return self._inner.copy()
+
+    def __deepcopy__(self, memo: dict) -> "CircularBuffer":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance
@@ -1,5 +1,6 @@
 #---------- Imports ----------
 
+import copy
 from _rust_snaplit import PriorityQueue as _RustPriorityQueue
 
 from typing import Any, List, Union, Tuple, Iterable
@@ -86,6 +87,10 @@ class PriorityQueue():
     __copy__() -> PriorityQueue:
         Enables use of Python's '.copy()' functionality.
 
+    __deepcopy__(memo: dict) -> PriorityQueue:
+        Enables the use of Python's internal 'copy.deepcopy()' functionality.
+        Returns a new instance holding recursive copies of all stored values.
+
     ----- Example -----
 
     >>> queue = PriorityQueue(heap_type = 'min')
@@ -162,4 +167,8 @@ class PriorityQueue():
     def __copy__(self) -> "PriorityQueue":
         return self._inner.copy()
 
-    
\ No newline at end of file
+    def __deepcopy__(self, memo: dict) -> "PriorityQueue":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance
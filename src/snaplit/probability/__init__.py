@@ -1,15 +1,33 @@
 #---------- Imports ----------
 
+from .py_binary_fuse_filter import BinaryFuseFilter
 from .py_bloom_filter import BloomFilter
+from .py_count_min_sketch import CountMinSketch
+from .py_counting_bloom_filter import CountingBloomFilter
 from .py_cuckoo_filter import CuckooFilter
 from .py_flatlist import Flatlist
+from .py_hyperloglog import HyperLogLog
+from .py_lsh_index import LSHIndex
+from .py_minhash import MinHash
+from .py_scalable_bloom_filter import ScalableBloomFilter
+from .py_tdigest import TDigest
+from .py_top_k import TopK
 
 #---------- Package Management ----------
 
 __all__ = [
+    "BinaryFuseFilter",
     "BloomFilter",
+    "CountMinSketch",
+    "CountingBloomFilter",
     "CuckooFilter",
     "Flatlist",
+    "HyperLogLog",
+    "LSHIndex",
+    "MinHash",
+    "ScalableBloomFilter",
+    "TDigest",
+    "TopK",
 ]
 __version__ = "0.1.1"
 __author__ = "HysingerDev"
\ No newline at end of file
@@ -2,7 +2,7 @@
 
 from _rust_snaplit import BloomFilter as _RustBloomFilter
 
-from typing import Any
+from typing import Any, Optional
 
 #---------- Bloom Filter Shim ----------
 
@@ -26,6 +26,12 @@ class BloomFilter():
         Desired false-positive probability for the filter.
         Must be a float betwee 0.00 - 1.00.
 
+    seed: Optional[int] = None
+        Enables deterministic hashing when set - items are hashed with xxh3 over a canonical byte encoding
+        using this seed, so the filter answers identically across processes and machines.
+        Only str, bytes and int items are supported in this mode. When None, Python's hash() is used,
+        which is randomised per process for str and bytes.
+
     ----- Methods -----
 
     add(item: Any) -> None:
@@ -38,6 +44,45 @@ class BloomFilter():
     clear() -> None:
         Resets the internal Bloom Filter, removing all stored values.
 
+    hash_scheme() -> str:
+        Returns the hash scheme recorded in the filter - 'python' or 'xxh3'.
+
+    seed() -> Optional[int]:
+        Returns the seed of the deterministic hash scheme, or None when Python's hash() is used.
+
+    union(other: BloomFilter) -> BloomFilter:
+        Returns a new filter containing every element of both filters (bitwise OR).
+        Both filters must share the same size, hash count and hash scheme.
+
+    intersection(other: BloomFilter) -> BloomFilter:
+        Returns a new filter approximating the shared elements of both filters (bitwise AND).
+        Both filters must share the same size, hash count and hash scheme.
+
+    approximate_len() -> float:
+        Estimates the number of distinct elements added, using the Swamidass-Baldi estimator.
+
+    current_false_positive_rate() -> float:
+        Returns the false-positive probability implied by the current fill ratio of the filter.
+
+    to_bytes() -> bytes:
+        Serialises the filter into a compact, versioned binary blob.
+        The header records the expected size, probability, bit count, hash count and hash scheme.
+
+    from_bytes(data: bytes) -> BloomFilter:
+        Class method - Rebuilds a filter from a blob produced by 'to_bytes()'.
+
+    save(path: str) -> None:
+        Writes the serialised filter to the specified file path.
+
+    load(path: str) -> BloomFilter:
+        Class method - Reads a filter previously written with 'save()'.
+
+    __or__(other: BloomFilter) -> BloomFilter:
+        Enables 'filter_a | filter_b' as a shorthand for 'union()'. Supports in-place '|='.
+
+    __and__(other: BloomFilter) -> BloomFilter:
+        Enables 'filter_a & filter_b' as a shorthand for 'intersection()'. Supports in-place '&='.
+
     ----- Example -----
 
     >>> test_filter = BloomFilter(size=10000, probability=0.1)
@@ -48,9 +93,13 @@ class BloomFilter():
     True
     >>> print(test_filter.contains("Chesnaught"))
     False
+
+    >>> restored = BloomFilter.from_bytes(test_filter.to_bytes())
+    >>> print(restored.contains("Greninja"))
+    True
     """
 
-    def __init__(self, size: int, probability: float):
+    def __init__(self, size: int, probability: float, seed: Optional[int] = None):
         if not isinstance(size, int):
             raise TypeError("Size must be of Type: int")
         if not isinstance(size, int):
@@ -59,8 +108,12 @@ class BloomFilter():
             raise ValueError("Size must be represented by a positive integer")
         if not (0.00 < probability <= 1.00):
             raise ValueError("Probability must be betweenn 0.00 - 1.00")
+        if seed is not None and not isinstance(seed, int):
+            raise TypeError("Seed must be of Type: int")
+        if seed is not None and not (0 <= seed < 2**64):
+            raise ValueError("Seed must be between 0 and 2**64 - 1")
         
-        self._inner = _RustBloomFilter(size=size, probability=probability)
+        self._inner = _RustBloomFilter(size=size, probability=probability, seed=seed)
 
     def add(self, item: Any) -> None:
         self._inner.add(item)
@@ -71,4 +124,73 @@ class BloomFilter():
     def clear(self) -> None:
         self._inner.clear()
 
-    
\ No newline at end of file
+    def hash_scheme(self) -> str:
+        return self._inner.hash_scheme()
+
+    def seed(self) -> Optional[int]:
+        return self._inner.seed()
+
+    def union(self, other: "BloomFilter") -> "BloomFilter":
+        return self._wrap(self._inner.union(self._unwrap(other)))
+
+    def intersection(self, other: "BloomFilter") -> "BloomFilter":
+        return self._wrap(self._inner.intersection(self._unwrap(other)))
+
+    def approximate_len(self) -> float:
+        return self._inner.approximate_len()
+
+    def current_false_positive_rate(self) -> float:
+        return self._inner.current_false_positive_rate()
+
+    def to_bytes(self) -> bytes:
+        return self._inner.to_bytes()
+
+    @classmethod
+    def from_bytes(cls, data: bytes) -> "BloomFilter":
+        if not isinstance(data, (bytes, bytearray)):
+            raise TypeError("Data must be of Type: bytes")
+        new_filter = cls.__new__(cls)
+        new_filter._inner = _RustBloomFilter.from_bytes(bytes(data))
+        return new_filter
+
+    def save(self, path: str) -> None:
+        self._inner.save(str(path))
+
+    @classmethod
+    def load(cls, path: str) -> "BloomFilter":
+        new_filter = cls.__new__(cls)
+        new_filter._inner = _RustBloomFilter.load(str(path))
+        return new_filter
+
+    def __or__(self, other: "BloomFilter") -> "BloomFilter":
+        if not isinstance(other, BloomFilter):
+            return NotImplemented
+        return self.union(other)
+
+    def __and__(self, other: "BloomFilter") -> "BloomFilter":
+        if not isinstance(other, BloomFilter):
+            return NotImplemented
+        return self.intersection(other)
+
+    def __ior__(self, other: "BloomFilter") -> "BloomFilter":
+        if not isinstance(other, BloomFilter):
+            return NotImplemented
+        self._inner |= other._inner
+        return self
+
+    def __iand__(self, other: "BloomFilter") -> "BloomFilter":
+        if not isinstance(other, BloomFilter):
+            return NotImplemented
+        self._inner &= other._inner
+        return self
+
+    def _wrap(self, inner: _RustBloomFilter) -> "BloomFilter":
+        new_filter = self.__class__.__new__(self.__class__)
+        new_filter._inner = inner
+        return new_filter
+
+    @staticmethod
+    def _unwrap(other: "BloomFilter") -> _RustBloomFilter:
+        if not isinstance(other, BloomFilter):
+            raise TypeError("Other must be of Type: BloomFilter")
+        return other._inner
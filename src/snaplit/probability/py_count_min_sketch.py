@@ -0,0 +1,177 @@
+#---------- Imports ----------
+
+from _rust_snaplit import CountMinSketch as _RustCountMinSketch
+
+from typing import Any, List, Optional, Tuple
+
+#---------- Count-Min Sketch Shim ----------
+
+class CountMinSketch():
+    """
+    A compact probabilistic table of counters estimating how often each element has been seen.
+
+    This Count-Min Sketch class implements a Rust-powered backend storing 'depth' rows of 'width' counters.
+    Every element increments one counter per row, and its frequency is estimated as the smallest of those
+    counters - estimates never undercount, and overshoot by at most epsilon * total with probability 1 - delta.
+    An optional top-k tracker keeps the heaviest hitters seen so far without holding a full dictionary.
+
+    ----- Parameters -----
+
+    width: Optional[int] = 2048
+        Number of counters per row. Cannot be combined with epsilon / delta.
+        Must be a positive integer.
+
+    depth: Optional[int] = 5
+        Number of rows, each hashed independently. Cannot be combined with epsilon / delta.
+        Must be a positive integer.
+
+    epsilon: Optional[float] = None
+        Relative error bound - sets width to ceil(e / epsilon). Must be given together with delta.
+        Must be a float between 0.00 - 1.00.
+
+    delta: Optional[float] = None
+        Failure probability of the error bound - sets depth to ceil(ln(1 / delta)).
+        Must be a float between 0.00 - 1.00.
+
+    conservative: Optional[bool] = False
+        Enables conservative update - only the counters below the new estimate are raised,
+        which tightens estimates considerably on skewed streams.
+
+    top_k: Optional[int] = 0
+        Number of heavy hitters to track alongside the sketch. 0 disables tracking.
+
+    seed: Optional[int] = None
+        Enables deterministic hashing when set - items are hashed with xxh3 over a canonical byte encoding
+        using this seed, so sketches built in different processes can be merged.
+        Only str, bytes and int items are supported in this mode.
+
+    ----- Methods -----
+
+    add(item: Any, count: int = 1) -> int:
+        Adds 'count' occurrences of an element and returns its updated frequency estimate.
+
+    estimate(item: Any) -> int:
+        Returns the estimated frequency of an element - never lower than the true frequency.
+
+    merge(other: CountMinSketch) -> None:
+        Adds every counter of another sketch into the current one and re-ranks the heavy hitters.
+        Both sketches must share the same width, depth and hash scheme.
+
+    heavy_hitters() -> List[Tuple[Any, int]]:
+        Returns the tracked heavy hitters as (item, estimate) pairs, heaviest first.
+
+    total() -> int:
+        Returns the total count added to the sketch.
+
+    width() -> int:
+        Returns the number of counters per row.
+
+    depth() -> int:
+        Returns the number of rows.
+
+    is_conservative() -> bool:
+        Returns True if the sketch uses conservative updates.
+
+    hash_scheme() -> str:
+        Returns the hash scheme recorded in the sketch - 'python' or 'xxh3'.
+
+    seed() -> Optional[int]:
+        Returns the seed of the deterministic hash scheme, or None when Python's hash() is used.
+
+    clear() -> None:
+        Resets every counter and the heavy hitter tracker.
+
+    ----- Example -----
+
+    >>> traffic = CountMinSketch(epsilon=0.001, delta=0.01, conservative=True, top_k=2)
+
+    >>> traffic.add("key-alpha", 120)
+    120
+    >>> traffic.add("key-beta", 40)
+    40
+    >>> traffic.add("key-gamma")
+    1
+
+    >>> print(traffic.estimate("key-alpha"))
+    120
+    >>> print(traffic.heavy_hitters())
+    [('key-alpha', 120), ('key-beta', 40)]
+    """
+
+    def __init__(
+        self,
+        width: Optional[int]=None,
+        depth: Optional[int]=None,
+        epsilon: Optional[float]=None,
+        delta: Optional[float]=None,
+        conservative: Optional[bool]=False,
+        top_k: Optional[int]=0,
+        seed: Optional[int]=None
+    ):
+        if width is not None and not isinstance(width, int):
+            raise TypeError("Width must be of Type: int")
+        if depth is not None and not isinstance(depth, int):
+            raise TypeError("Depth must be of Type: int")
+        if epsilon is not None and not isinstance(epsilon, float):
+            raise TypeError("Epsilon must be of Type: float")
+        if delta is not None and not isinstance(delta, float):
+            raise TypeError("Delta must be of Type: float")
+        if not isinstance(conservative, bool):
+            raise TypeError("Conservative must be of Type: bool")
+        if not isinstance(top_k, int):
+            raise TypeError("Top k must be of Type: int")
+        if top_k < 0:
+            raise ValueError("Top k must be represented by a non-negative integer")
+        if seed is not None and not isinstance(seed, int):
+            raise TypeError("Seed must be of Type: int")
+        if seed is not None and not (0 <= seed < 2**64):
+            raise ValueError("Seed must be between 0 and 2**64 - 1")
+
+        self._inner = _RustCountMinSketch(
+            width=width,
+            depth=depth,
+            epsilon=epsilon,
+            delta=delta,
+            conservative=conservative,
+            top_k=top_k,
+            seed=seed
+        )
+
+    def add(self, item: Any, count: int = 1) -> int:
+        if not isinstance(count, int):
+            raise TypeError("Count must be of Type: int")
+        if count < 0:
+            raise ValueError("Count must be represented by a non-negative integer")
+        return self._inner.add(item, count)
+
+    def estimate(self, item: Any) -> int:
+        return self._inner.estimate(item)
+
+    def merge(self, other: "CountMinSketch") -> None:
+        if not isinstance(other, CountMinSketch):
+            raise TypeError("Other must be of Type: CountMinSketch")
+        self._inner.merge(other._inner)
+
+    def heavy_hitters(self) -> List[Tuple[Any, int]]:
+        return self._inner.heavy_hitters()
+
+    def total(self) -> int:
+        return self._inner.total()
+
+    def width(self) -> int:
+        return self._inner.width()
+
+    def depth(self) -> int:
+        return self._inner.depth()
+
+    def is_conservative(self) -> bool:
+        return self._inner.is_conservative()
+
+    def hash_scheme(self) -> str:
+        return self._inner.hash_scheme()
+
+    def seed(self) -> Optional[int]:
+        return self._inner.seed()
+
+    def clear(self) -> None:
+        self._inner.clear()
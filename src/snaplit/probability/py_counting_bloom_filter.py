@@ -0,0 +1,135 @@
+#---------- Imports ----------
+
+from _rust_snaplit import CountingBloomFilter as _RustCountingBloomFilter
+
+from typing import Any, Optional
+
+#---------- Counting Bloom Filter Shim ----------
+
+class CountingBloomFilter():
+    """
+    A Bloom Filter variant that replaces every bit with a small counter, allowing elements to be removed again.
+
+    This Counting Bloom Filter class implements a Rust-powered backend sharing the hashing scheme of the
+    regular Bloom Filter. Each slot holds a 4-bit or 8-bit saturating counter - counters that reach their
+    maximum stay there, and every increment lost this way is reported through 'overflow_count()'.
+
+    ----- Parameters -----
+
+    size: int
+        Expected number of elements to be stored in the Counting Bloom Filter.
+        Must be a positive integer.
+
+    probability: float
+        Desired false-positive probability for the filter.
+        Must be a float betwee 0.00 - 1.00.
+
+    counter_bits: int
+        Width of every counter, either 4 (max count 15) or 8 (max count 255).
+        Defaults to 4.
+
+    seed: Optional[int] = None
+        Enables deterministic hashing when set - items are hashed with xxh3 over a canonical byte encoding
+        using this seed, so the filter answers identically across processes and machines.
+        Only str, bytes and int items are supported in this mode. When None, Python's hash() is used,
+        which is randomised per process for str and bytes.
+
+    ----- Methods -----
+
+    add(item: Any) -> None:
+        Inserts an element into the internal Counting Bloom Filter, incrementing its counters.
+
+    remove(item: Any) -> bool:
+        Decrements the counters of an element and returns True.
+        Returns False if the element is *definitely* not present. Saturated counters are never decremented.
+
+    contains(item: Any) -> bool:
+        Returns True if an element *might* be present in the internal Counting Bloom Filter.
+        Returns False if the element is *definitely* not present in the internal Counting Bloom Filter.
+
+    count_estimate(item: Any) -> int:
+        Returns an upper bound on the number of times an element has been added (the smallest probed counter).
+
+    overflow_count() -> int:
+        Returns the number of increments dropped because a counter was already saturated.
+
+    saturated_counters() -> int:
+        Returns the number of counters currently stuck at their maximum value.
+
+    counter_bits() -> int:
+        Returns the width of the internal counters (4 or 8).
+
+    hash_scheme() -> str:
+        Returns the hash scheme recorded in the filter - 'python' or 'xxh3'.
+
+    seed() -> Optional[int]:
+        Returns the seed of the deterministic hash scheme, or None when Python's hash() is used.
+
+    clear() -> None:
+        Resets the internal Counting Bloom Filter, removing all stored values and overflow records.
+
+    ----- Example -----
+
+    >>> test_filter = CountingBloomFilter(size=10000, probability=0.1)
+
+    >>> test_filter.add("Greninja")
+    >>> test_filter.add("Greninja")
+
+    >>> print(test_filter.count_estimate("Greninja"))
+    2
+    >>> print(test_filter.remove("Greninja"))
+    True
+    >>> print(test_filter.contains("Greninja"))
+    True
+    """
+
+    def __init__(self, size: int, probability: float, counter_bits: int = 4, seed: Optional[int] = None):
+        if not isinstance(size, int):
+            raise TypeError("Size must be of Type: int")
+        if not isinstance(probability, float):
+            raise TypeError("Probability must be of Type: float")
+        if size <= 0:
+            raise ValueError("Size must be represented by a positive integer")
+        if not (0.00 < probability < 1.00):
+            raise ValueError("Probability must be betweenn 0.00 - 1.00")
+        if counter_bits not in (4, 8):
+            raise ValueError("Counter bits must be either 4 or 8")
+        if seed is not None and not isinstance(seed, int):
+            raise TypeError("Seed must be of Type: int")
+        if seed is not None and not (0 <= seed < 2**64):
+            raise ValueError("Seed must be between 0 and 2**64 - 1")
+
+        self._inner = _RustCountingBloomFilter(size=size, probability=probability, counter_bits=counter_bits, seed=seed)
+
+    def add(self, item: Any) -> None:
+        self._inner.add(item)
+
+    def remove(self, item: Any) -> bool:
+        return self._inner.remove(item)
+
+    def contains(self, item: Any) -> bool:
+        return self._inner.contains(item)
+
+    def count_estimate(self, item: Any) -> int:
+        return self._inner.count_estimate(item)
+
+    def overflow_count(self) -> int:
+        return self._inner.overflow_count()
+
+    def saturated_counters(self) -> int:
+        return self._inner.saturated_counters()
+
+    def counter_bits(self) -> int:
+        return self._inner.counter_bits()
+
+    def hash_scheme(self) -> str:
+        return self._inner.hash_scheme()
+
+    def seed(self) -> Optional[int]:
+        return self._inner.seed()
+
+    def clear(self) -> None:
+        self._inner.clear()
+
+    def __contains__(self, item: Any) -> bool:
+        return self._inner.contains(item)
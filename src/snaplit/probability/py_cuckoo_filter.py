@@ -18,20 +18,34 @@ class CuckooFilter():
 
     size: Optional[int] = 100:
         The number of individual storage buckets implemented in the internal filter.
+        Rounded up to the next power of two, so relocated entries can always find their way back.
         Must be a positive integer.
     bucket_size: Optional[int] = 4:
         The number individual slots per bucket. higher values reduce false positivity rates but increase
         memory usage.
         Must be a positive integer.
-    retries: Optional[int] = 5:
+    retries: Optional[int] = 500:
         The maximum number of displacemnt attempts made during insertions before giving up.
+        An insertion only fails once this kick chain ends with the victim stash full as well.
         Must be a positive integer.
+    seed: Optional[int] = None
+        Enables deterministic hashing when set - items are hashed with xxh3 over a canonical byte encoding
+        using this seed, so the filter answers identically across processes and machines.
+        Only str, bytes and int items are supported in this mode. When None, Python's hash() is used,
+        which is randomised per process for str and bytes.
+    fingerprint_bits: Optional[int] = 16:
+        Width of the stored fingerprints - 8, 12, 16 or 32 bits.
+        Wider fingerprints lower the false-positive rate at the cost of memory.
+    auto_resize: Optional[bool] = False:
+        When enabled, an insert that finds the filter full appends a new bucket table twice the size
+        of the previous one instead of failing.
 
     ----- Methods -----
 
     insert(item: Any) -> bool:
         Inserts an item into the filter. Returns True if the insertion in successfull,
-        or False if the filter is full.
+        or False if the filter is full. Entries displaced during insertion are kept in a small
+        victim stash, so a failed insert never loses previously stored items.
 
     contains(item: Any) -> bool:
         Checks whether the specified item is *possibly* present in the filter.
@@ -46,9 +60,43 @@ class CuckooFilter():
     load_factor() -> float:
         Returns the current filter's load factor - A floating-point representation of how full the filter is.
 
+    capacity() -> int:
+        Returns the total number of fingerprint slots across all bucket tables.
+
+    table_count() -> int:
+        Returns the number of bucket tables - grows when 'auto_resize' is enabled.
+
+    stash_len() -> int:
+        Returns the number of entries currently held in the victim stashes.
+
+    fingerprint_bits() -> int:
+        Returns the width of the stored fingerprints.
+
+    false_positive_rate() -> float:
+        Returns the theoretical false-positive rate at the current load and fingerprint width.
+
     clear() -> None:
         Clears all entries from the current filter.
 
+    hash_scheme() -> str:
+        Returns the hash scheme recorded in the filter - 'python' or 'xxh3'.
+
+    seed() -> Optional[int]:
+        Returns the seed of the deterministic hash scheme, or None when Python's hash() is used.
+
+    to_bytes() -> bytes:
+        Serialises the filter into a compact, versioned binary blob.
+        The header records the bucket layout, retries, fingerprint width, resize mode and hash scheme.
+
+    from_bytes(data: bytes) -> CuckooFilter:
+        Class method - Rebuilds a filter from a blob produced by 'to_bytes()'.
+
+    save(path: str) -> None:
+        Writes the serialised filter to the specified file path.
+
+    load(path: str) -> CuckooFilter:
+        Class method - Reads a filter previously written with 'save()'.
+
     __len__() -> int:
         Returns the number of elements currently stored in the filter.
 
@@ -80,7 +128,10 @@ class CuckooFilter():
         self,
         size: Optional[int]=100,
         bucket_size: Optional[int]=4,
-        retries: Optional[int]=5
+        retries: Optional[int]=500,
+        seed: Optional[int]=None,
+        fingerprint_bits: Optional[int]=16,
+        auto_resize: Optional[bool]=False
     ):
         if not isinstance(size, int):
             raise TypeError("Size must be of Type: int")
@@ -94,8 +145,23 @@ class CuckooFilter():
             raise ValueError("Bucket size must be represented by a positive integer")
         if retries <= 0:
             raise ValueError("Retries must be represented by a positive integer")
+        if seed is not None and not isinstance(seed, int):
+            raise TypeError("Seed must be of Type: int")
+        if seed is not None and not (0 <= seed < 2**64):
+            raise ValueError("Seed must be between 0 and 2**64 - 1")
+        if fingerprint_bits not in (8, 12, 16, 32):
+            raise ValueError("Fingerprint bits must be 8, 12, 16 or 32")
+        if not isinstance(auto_resize, bool):
+            raise TypeError("Auto resize must be of Type: bool")
         
-        self._inner = _RustCuckooFilter(size=size, bucket_size=bucket_size, retries=retries)
+        self._inner = _RustCuckooFilter(
+            size=size,
+            bucket_size=bucket_size,
+            retries=retries,
+            seed=seed,
+            fingerprint_bits=fingerprint_bits,
+            auto_resize=auto_resize
+        )
 
     def insert(self, item: Any) -> bool:
         return self._inner.insert(item)
@@ -112,9 +178,50 @@ class CuckooFilter():
     def load_factor(self) -> float:
         return self._inner.load_factor()
     
+    def capacity(self) -> int:
+        return self._inner.capacity()
+    
+    def table_count(self) -> int:
+        return self._inner.table_count()
+    
+    def stash_len(self) -> int:
+        return self._inner.stash_len()
+    
+    def fingerprint_bits(self) -> int:
+        return self._inner.fingerprint_bits()
+    
+    def false_positive_rate(self) -> float:
+        return self._inner.false_positive_rate()
+    
     def clear(self) -> None:
         self._inner.clear()
 
+    def hash_scheme(self) -> str:
+        return self._inner.hash_scheme()
+
+    def seed(self) -> Optional[int]:
+        return self._inner.seed()
+
+    def to_bytes(self) -> bytes:
+        return self._inner.to_bytes()
+
+    @classmethod
+    def from_bytes(cls, data: bytes) -> "CuckooFilter":
+        if not isinstance(data, (bytes, bytearray)):
+            raise TypeError("Data must be of Type: bytes")
+        new_filter = cls.__new__(cls)
+        new_filter._inner = _RustCuckooFilter.from_bytes(bytes(data))
+        return new_filter
+
+    def save(self, path: str) -> None:
+        self._inner.save(str(path))
+
+    @classmethod
+    def load(cls, path: str) -> "CuckooFilter":
+        new_filter = cls.__new__(cls)
+        new_filter._inner = _RustCuckooFilter.load(str(path))
+        return new_filter
+
     def __len__(self) -> int:
         return self._inner.len()
     
@@ -10,17 +10,17 @@ class Flatlist():
     """
     An optimized, Rust-powered ordered collection for Python operations.
 
-    'Flatlist' provides a high-performance, memory-efficient data structure based on a flattened Skiplist,
-    implemented in Rust. This hybrid design combines Rust's low-level speed and memory safety guarantess 
-    with a clean Pythonic API. The result is a deterministic, lock-free ordered listthat supports fast 
-    insertions, deletion and lookups - Ideal for workloads requiring predictable ordering and high output.
+    'Flatlist' provides a high-performance sorted list based on an indexable Skiplist, implemented in Rust.
+    Every forward pointer records how many elements it skips, so searches, insertions, deletions, rank
+    queries and positional access all run in O(log n). Equal elements keep their insertion order, which
+    makes 'Flatlist' a drop-in replacement for a SortedList with bisect-style queries.
 
     ----- Parameters -----
 
     levels: Optional[int] = None
         Maximum skiplist height - The number of levels that the Flatlist include.
         Must be represented by a positive, unsigned integer.
-        Rust backend ensures that default 'levels' value is 4.
+        Rust backend ensures that default 'levels' value is 32.
 
     probability: Optional[float] = None
         Probability controlling level management.
@@ -50,6 +50,21 @@ class Flatlist():
     index_of(key: Any) -> int:
         Returns the index position of the specified value.
 
+    rank(value: Any) -> int:
+        Returns the number of elements strictly smaller than the specified value.
+
+    bisect_left(value: Any) -> int:
+        Returns the leftmost position at which the value could be inserted while keeping the order.
+
+    bisect_right(value: Any) -> int:
+        Returns the rightmost position at which the value could be inserted while keeping the order.
+
+    at(index: int) -> Any:
+        Returns the element at the specified position - negative indexes count from the end.
+
+    range(lo: Optional[Any] = None, hi: Optional[Any] = None) -> List[Any]:
+        Returns all elements within the half-open interval [lo, hi) - a 'None' bound stays open.
+
     to_list() -> List[Any]:
         Returns a list representation of all internally stored values.
 
@@ -77,8 +92,21 @@ class Flatlist():
     clear() -> None:
         Removes all current elements from internal lists and then resets all related instances.
 
-    __len__() -> 
-    
+    __len__() -> int:
+        Returns the number of elements currently stored.
+
+    __getitem__(index: int) -> Any:
+        Enables indexing - equivalent to 'at(index)'.
+
+    ----- Example -----
+
+    >>> scores = Flatlist()
+    >>> scores.extend([42, 7, 19, 7, 88])
+
+    >>> print(scores.to_list(), scores.rank(19), scores[-1])
+    [7, 7, 19, 42, 88] 2 88
+    >>> print(scores.range(10, 50), scores.bisect_right(7))
+    [19, 42] 2
     """
 
     def __init__(self, levels: Optional[int] = None, probability: Optional[float] = None):
@@ -117,6 +145,23 @@ class Flatlist():
     def index_of(self, key: Any) -> int:
         return self._inner.index_of(key)
     
+    def rank(self, value: Any) -> int:
+        return self._inner.rank(value)
+
+    def bisect_left(self, value: Any) -> int:
+        return self._inner.bisect_left(value)
+
+    def bisect_right(self, value: Any) -> int:
+        return self._inner.bisect_right(value)
+
+    def at(self, index: int) -> Any:
+        if not isinstance(index, int):
+            raise TypeError(f"Index must be of Type: int - Current type {type(index)}")
+        return self._inner.at(index)
+
+    def range(self, lo: Optional[Any] = None, hi: Optional[Any] = None) -> List[Any]:
+        return self._inner.range(lo, hi)
+
     def to_list(self) -> List[Any]:
         return self._inner.to_list()
     
@@ -153,6 +198,9 @@ class Flatlist():
     def __len__(self) -> int:
         return self._inner.size()
     
+    def __getitem__(self, index: int) -> Any:
+        return self.at(index)
+
     def __bool__(self) -> bool:
         return not self._inner.is_empty()
     
@@ -0,0 +1,145 @@
+#---------- Imports ----------
+
+from _rust_snaplit import HyperLogLog as _RustHyperLogLog
+
+from typing import Any, Optional
+
+#---------- HyperLogLog Shim ----------
+
+class HyperLogLog():
+    """
+    A mergeable probabilistic sketch estimating the number of distinct elements in a stream.
+
+    This HyperLogLog class implements a Rust-powered backend using a fixed amount of memory regardless of
+    the number of elements added. Small sketches keep a sparse register map and switch to a dense register
+    array once it pays off. Items are hashed with seeded xxh3 over a canonical byte encoding, so sketches
+    built in different processes or on different machines can be merged and compared.
+
+    ----- Parameters -----
+
+    precision: Optional[int] = 14
+        Number of index bits - the sketch holds 2**precision registers.
+        Higher values lower the estimation error but increase memory usage.
+        Must be an integer between 4 - 18.
+
+    seed: Optional[int] = 0
+        Seed of the xxh3 hash. Only sketches sharing precision and seed can be merged.
+        Must be an integer between 0 and 2**64 - 1.
+
+    ----- Methods -----
+
+    add(item: Any) -> None:
+        Adds an element (str, bytes or int) to the sketch.
+
+    count() -> float:
+        Returns the estimated number of distinct elements added.
+
+    merge(other: HyperLogLog) -> None:
+        Folds another sketch into the current one, as if all of its elements were added here.
+        Both sketches must share the same precision and seed.
+
+    precision() -> int:
+        Returns the precision of the sketch.
+
+    seed() -> int:
+        Returns the seed of the sketch's hash.
+
+    is_sparse() -> bool:
+        Returns True while the sketch still uses the sparse register representation.
+
+    relative_error() -> float:
+        Returns the standard error of the estimate (1.04 / sqrt(2**precision)).
+
+    clear() -> None:
+        Resets the sketch, removing all registered elements.
+
+    to_bytes() -> bytes:
+        Serialises the sketch into a compact, versioned binary blob.
+
+    from_bytes(data: bytes) -> HyperLogLog:
+        Class method - Rebuilds a sketch from a blob produced by 'to_bytes()'.
+
+    save(path: str) -> None:
+        Writes the serialised sketch to the specified file path.
+
+    load(path: str) -> HyperLogLog:
+        Class method - Reads a sketch previously written with 'save()'.
+
+    __len__() -> int:
+        Returns the estimated number of distinct elements, rounded to the nearest integer.
+
+    ----- Example -----
+
+    >>> shard_a = HyperLogLog(precision=14, seed=42)
+    >>> shard_b = HyperLogLog(precision=14, seed=42)
+
+    >>> for user in range(0, 6000):
+    ...     shard_a.add(user)
+    >>> for user in range(4000, 10000):
+    ...     shard_b.add(user)
+
+    >>> shard_a.merge(HyperLogLog.from_bytes(shard_b.to_bytes()))
+    >>> print(round(shard_a.count(), -2))
+    10000.0
+    """
+
+    def __init__(self, precision: Optional[int]=14, seed: Optional[int]=0):
+        if not isinstance(precision, int):
+            raise TypeError("Precision must be of Type: int")
+        if not isinstance(seed, int):
+            raise TypeError("Seed must be of Type: int")
+        if not (4 <= precision <= 18):
+            raise ValueError("Precision must be between 4 - 18")
+        if not (0 <= seed < 2**64):
+            raise ValueError("Seed must be between 0 and 2**64 - 1")
+
+        self._inner = _RustHyperLogLog(precision=precision, seed=seed)
+
+    def add(self, item: Any) -> None:
+        self._inner.add(item)
+
+    def count(self) -> float:
+        return self._inner.count()
+
+    def merge(self, other: "HyperLogLog") -> None:
+        if not isinstance(other, HyperLogLog):
+            raise TypeError("Other must be of Type: HyperLogLog")
+        self._inner.merge(other._inner)
+
+    def precision(self) -> int:
+        return self._inner.precision()
+
+    def seed(self) -> int:
+        return self._inner.seed()
+
+    def is_sparse(self) -> bool:
+        return self._inner.is_sparse()
+
+    def relative_error(self) -> float:
+        return self._inner.relative_error()
+
+    def clear(self) -> None:
+        self._inner.clear()
+
+    def to_bytes(self) -> bytes:
+        return self._inner.to_bytes()
+
+    @classmethod
+    def from_bytes(cls, data: bytes) -> "HyperLogLog":
+        if not isinstance(data, (bytes, bytearray)):
+            raise TypeError("Data must be of Type: bytes")
+        new_sketch = cls.__new__(cls)
+        new_sketch._inner = _RustHyperLogLog.from_bytes(bytes(data))
+        return new_sketch
+
+    def save(self, path: str) -> None:
+        self._inner.save(str(path))
+
+    @classmethod
+    def load(cls, path: str) -> "HyperLogLog":
+        new_sketch = cls.__new__(cls)
+        new_sketch._inner = _RustHyperLogLog.load(str(path))
+        return new_sketch
+
+    def __len__(self) -> int:
+        return len(self._inner)
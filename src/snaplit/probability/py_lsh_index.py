@@ -0,0 +1,137 @@
+#---------- Imports ----------
+
+from _rust_snaplit import LSHIndex as _RustLSHIndex
+
+from .py_minhash import MinHash
+
+from typing import Any, List, Optional, Sequence, Union
+
+#---------- LSH Index Shim ----------
+
+class LSHIndex():
+    """
+    A locality-sensitive hashing index over MinHash signatures for finding near-duplicate sets.
+
+    This LSHIndex class implements a Rust-powered backend splitting every signature into 'bands' bands of
+    'rows' values each. Signatures sharing at least one identical band land in the same bucket and become
+    candidates, which are then filtered on their estimated Jaccard similarity. Unless given explicitly, the
+    band layout is chosen to minimise the false positives and false negatives around the configured threshold.
+
+    ----- Parameters -----
+
+    threshold: Optional[float] = 0.5
+        Jaccard similarity above which sets count as near-duplicates.
+        Must be a float between 0.0 - 1.0.
+
+    num_perm: Optional[int] = 128
+        Number of permutations of the indexed signatures.
+        Must match the 'num_perm' of every MinHash inserted or queried.
+
+    bands: Optional[int] = None
+        Number of bands per signature. Must be given together with 'rows'.
+
+    rows: Optional[int] = None
+        Number of signature values per band. 'bands * rows' may not exceed 'num_perm'.
+
+    ----- Methods -----
+
+    insert(key: Any, signature: MinHash | Sequence[int]) -> None:
+        Indexes a signature under a hashable key. Raises ValueError if the key already exists.
+
+    remove(key: Any) -> None:
+        Removes a key from the index. Raises KeyError if the key is missing.
+
+    query(signature: MinHash | Sequence[int], threshold: Optional[float] = None) -> List[Any]:
+        Returns the keys of indexed signatures whose estimated similarity reaches the threshold
+        (the index's own threshold by default), most similar first.
+
+    contains(key: Any) -> bool:
+        Checks if a key has been indexed.
+
+    keys() -> List[Any]:
+        Returns every indexed key in insertion order.
+
+    threshold() -> float:
+        Returns the configured similarity threshold.
+
+    num_perm() -> int:
+        Returns the expected number of permutations per signature.
+
+    bands() -> int:
+        Returns the number of bands per signature.
+
+    rows() -> int:
+        Returns the number of signature values per band.
+
+    clear() -> None:
+        Removes every key from the index.
+
+    __len__() -> int:
+        Returns the number of indexed keys.
+
+    __contains__(key: Any) -> bool:
+        Enables use of 'in' keyword to check for keys.
+
+    ----- Example -----
+
+    >>> index = LSHIndex(threshold=0.7)
+    >>> index.insert("doc-1", MinHash(items="the quick brown fox jumps over the lazy dog".split()))
+    >>> index.insert("doc-2", MinHash(items="lorem ipsum dolor sit amet".split()))
+
+    >>> print(index.query(MinHash(items="the quick brown fox leaps over the lazy dog".split())))
+    ['doc-1']
+    """
+
+    def __init__(self, threshold: Optional[float]=0.5, num_perm: Optional[int]=128, bands: Optional[int]=None, rows: Optional[int]=None):
+        if not isinstance(threshold, (int, float)):
+            raise TypeError("Threshold must be of Type: float")
+        if not isinstance(num_perm, int):
+            raise TypeError("Number of permutations must be of Type: int")
+        if not (0.0 <= threshold <= 1.0):
+            raise ValueError("Threshold must be between 0.0 - 1.0")
+        if num_perm <= 0:
+            raise ValueError("Number of permutations must be a positive integer")
+
+        self._inner = _RustLSHIndex(threshold=float(threshold), num_perm=num_perm, bands=bands, rows=rows)
+
+    @staticmethod
+    def _unwrap(signature: Union[MinHash, Sequence[int]]) -> Any:
+        if isinstance(signature, MinHash):
+            return signature._inner
+        return list(signature)
+
+    def insert(self, key: Any, signature: Union[MinHash, Sequence[int]]) -> None:
+        self._inner.insert(key, self._unwrap(signature))
+
+    def remove(self, key: Any) -> None:
+        self._inner.remove(key)
+
+    def query(self, signature: Union[MinHash, Sequence[int]], threshold: Optional[float]=None) -> List[Any]:
+        return self._inner.query(self._unwrap(signature), threshold)
+
+    def contains(self, key: Any) -> bool:
+        return self._inner.contains(key)
+
+    def keys(self) -> List[Any]:
+        return self._inner.keys()
+
+    def threshold(self) -> float:
+        return self._inner.threshold()
+
+    def num_perm(self) -> int:
+        return self._inner.num_perm()
+
+    def bands(self) -> int:
+        return self._inner.bands()
+
+    def rows(self) -> int:
+        return self._inner.rows()
+
+    def clear(self) -> None:
+        self._inner.clear()
+
+    def __len__(self) -> int:
+        return len(self._inner)
+
+    def __contains__(self, key: Any) -> bool:
+        return self._inner.contains(key)
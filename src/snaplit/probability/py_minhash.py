@@ -0,0 +1,124 @@
+#---------- Imports ----------
+
+from _rust_snaplit import MinHash as _RustMinHash
+
+from typing import Any, Iterable, List, Optional
+
+#---------- MinHash Shim ----------
+
+class MinHash():
+    """
+    A fixed-size signature of a set, used to estimate the Jaccard similarity between sets.
+
+    This MinHash class implements a Rust-powered backend keeping the minimum of 'num_perm' independent hash
+    permutations over every element seen. Two signatures agree on a permutation with probability equal to the
+    Jaccard similarity of the underlying sets, so comparing signatures estimates similarity without the sets.
+    Items are hashed with seeded xxh3 over a canonical byte encoding, so signatures computed in different
+    processes or on different machines can be compared and indexed together.
+
+    ----- Parameters -----
+
+    num_perm: Optional[int] = 128
+        Number of hash permutations in the signature.
+        Higher values lower the estimation error (~1 / sqrt(num_perm)) but cost more time and memory.
+        Must be a positive integer.
+
+    seed: Optional[int] = 0
+        Seed of the hash permutations. Only signatures sharing num_perm and seed can be compared.
+        Must be an integer between 0 and 2**64 - 1.
+
+    items: Optional[Iterable[Any]] = None
+        Optional iterable of elements (str, bytes or int) to add right away.
+
+    ----- Methods -----
+
+    update(item: Any) -> None:
+        Adds a single element to the signature.
+
+    update_batch(items: Iterable[Any]) -> None:
+        Adds every element of an iterable to the signature.
+
+    jaccard(other: MinHash) -> float:
+        Returns the estimated Jaccard similarity between both underlying sets.
+
+    merge(other: MinHash) -> None:
+        Folds another signature into the current one, yielding the signature of the union of both sets.
+
+    count() -> float:
+        Returns the estimated number of distinct elements added.
+
+    signature() -> List[int]:
+        Returns the signature values as a list of integers.
+
+    num_perm() -> int:
+        Returns the number of permutations in the signature.
+
+    seed() -> int:
+        Returns the seed of the hash permutations.
+
+    is_empty() -> bool:
+        Checks if no element has been added yet.
+
+    clear() -> None:
+        Resets the signature to its empty state.
+
+    __len__() -> int:
+        Returns the number of permutations in the signature.
+
+    ----- Example -----
+
+    >>> first = MinHash(items="the quick brown fox jumps over the lazy dog".split())
+    >>> second = MinHash(items="the quick brown fox leaps over the lazy dog".split())
+
+    >>> print(round(first.jaccard(second), 1))
+    0.8
+    """
+
+    def __init__(self, num_perm: Optional[int]=128, seed: Optional[int]=0, items: Optional[Iterable[Any]]=None):
+        if not isinstance(num_perm, int):
+            raise TypeError("Number of permutations must be of Type: int")
+        if not isinstance(seed, int):
+            raise TypeError("Seed must be of Type: int")
+        if num_perm <= 0:
+            raise ValueError("Number of permutations must be a positive integer")
+        if not (0 <= seed < 2**64):
+            raise ValueError("Seed must be between 0 and 2**64 - 1")
+
+        self._inner = _RustMinHash(num_perm=num_perm, seed=seed, items=items)
+
+    def update(self, item: Any) -> None:
+        self._inner.update(item)
+
+    def update_batch(self, items: Iterable[Any]) -> None:
+        self._inner.update_batch(items)
+
+    def jaccard(self, other: "MinHash") -> float:
+        if not isinstance(other, MinHash):
+            raise TypeError("Other must be of Type: MinHash")
+        return self._inner.jaccard(other._inner)
+
+    def merge(self, other: "MinHash") -> None:
+        if not isinstance(other, MinHash):
+            raise TypeError("Other must be of Type: MinHash")
+        self._inner.merge(other._inner)
+
+    def count(self) -> float:
+        return self._inner.count()
+
+    def signature(self) -> List[int]:
+        return self._inner.signature()
+
+    def num_perm(self) -> int:
+        return self._inner.num_perm()
+
+    def seed(self) -> int:
+        return self._inner.seed()
+
+    def is_empty(self) -> bool:
+        return self._inner.is_empty()
+
+    def clear(self) -> None:
+        self._inner.clear()
+
+    def __len__(self) -> int:
+        return len(self._inner)
@@ -0,0 +1,162 @@
+#---------- Imports ----------
+
+from _rust_snaplit import ScalableBloomFilter as _RustScalableBloomFilter
+
+from typing import Any, Optional
+
+#---------- Scalable Bloom Filter Shim ----------
+
+class ScalableBloomFilter():
+    """
+    A Bloom Filter that grows on demand while keeping its overall false-positive rate bounded.
+
+    This Scalable Bloom Filter class implements a Rust-powered backend chaining individual filter slices.
+    Once the newest slice reaches its capacity, a larger slice with a tighter error rate is appended, so the
+    compounded false-positive probability stays below the requested target no matter how many items arrive.
+    Well suited for streams whose cardinality is not known in advance.
+
+    ----- Parameters -----
+
+    initial_capacity: int
+        Number of elements the first slice is sized for.
+        Must be a positive integer.
+
+    probability: float
+        Target false-positive probability for the whole filter.
+        Must be a float betwee 0.00 - 1.00.
+
+    growth: Optional[int] = 2
+        Factor by which the capacity of every new slice grows over the previous one.
+        Must be a positive integer.
+
+    ratio: Optional[float] = 0.9
+        Factor by which the error rate of every new slice tightens over the previous one.
+        Must be a float between 0.00 - 1.00.
+
+    seed: Optional[int] = None
+        Enables deterministic hashing when set - items are hashed with xxh3 over a canonical byte encoding
+        using this seed, so the filter answers identically across processes and machines.
+        Only str, bytes and int items are supported in this mode. When None, Python's hash() is used,
+        which is randomised per process for str and bytes.
+
+    ----- Methods -----
+
+    add(item: Any) -> bool:
+        Inserts an element into the filter, appending a new slice when the current one is full.
+        Returns False if the element was already reported as present, else True.
+        Raises OverflowError once the next slice's tightened error rate or size can no longer be represented.
+
+    contains(item: Any) -> bool:
+        Returns True if an element *might* be present in the filter.
+        Returns False if the element is *definitely* not present in the filter.
+
+    len() -> int:
+        Returns the number of distinct elements added to the filter.
+
+    capacity() -> int:
+        Returns the combined capacity of all slices currently allocated.
+
+    slice_count() -> int:
+        Returns the number of slices currently allocated.
+
+    false_positive_rate() -> float:
+        Returns the effective false-positive probability implied by the current fill of every slice.
+
+    hash_scheme() -> str:
+        Returns the hash scheme recorded in the filter - 'python' or 'xxh3'.
+
+    seed() -> Optional[int]:
+        Returns the seed of the deterministic hash scheme, or None when Python's hash() is used.
+
+    clear() -> None:
+        Resets the filter to a single, empty slice.
+
+    __len__() -> int:
+        Returns the number of distinct elements added to the filter.
+
+    __contains__(item: Any) -> bool:
+        Enables Python's native 'in' operation:
+        'item in scalable_filter'.
+
+    ----- Example -----
+
+    >>> test_filter = ScalableBloomFilter(initial_capacity=100, probability=0.01)
+
+    >>> for number in range(1000):
+    ...     test_filter.add(number)
+
+    >>> print(test_filter.slice_count(), test_filter.capacity())
+    4 1500
+    >>> print(500 in test_filter)
+    True
+    """
+
+    def __init__(
+        self,
+        initial_capacity: int,
+        probability: float,
+        growth: Optional[int]=2,
+        ratio: Optional[float]=0.9,
+        seed: Optional[int]=None
+    ):
+        if not isinstance(initial_capacity, int):
+            raise TypeError("Initial capacity must be of Type: int")
+        if not isinstance(probability, float):
+            raise TypeError("Probability must be of Type: float")
+        if not isinstance(growth, int):
+            raise TypeError("Growth must be of Type: int")
+        if not isinstance(ratio, float):
+            raise TypeError("Ratio must be of Type: float")
+        if initial_capacity <= 0:
+            raise ValueError("Initial capacity must be represented by a positive integer")
+        if not (0.00 < probability < 1.00):
+            raise ValueError("Probability must be betweenn 0.00 - 1.00")
+        if growth <= 0:
+            raise ValueError("Growth must be represented by a positive integer")
+        if not (0.00 < ratio < 1.00):
+            raise ValueError("Ratio must be between 0.00 - 1.00")
+        if seed is not None and not isinstance(seed, int):
+            raise TypeError("Seed must be of Type: int")
+        if seed is not None and not (0 <= seed < 2**64):
+            raise ValueError("Seed must be between 0 and 2**64 - 1")
+
+        self._inner = _RustScalableBloomFilter(
+            initial_capacity=initial_capacity,
+            probability=probability,
+            growth=growth,
+            ratio=ratio,
+            seed=seed
+        )
+
+    def add(self, item: Any) -> bool:
+        return self._inner.add(item)
+
+    def contains(self, item: Any) -> bool:
+        return self._inner.contains(item)
+
+    def len(self) -> int:
+        return self._inner.len()
+
+    def capacity(self) -> int:
+        return self._inner.capacity()
+
+    def slice_count(self) -> int:
+        return self._inner.slice_count()
+
+    def false_positive_rate(self) -> float:
+        return self._inner.false_positive_rate()
+
+    def hash_scheme(self) -> str:
+        return self._inner.hash_scheme()
+
+    def seed(self) -> Optional[int]:
+        return self._inner.seed()
+
+    def clear(self) -> None:
+        self._inner.clear()
+
+    def __len__(self) -> int:
+        return self._inner.len()
+
+    def __contains__(self, item: Any) -> bool:
+        return self._inner.contains(item)
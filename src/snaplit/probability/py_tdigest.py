@@ -0,0 +1,160 @@
+#---------- Imports ----------
+
+from _rust_snaplit import TDigest as _RustTDigest
+
+from typing import Iterable, List, Optional
+
+#---------- TDigest Shim ----------
+
+class TDigest():
+    """
+    A mergeable streaming sketch estimating quantiles and cumulative distributions of numeric values.
+
+    This TDigest class implements a Rust-powered backend of the merging t-digest. Values are buffered and
+    merged into a sorted list of weighted centroids, which stay small in the tails of the distribution and
+    grow towards the median. Extreme percentiles such as p99 and p999 therefore stay accurate while the whole
+    digest only holds about 'compression' centroids, regardless of the number of values added.
+
+    ----- Parameters -----
+
+    compression: Optional[float] = 100.0
+        Accuracy knob bounding the number of centroids.
+        Higher values lower the estimation error but increase memory usage.
+        Must be a number of at least 10.
+
+    ----- Methods -----
+
+    add(value: float, weight: float = 1.0) -> None:
+        Adds a value with an optional positive weight to the digest.
+
+    add_batch(values: Iterable[float]) -> None:
+        Adds every value of an iterable with a weight of 1.
+
+    merge(other: TDigest) -> None:
+        Folds another digest into the current one, as if all of its values were added here.
+
+    quantile(q: float) -> float:
+        Returns the estimated value at quantile q (0.0 - 1.0), e.g. 0.99 for p99.
+
+    quantiles(qs: Iterable[float]) -> List[float]:
+        Returns the estimated values of several quantiles at once.
+
+    cdf(x: float) -> float:
+        Returns the estimated fraction of the total weight at or below x.
+
+    total_weight() -> float:
+        Returns the combined weight of all values added.
+
+    min() -> float:
+        Returns the smallest value added.
+
+    max() -> float:
+        Returns the largest value added.
+
+    compression() -> float:
+        Returns the compression of the digest.
+
+    centroid_count() -> int:
+        Returns the number of centroids currently held.
+
+    is_empty() -> bool:
+        Checks if no value has been added yet.
+
+    clear() -> None:
+        Resets the digest, removing all values.
+
+    to_bytes() -> bytes:
+        Serialises the digest into a compact, versioned binary blob.
+
+    from_bytes(data: bytes) -> TDigest:
+        Class method - Rebuilds a digest from a blob produced by 'to_bytes()'.
+
+    save(path: str) -> None:
+        Writes the serialised digest to the specified file path.
+
+    load(path: str) -> TDigest:
+        Class method - Reads a digest previously written with 'save()'.
+
+    __len__() -> int:
+        Returns the combined weight of all values, rounded to the nearest integer.
+
+    ----- Example -----
+
+    >>> latencies = TDigest()
+    >>> latencies.add_batch(range(1, 10001))
+
+    >>> print([round(value) for value in latencies.quantiles([0.5, 0.99, 0.999])])
+    [5000, 9900, 9990]
+    """
+
+    def __init__(self, compression: Optional[float]=100.0):
+        if not isinstance(compression, (int, float)):
+            raise TypeError("Compression must be of Type: float")
+        if compression < 10:
+            raise ValueError("Compression must be a number of at least 10")
+
+        self._inner = _RustTDigest(compression=float(compression))
+
+    def add(self, value: float, weight: float=1.0) -> None:
+        self._inner.add(value, weight)
+
+    def add_batch(self, values: Iterable[float]) -> None:
+        self._inner.add_batch(list(values))
+
+    def merge(self, other: "TDigest") -> None:
+        if not isinstance(other, TDigest):
+            raise TypeError("Other must be of Type: TDigest")
+        self._inner.merge(other._inner)
+
+    def quantile(self, q: float) -> float:
+        return self._inner.quantile(q)
+
+    def quantiles(self, qs: Iterable[float]) -> List[float]:
+        return self._inner.quantiles(list(qs))
+
+    def cdf(self, x: float) -> float:
+        return self._inner.cdf(x)
+
+    def total_weight(self) -> float:
+        return self._inner.total_weight()
+
+    def min(self) -> float:
+        return self._inner.min()
+
+    def max(self) -> float:
+        return self._inner.max()
+
+    def compression(self) -> float:
+        return self._inner.compression()
+
+    def centroid_count(self) -> int:
+        return self._inner.centroid_count()
+
+    def is_empty(self) -> bool:
+        return self._inner.is_empty()
+
+    def clear(self) -> None:
+        self._inner.clear()
+
+    def to_bytes(self) -> bytes:
+        return self._inner.to_bytes()
+
+    @classmethod
+    def from_bytes(cls, data: bytes) -> "TDigest":
+        if not isinstance(data, (bytes, bytearray)):
+            raise TypeError("Data must be of Type: bytes")
+        new_digest = cls.__new__(cls)
+        new_digest._inner = _RustTDigest.from_bytes(bytes(data))
+        return new_digest
+
+    def save(self, path: str) -> None:
+        self._inner.save(str(path))
+
+    @classmethod
+    def load(cls, path: str) -> "TDigest":
+        new_digest = cls.__new__(cls)
+        new_digest._inner = _RustTDigest.load(str(path))
+        return new_digest
+
+    def __len__(self) -> int:
+        return len(self._inner)
@@ -0,0 +1,128 @@
+#---------- Imports ----------
+
+from _rust_snaplit import TopK as _RustTopK
+
+from typing import Any, List, Optional, Tuple
+
+#---------- TopK Shim ----------
+
+class TopK():
+    """
+    A bounded-memory summary tracking the most frequent items of a stream.
+
+    This TopK class implements a Rust-powered backend of the Space-Saving algorithm. It keeps at most 'k'
+    counters; an unseen item takes over the counter with the smallest count and inherits that count as its
+    error. Every reported count overestimates the true frequency by at most its error, and no error exceeds
+    total / k - so items occurring more than total / k times are never missed.
+
+    ----- Parameters -----
+
+    k: int
+        Maximum number of counters tracked at once.
+        Higher values tighten the error bound but increase memory usage.
+        Must be a positive integer.
+
+    ----- Methods -----
+
+    add(item: Any, count: int = 1) -> Optional[Any]:
+        Counts a hashable item, returning the item evicted from the summary (if any).
+
+    top(n: Optional[int] = None) -> List[Tuple[Any, int, int]]:
+        Returns the n most frequent (item, count, error) tuples, most frequent first.
+        The true frequency of each item lies between 'count - error' and 'count'.
+
+    estimate(item: Any) -> int:
+        Returns the estimated count of an item - the smallest tracked count if it's untracked.
+
+    error(item: Any) -> int:
+        Returns the maximum overestimation of an item's count.
+
+    merge(other: TopK) -> None:
+        Folds another summary into the current one, as if its stream was added here.
+
+    contains(item: Any) -> bool:
+        Checks if an item is currently tracked.
+
+    k() -> int:
+        Returns the maximum number of counters.
+
+    total() -> int:
+        Returns the combined count of every item added.
+
+    max_error() -> int:
+        Returns the largest possible overestimation of any count (the smallest tracked count).
+
+    is_empty() -> bool:
+        Checks if no item has been added yet.
+
+    clear() -> None:
+        Resets the summary, removing all counters.
+
+    __len__() -> int:
+        Returns the number of items currently tracked.
+
+    __contains__(item: Any) -> bool:
+        Enables use of 'in' keyword to check if an item is tracked.
+
+    ----- Example -----
+
+    >>> trending = TopK(k=100)
+    >>> for tag in ["rust", "python", "rust", "go", "rust", "python"]:
+    ...     trending.add(tag)
+
+    >>> print(trending.top(2))
+    [('rust', 3, 0), ('python', 2, 0)]
+    """
+
+    def __init__(self, k: int):
+        if not isinstance(k, int):
+            raise TypeError("K must be of Type: int")
+        if k <= 0:
+            raise ValueError("K must be a positive integer")
+
+        self._inner = _RustTopK(k)
+
+    def add(self, item: Any, count: int=1) -> Optional[Any]:
+        if not isinstance(count, int):
+            raise TypeError("Count must be of Type: int")
+        if count <= 0:
+            raise ValueError("Count must be a positive integer")
+        return self._inner.add(item, count)
+
+    def top(self, n: Optional[int]=None) -> List[Tuple[Any, int, int]]:
+        return self._inner.top(n)
+
+    def estimate(self, item: Any) -> int:
+        return self._inner.estimate(item)
+
+    def error(self, item: Any) -> int:
+        return self._inner.error(item)
+
+    def merge(self, other: "TopK") -> None:
+        if not isinstance(other, TopK):
+            raise TypeError("Other must be of Type: TopK")
+        self._inner.merge(other._inner)
+
+    def contains(self, item: Any) -> bool:
+        return self._inner.contains(item)
+
+    def k(self) -> int:
+        return self._inner.k()
+
+    def total(self) -> int:
+        return self._inner.total()
+
+    def max_error(self) -> int:
+        return self._inner.max_error()
+
+    def is_empty(self) -> bool:
+        return self._inner.is_empty()
+
+    def clear(self) -> None:
+        self._inner.clear()
+
+    def __len__(self) -> int:
+        return len(self._inner)
+
+    def __contains__(self, item: Any) -> bool:
+        return self._inner.contains(item)
@@ -1,5 +1,6 @@
 #---------- Imports ----------
 
+import copy
 from _rust_snaplit import AVLTree as _RustAVL
 
 from typing import Any, List, Iterable, Iterator
@@ -98,6 +99,10 @@ class AVLTree():
         Enables the use of Python's internal 'copy()' functionality.
         Returns a new instance of the current AVL.
 
+    __deepcopy__(memo: dict) -> AVLTree:
+        Enables the use of Python's internal 'copy.deepcopy()' functionality.
+        Returns a new instance holding recursive copies of all stored values.
+
     ----- Example -----
 
     >>> avl = AVLTree(allow_duplicates=allow_duplicates)
@@ -209,3 +214,8 @@ class AVLTree():
     def __copy__(self) -> "AVLTree":
         return self._inner.bfs_list()
 
+    def __deepcopy__(self, memo: dict) -> "AVLTree":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance
@@ -1,5 +1,6 @@
 #---------- Imports ----------
 
+import copy
 from _rust_snaplit import BinarySearchTree as _RustBST
 
 from typing import Any, List, Iterable
@@ -105,6 +106,10 @@ class BinarySearchTree():
         Enables the use of Python's internal 'copy()' functionality.
         Returns a new instance of the current BST. 
 
+    __deepcopy__(memo: dict) -> BinarySearchTree:
+        Enables the use of Python's internal 'copy.deepcopy()' functionality.
+        Returns a new instance holding recursive copies of all stored values.
+
     ----- Example -----
 
     >>> bst = BinarySearchTree(allow_duplicates=False)
@@ -199,4 +204,10 @@ class BinarySearchTree():
         return iter(self.inorder_list())
 
     def __copy__(self) -> "BinarySearchTree":
-        return self.copy()
\ No newline at end of file
+        return self.copy()
+
+    def __deepcopy__(self, memo: dict) -> "BinarySearchTree":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance
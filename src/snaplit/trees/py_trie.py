@@ -1,5 +1,6 @@
 #---------- Imports ----------
 
+import copy
 from _rust_snaplit import Trie as _RustTrie
 
 from typing import List, Iterable, Iterator
@@ -86,6 +87,10 @@ class Trie():
         Enables the use of Python's internal 'copy()' functionality.
         Returns a new instance of the current Trie structure.
 
+    __deepcopy__(memo: dict) -> Trie:
+        Enables the use of Python's internal 'copy.deepcopy()' functionality.
+        Returns a new instance holding recursive copies of all stored values.
+
     ----- Example -----
 
     >>> trie = Trie()
@@ -182,4 +187,10 @@ class Trie():
         return self._inner.words()
     
     def __copy__(self) -> "Trie":
-        return self._inner.copy()
\ No newline at end of file
+        return self._inner.copy()
+
+    def __deepcopy__(self, memo: dict) -> "Trie":
+        new_instance = self.__class__.__new__(self.__class__)
+        memo[id(self)] = new_instance
+        new_instance._inner = copy.deepcopy(self._inner, memo)
+        return new_instance